@@ -1,7 +1,16 @@
-use clap::{command, Command};
+use anyhow::anyhow;
+use clap::{command, Arg, ArgMatches, Args, Command, FromArgMatches};
 use yas::utils::press_any_key_to_continue;
+use yas::game_info::{Platform, UI};
+use yas::ocr::{ImageToText, OcrModelConfig};
+use yas::positioning::Size;
+use yas::window_info::{CalibrateConfig, Calibrator, DebugLayoutConfig, WindowInfoConfig, WindowInfoRepository, WindowInfoType};
+use yas_genshin::scanner::GenshinArtifactScanner;
+use yas_starrail::scanner::relic_scanner::StarRailRelicScanner;
+use yas_wutheringwaves::scanner::WWEchoScanner;
 use yas_genshin::application::ArtifactScannerApplication;
 use yas_starrail::application::RelicScannerApplication;
+use yas_wutheringwaves::application::WWEchoScannerApplication;
 
 fn get_genshin_command() -> Command {
     let cmd = ArtifactScannerApplication::build_command();
@@ -13,6 +22,75 @@ fn get_starrail_command() -> Command {
     cmd.name("starrail")
 }
 
+fn game_arg() -> Arg {
+    Arg::new("game")
+        .long("game")
+        .help("游戏")
+        .value_parser(["genshin", "starrail", "wutheringwaves"])
+        .required(true)
+}
+
+/// The built-in window info of the game, merged with the files of `--window-info`
+fn get_window_info_repository(matches: &ArgMatches) -> anyhow::Result<WindowInfoRepository> {
+    let mut repo = match matches.get_one::<String>("game").map(|x| x.as_str()) {
+        Some("genshin") => ArtifactScannerApplication::get_window_info_repository(),
+        Some("starrail") => RelicScannerApplication::get_window_info_repository(),
+        Some("wutheringwaves") => WWEchoScannerApplication::get_window_info_repository(),
+        game => return Err(anyhow!("unknown game {:?}", game)),
+    };
+    WindowInfoConfig::from_arg_matches(matches)?.merge_into(&mut repo)?;
+    Ok(repo)
+}
+
+fn get_calibrate_command() -> Command {
+    let mut cmd = Command::new("calibrate")
+        .about("根据截图推算新分辨率的窗口布局")
+        .arg(game_arg());
+    cmd = <CalibrateConfig as Args>::augment_args(cmd);
+    cmd = <WindowInfoConfig as Args>::augment_args(cmd);
+    cmd
+}
+
+fn run_calibrate(matches: &ArgMatches) -> anyhow::Result<()> {
+    let repo = get_window_info_repository(matches)?;
+    let config = CalibrateConfig::from_arg_matches(matches)?;
+    Calibrator::new(&repo, config).run()?;
+    Ok(())
+}
+
+fn get_debug_layout_command() -> Command {
+    let mut cmd = Command::new("debug-layout")
+        .about("在截图上绘制并标注所有窗口布局区域，并识别各区域的文字")
+        .arg(game_arg());
+    cmd = <DebugLayoutConfig as Args>::augment_args(cmd);
+    cmd = <WindowInfoConfig as Args>::augment_args(cmd);
+    cmd = <OcrModelConfig as Args>::augment_args(cmd);
+    cmd
+}
+
+type ResolveWindowInfo = fn(&WindowInfoRepository, Size<usize>, UI, Platform) -> anyhow::Result<Vec<(String, WindowInfoType)>>;
+type BuildImageToText = fn(&OcrModelConfig) -> anyhow::Result<Box<dyn ImageToText<image::RgbImage> + Send>>;
+
+fn run_debug_layout(matches: &ArgMatches) -> anyhow::Result<()> {
+    let repo = get_window_info_repository(matches)?;
+    let (resolve, build_model): (ResolveWindowInfo, BuildImageToText) = match matches.get_one::<String>("game").map(|x| x.as_str()) {
+        Some("genshin") => (GenshinArtifactScanner::window_info_values, GenshinArtifactScanner::get_image_to_text),
+        Some("starrail") => (StarRailRelicScanner::window_info_values, StarRailRelicScanner::get_image_to_text),
+        _ => (WWEchoScanner::window_info_values, WWEchoScanner::get_image_to_text),
+    };
+
+    let config = DebugLayoutConfig::from_arg_matches(matches)?;
+    let model = if config.no_ocr {
+        None
+    } else {
+        Some(build_model(&OcrModelConfig::from_arg_matches(matches)?)?)
+    };
+    config.run(
+        |window_size, ui, platform| resolve(&repo, window_size, ui, platform),
+        model.as_ref().map(|m| m.as_ref() as &dyn ImageToText<image::RgbImage>),
+    )
+}
+
 fn init() {
     env_logger::Builder::new()
         .filter_level(log::LevelFilter::Info)
@@ -23,7 +101,9 @@ pub fn main() {
     init();
     let cmd = command!()
         .subcommand(get_genshin_command())
-        .subcommand(get_starrail_command());
+        .subcommand(get_starrail_command())
+        .subcommand(get_calibrate_command())
+        .subcommand(get_debug_layout_command());
     let arg_matches = cmd.get_matches();
 
     let res = if let Some((subcommand_name, matches)) = arg_matches.subcommand() {
@@ -33,6 +113,10 @@ pub fn main() {
         } else if subcommand_name == "starrail" {
             let application = RelicScannerApplication::new(matches.clone());
             application.run()
+        } else if subcommand_name == "calibrate" {
+            run_calibrate(matches)
+        } else if subcommand_name == "debug-layout" {
+            run_debug_layout(matches)
         } else {
             Ok(())
         }
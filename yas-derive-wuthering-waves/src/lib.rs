@@ -28,8 +28,13 @@ fn echo_name_from_chs(data: &[EchoDataItem], echo_names: &[proc_macro2::TokenStr
         });
     }
 
+    let chs_names_len = chs_names.len();
+
     quote! {
         impl WWEchoName {
+            /// The names accepted by `from_chs`
+            pub const CHS_NAMES: [&'static str; #chs_names_len] = [#(#chs_names),*];
+
             pub fn from_chs(chs: &str) -> Option<Self> {
                 match chs {
                     #(#temp)*
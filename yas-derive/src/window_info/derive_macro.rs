@@ -2,52 +2,202 @@ use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
 use crate::window_info::WindowInfoNestedAttributes;
 
+/// The `T` of an `Option<T>` field
+fn option_inner_type(ty: &syn::Type) -> Option<&syn::Type> {
+    let syn::Type::Path(type_path) = ty else { return None };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else { return None };
+    match args.args.first() {
+        Some(syn::GenericArgument::Type(inner)) if args.args.len() == 1 => Some(inner),
+        _ => None,
+    }
+}
+
 pub fn yas_window_info(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+    match derive_window_info(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn derive_window_info(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let struct_name = &input.ident;
 
-    if let syn::Data::Struct(data_struct) = &input.data {
-        let mut fields = Vec::new();
-        for field in data_struct.fields.iter() {
-            let name = field.ident.as_ref().unwrap();
-
-            let mut window_info_key: String = name.to_string();
-            for attr in field.attrs.iter() {
-                if attr.path().is_ident("window_info") {
-                    let nested_attributes = WindowInfoNestedAttributes::from_attr(attr).unwrap();
-                    if nested_attributes.rename.is_some() {
-                        window_info_key = nested_attributes.rename.clone().unwrap().value();
-                    }
-                }
+    let syn::Data::Struct(data_struct) = &input.data else {
+        return Err(syn::Error::new_spanned(input, "YasWindowInfo can only be derived for structs"));
+    };
+
+    let mut fields = Vec::new();
+    let mut values = Vec::new();
+    let mut required_keys = Vec::new();
+    for field in data_struct.fields.iter() {
+        let name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let attributes = WindowInfoNestedAttributes::from_attrs(&field.attrs)?;
+
+        if attributes.flatten {
+            if attributes.rename.is_some() || attributes.default.is_some() || attributes.exact
+                || attributes.anchor.is_some() || attributes.scale.is_some() {
+                return Err(syn::Error::new_spanned(field, "a flattened window info field only accepts `prefix`"));
             }
+            let prefix = attributes.prefix.map(|p| p.value()).unwrap_or_default();
 
             fields.push(quote! {
-                #name: match repo.get_auto_scale(#window_info_key, window_size, ui, platform) {
+                #name: <#ty as yas::window_info::FromWindowInfoRepository>::from_window_info_repository_with_prefix(
+                    &format!("{}{}", prefix, #prefix), window_size, ui, platform, repo
+                )?
+            });
+            values.push(quote! {
+                values.extend(yas::window_info::WindowInfoValues::window_info_values_with_prefix(
+                    &self.#name, &format!("{}{}", prefix, #prefix)
+                ));
+            });
+            required_keys.push(quote! {
+                keys.extend(<#ty as yas::window_info::RequiredWindowInfoKeys>::required_window_info_keys_with_prefix(
+                    &format!("{}{}", prefix, #prefix)
+                ));
+            });
+            continue;
+        }
+
+        if let Some(prefix) = &attributes.prefix {
+            return Err(syn::Error::new_spanned(prefix, "`prefix` is only used with `flatten`"));
+        }
+
+        let window_info_key = attributes.rename.map(|r| r.value()).unwrap_or_else(|| name.to_string());
+
+        let optional_inner = option_inner_type(ty);
+        if optional_inner.is_some() && attributes.default.is_some() {
+            return Err(syn::Error::new_spanned(field, "an `Option` field cannot have a default"));
+        }
+        let value_type = optional_inner.unwrap_or(ty);
+        let optional = optional_inner.is_some() || attributes.default.is_some();
+
+        let value = if attributes.exact {
+            if attributes.anchor.is_some() || attributes.scale.is_some() {
+                return Err(syn::Error::new_spanned(field, "an `exact` field is not scaled, it cannot have an anchor or a scale mode"));
+            }
+            quote! {
+                repo.get_exact::<#value_type>(&key, window_size, ui, platform)
+            }
+        } else if attributes.anchor.is_some() || attributes.scale.is_some() {
+            let anchor = match &attributes.anchor {
+                Some(anchor) => {
+                    let anchor: syn::Ident = anchor.parse()?;
+                    quote!(yas::window_info::Anchor::#anchor)
+                },
+                None => quote!(Default::default()),
+            };
+            let scale = match &attributes.scale {
+                Some(scale) => {
+                    let scale: syn::Ident = scale.parse()?;
+                    quote!(yas::window_info::ScaleMode::#scale)
+                },
+                None => quote!(Default::default()),
+            };
+            quote! {
+                repo.get_auto_scale_with_layout::<#value_type>(
+                    &key, window_size, ui, platform, yas::window_info::LayoutRule::new(#anchor, #scale)
+                )
+            }
+        } else {
+            quote! {
+                repo.get_auto_scale::<#value_type>(&key, window_size, ui, platform)
+            }
+        };
+
+        let field_value = if optional_inner.is_some() {
+            value
+        } else if let Some(default) = &attributes.default {
+            let default = match default {
+                Some(path) => {
+                    let path: syn::ExprPath = path.parse()?;
+                    quote!(#path)
+                },
+                None => quote!(Default::default),
+            };
+            quote! {
+                #value.unwrap_or_else(#default)
+            }
+        } else {
+            quote! {
+                match #value {
                     None => {
-                        return Err(anyhow::anyhow!("cannot find window info key \"{}\"", #window_info_key));
+                        return Err(yas::window_info::WindowInfoError::MissingKey {
+                            key,
+                            window_size,
+                            ui,
+                            platform,
+                        }.into());
                     },
                     Some(value) => value
                 }
+            }
+        };
+
+        fields.push(quote! {
+            #name: {
+                let key = format!("{}{}", prefix, #window_info_key);
+                #field_value
+            }
+        });
+
+        let type_name = quote!(#value_type).to_string().replace(' ', "");
+        required_keys.push(quote! {
+            keys.push(yas::window_info::RequiredWindowInfoKey {
+                key: format!("{}{}", prefix, #window_info_key),
+                type_name: #type_name,
+                optional: #optional,
+                accepts: |value| TryInto::<#value_type>::try_into(value.clone()).is_ok(),
             });
-        }
+        });
 
-        let trait_impl = quote! {
-            impl yas::window_info::FromWindowInfoRepository for #struct_name {
-                fn from_window_info_repository(
-                    window_size: yas::positioning::Size<usize>,
-                    ui: yas::game_info::UI,
-                    platform: yas::game_info::Platform,
-                    repo: &yas::window_info::WindowInfoRepository
-                ) -> anyhow::Result<Self> {
-                    Ok(Self {
-                        #(#fields),*
-                    })
+        if optional_inner.is_some() {
+            values.push(quote! {
+                if let Some(value) = &self.#name {
+                    values.push((format!("{}{}", prefix, #window_info_key), value.clone().into()));
                 }
+            });
+        } else {
+            values.push(quote! {
+                values.push((format!("{}{}", prefix, #window_info_key), self.#name.clone().into()));
+            });
+        }
+    }
+
+    Ok(quote! {
+        impl yas::window_info::FromWindowInfoRepository for #struct_name {
+            fn from_window_info_repository_with_prefix(
+                prefix: &str,
+                window_size: yas::positioning::Size<usize>,
+                ui: yas::game_info::UI,
+                platform: yas::game_info::Platform,
+                repo: &yas::window_info::WindowInfoRepository
+            ) -> anyhow::Result<Self> {
+                Ok(Self {
+                    #(#fields),*
+                })
             }
-        };
+        }
 
-        return trait_impl.into();
-    }
+        impl yas::window_info::WindowInfoValues for #struct_name {
+            fn window_info_values_with_prefix(&self, prefix: &str) -> Vec<(String, yas::window_info::WindowInfoType)> {
+                let mut values = Vec::new();
+                #(#values)*
+                values
+            }
+        }
 
-    proc_macro::TokenStream::new()
-}
\ No newline at end of file
+        impl yas::window_info::RequiredWindowInfoKeys for #struct_name {
+            fn required_window_info_keys_with_prefix(prefix: &str) -> Vec<yas::window_info::RequiredWindowInfoKey> {
+                let mut keys = Vec::new();
+                #(#required_keys)*
+                keys
+            }
+        }
+    })
+}
@@ -3,26 +3,81 @@ use syn::{token, Token};
 #[derive(Default)]
 pub struct WindowInfoNestedAttributes {
     pub rename: Option<syn::LitStr>,
+    /// `default` uses `Default::default`, `default = "path"` calls the function at the path
+    pub default: Option<Option<syn::LitStr>>,
+    /// read the value recorded for the window size, without scaling
+    pub exact: bool,
+    pub anchor: Option<syn::LitStr>,
+    pub scale: Option<syn::LitStr>,
+    /// read a nested window info struct, with its keys prefixed by `prefix`
+    pub flatten: bool,
+    pub prefix: Option<syn::LitStr>,
+}
+
+fn parse_lit_str(meta: &syn::meta::ParseNestedMeta) -> syn::parse::Result<syn::LitStr> {
+    let _eq: Token![=] = meta.input.parse()?;
+    meta.input.parse()
 }
 
 impl WindowInfoNestedAttributes {
-    pub fn from_attr(attr: &syn::Attribute) -> syn::parse::Result<Self> {
+    /// Merge all the `window_info` attributes of a field
+    pub fn from_attrs(attrs: &[syn::Attribute]) -> syn::parse::Result<Self> {
         let mut result: WindowInfoNestedAttributes = Default::default();
+        for attr in attrs.iter() {
+            if attr.path().is_ident("window_info") {
+                result.parse_attr(attr)?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn parse_attr(&mut self, attr: &syn::Attribute) -> syn::parse::Result<()> {
         attr.parse_nested_meta( |meta| {
             if meta.path.is_ident("rename") {
                 if meta.input.peek(token::Eq) {
-                    let _eq: Token![=] = meta.input.parse()?;
-                    let expr: syn::LitStr = meta.input.parse()?;
-                    result.rename = Some(expr);
+                    self.rename = Some(parse_lit_str(&meta)?);
                     return Ok(());
                 }
-                result.rename = None;
+                self.rename = None;
                 return Ok(());
             }
 
-            Err(meta.error("unrecognized window_info"))
-        })?;
+            if meta.path.is_ident("default") {
+                if meta.input.peek(token::Eq) {
+                    self.default = Some(Some(parse_lit_str(&meta)?));
+                } else {
+                    self.default = Some(None);
+                }
+                return Ok(());
+            }
 
-        Ok(result)
+            if meta.path.is_ident("exact") {
+                self.exact = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("anchor") {
+                self.anchor = Some(parse_lit_str(&meta)?);
+                return Ok(());
+            }
+
+            if meta.path.is_ident("scale") {
+                self.scale = Some(parse_lit_str(&meta)?);
+                return Ok(());
+            }
+
+            if meta.path.is_ident("flatten") {
+                self.flatten = true;
+                return Ok(());
+            }
+
+            if meta.path.is_ident("prefix") {
+                self.prefix = Some(parse_lit_str(&meta)?);
+                return Ok(());
+            }
+
+            Err(meta.error("unrecognized window_info"))
+        })
     }
 }
@@ -1,11 +1,14 @@
 use anyhow::Result;
 use anyhow::anyhow;
-use clap::{command, ArgMatches, Args};
+use clap::{command, ArgMatches, Args, FromArgMatches};
 use log::info;
 
+use yas::capture::CapturerConfig;
+use yas::ocr::OcrModelConfig;
 use yas::export::{AssetEmitter, ExportAssets};
-use yas::game_info::{GameInfo, GameInfoBuilder};
-use yas::window_info::{load_window_info_repo, WindowInfoRepository};
+use yas::text::TextMatchConfig;
+use yas::game_info::{GameInfo, GameInfoBuilder, GameInfoConfig};
+use yas::window_info::{load_window_info_repo, WindowInfoConfig, WindowInfoRepository};
 
 use crate::artifact::GenshinArtifact;
 use crate::export::artifact::{ExportArtifactConfig, GenshinArtifactExporter};
@@ -28,10 +31,16 @@ impl ArtifactScannerApplication {
         cmd = <ExportArtifactConfig as Args>::augment_args_for_update(cmd);
         cmd = <GenshinArtifactScannerConfig as Args>::augment_args_for_update(cmd);
         cmd = <GenshinRepositoryScannerLogicConfig as Args>::augment_args_for_update(cmd);
+        cmd = <CapturerConfig as Args>::augment_args_for_update(cmd);
+        cmd = <GameInfoConfig as Args>::augment_args_for_update(cmd);
+        cmd = <WindowInfoConfig as Args>::augment_args_for_update(cmd);
+        cmd = <OcrModelConfig as Args>::augment_args_for_update(cmd);
+        cmd = <TextMatchConfig as Args>::augment_args_for_update(cmd);
         cmd
     }
 
-    fn get_window_info_repository() -> WindowInfoRepository {
+    /// The built-in window info
+    pub fn get_window_info_repository() -> WindowInfoRepository {
         load_window_info_repo!(
             "../../window_info/windows1600x900.json",
             "../../window_info/windows1280x960.json",
@@ -47,21 +56,29 @@ impl ArtifactScannerApplication {
     //         .init();
     // }
 
-    fn get_game_info() -> Result<GameInfo> {
-        let game_info = GameInfoBuilder::new()
+    fn get_game_info(arg_matches: &ArgMatches) -> Result<GameInfo> {
+        let mut builder = GameInfoBuilder::new();
+        builder
             .add_local_window_name("原神")
             .add_local_window_name("Genshin Impact")
             .add_cloud_window_name("云·原神")
-            .build();
-        game_info
+            .add_window_class("genshinimpact.exe")
+            .add_window_class("yuanshen.exe");
+        GameInfoConfig::from_arg_matches(arg_matches)?.apply_to(&mut builder);
+        builder.build()
     }
 }
 
 impl ArtifactScannerApplication {
     pub fn run(&self) -> Result<()> {
         let arg_matches = &self.arg_matches;
-        let window_info_repository = Self::get_window_info_repository();
-        let game_info = Self::get_game_info()?;
+        let mut window_info_repository = Self::get_window_info_repository();
+        WindowInfoConfig::from_arg_matches(arg_matches)?.merge_into(&mut window_info_repository)?;
+        let capturer_config = CapturerConfig::from_arg_matches(arg_matches)?;
+        let game_info = match capturer_config.replay_game_info()? {
+            Some(game_info) => game_info,
+            None => Self::get_game_info(arg_matches)?,
+        };
 
         info!("window: {:?}", game_info.window);
         info!("ui: {:?}", game_info.ui);
@@ -82,10 +99,11 @@ impl ArtifactScannerApplication {
             game_info.clone()
         )?;
 
+        let text_match_config = TextMatchConfig::from_arg_matches(arg_matches)?;
         let result = scanner.scan()?;
         let artifacts = result
             .iter()
-            .flat_map(GenshinArtifact::try_from)
+            .flat_map(|x| GenshinArtifact::from_scan_result(x, text_match_config.max_distance))
             .collect::<Vec<_>>();
 
         let exporter = GenshinArtifactExporter::new(arg_matches, &artifacts)?;
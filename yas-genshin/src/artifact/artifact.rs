@@ -1,8 +1,10 @@
 use std::hash::{Hash, Hasher};
 
+use lazy_static::lazy_static;
 use log::error;
 use regex::Regex;
 use strum_macros::Display;
+use yas::text::FuzzyMatcher;
 
 use crate::character::CHARACTER_NAMES;
 use crate::scanner::GenshinArtifactScanResult;
@@ -142,44 +144,85 @@ impl PartialEq for ArtifactStat {
 
 impl Eq for ArtifactStat {}
 
-impl ArtifactStatName {
-    #[rustfmt::skip]
-    pub fn from_zh_cn(name: &str, is_percentage: bool) -> Option<ArtifactStatName> {
-        match name {
-            "治疗加成" => Some(ArtifactStatName::HealingBonus),
-            "暴击伤害" => Some(ArtifactStatName::CriticalDamage),
-            "暴击率" => Some(ArtifactStatName::Critical),
-            "攻击力" => if is_percentage { Some(ArtifactStatName::AtkPercentage) } else { Some(ArtifactStatName::Atk) },
-            "元素精通" => Some(ArtifactStatName::ElementalMastery),
-            "元素充能效率" => Some(ArtifactStatName::Recharge),
-            "生命值" => if is_percentage { Some(ArtifactStatName::HpPercentage) } else { Some(ArtifactStatName::Hp) },
-            "防御力" => if is_percentage { Some(ArtifactStatName::DefPercentage) } else { Some(ArtifactStatName::Def) },
-            "雷元素伤害加成" => Some(ArtifactStatName::ElectroBonus),
-            "火元素伤害加成" => Some(ArtifactStatName::PyroBonus),
-            "水元素伤害加成" => Some(ArtifactStatName::HydroBonus),
-            "冰元素伤害加成" => Some(ArtifactStatName::CryoBonus),
-            "风元素伤害加成" => Some(ArtifactStatName::AnemoBonus),
-            "岩元素伤害加成" => Some(ArtifactStatName::GeoBonus),
-            "草元素伤害加成" => Some(ArtifactStatName::DendroBonus),
-            "物理伤害加成" => Some(ArtifactStatName::PhysicalBonus),
-            _ => None,
+lazy_static! {
+    static ref STAT_NAME_MATCHER: FuzzyMatcher<&'static str> =
+        FuzzyMatcher::new(ArtifactStatName::ZH_CN_NAMES.iter().map(|&name| (name, name)));
+
+    static ref ARTIFACT_NAME_MATCHER: FuzzyMatcher<(ArtifactSetName, ArtifactSlot)> =
+        FuzzyMatcher::new(ArtifactSetName::ZH_CN_NAMES.iter().filter_map(|&name| {
+            Some((name, (ArtifactSetName::from_zh_cn(name)?, ArtifactSlot::from_zh_cn(name)?)))
+        }));
+
+    static ref CHARACTER_NAME_MATCHER: FuzzyMatcher<&'static str> =
+        FuzzyMatcher::new(CHARACTER_NAMES.iter().map(|&name| (name, name)));
+}
+
+/// Generates `ArtifactStatName::ZH_CN_NAMES` and `ArtifactStatName::from_zh_cn` from one list,
+/// a stat with a percentage form is written as `flat / percentage`
+macro_rules! stat_names {
+    ($($name:literal => $stat:ident $(/ $percentage:ident)?,)*) => {
+        impl ArtifactStatName {
+            /// The names accepted by `from_zh_cn`
+            pub const ZH_CN_NAMES: &'static [&'static str] = &[$($name),*];
+
+            pub fn from_zh_cn(name: &str, is_percentage: bool) -> Option<ArtifactStatName> {
+                match name {
+                    $($name => Some(stat_names!(@pick is_percentage, $stat $(, $percentage)?)),)*
+                    _ => None,
+                }
+            }
         }
+    };
+    (@pick $is_percentage:ident, $stat:ident) => {
+        ArtifactStatName::$stat
+    };
+    (@pick $is_percentage:ident, $stat:ident, $percentage:ident) => {
+        if $is_percentage { ArtifactStatName::$percentage } else { ArtifactStatName::$stat }
+    };
+}
+
+stat_names! {
+    "治疗加成" => HealingBonus,
+    "暴击伤害" => CriticalDamage,
+    "暴击率" => Critical,
+    "攻击力" => Atk / AtkPercentage,
+    "元素精通" => ElementalMastery,
+    "元素充能效率" => Recharge,
+    "生命值" => Hp / HpPercentage,
+    "防御力" => Def / DefPercentage,
+    "雷元素伤害加成" => ElectroBonus,
+    "火元素伤害加成" => PyroBonus,
+    "水元素伤害加成" => HydroBonus,
+    "冰元素伤害加成" => CryoBonus,
+    "风元素伤害加成" => AnemoBonus,
+    "岩元素伤害加成" => GeoBonus,
+    "草元素伤害加成" => DendroBonus,
+    "物理伤害加成" => PhysicalBonus,
+}
+
+impl ArtifactStatName {
+    /// Like `from_zh_cn`, but a name within `max_distance` edits of a known name is accepted too
+    pub fn from_zh_cn_fuzzy(name: &str, is_percentage: bool, max_distance: usize) -> Option<ArtifactStatName> {
+        let name = STAT_NAME_MATCHER.find(name, max_distance)?;
+        ArtifactStatName::from_zh_cn(name, is_percentage)
     }
 }
 
 impl ArtifactStat {
     // e.g "生命值+4,123", "暴击率+10%"
     pub fn from_zh_cn_raw(s: &str) -> Option<ArtifactStat> {
+        ArtifactStat::from_zh_cn_raw_fuzzy(s, 0)
+    }
+
+    /// Like `from_zh_cn_raw`, tolerating up to `max_distance` wrong characters in the stat name
+    pub fn from_zh_cn_raw_fuzzy(s: &str, max_distance: usize) -> Option<ArtifactStat> {
         let temp: Vec<&str> = s.split('+').collect();
         if temp.len() != 2 {
             return None;
         }
 
         let is_percentage = temp[1].contains('%');
-        let stat_name = match ArtifactStatName::from_zh_cn(temp[0], is_percentage) {
-            Some(v) => v,
-            None => return None,
-        };
+        let stat_name = ArtifactStatName::from_zh_cn_fuzzy(temp[0], is_percentage, max_distance)?;
 
         let re = Regex::new("[%,]").unwrap();
         let mut value = match re.replace_all(temp[1], "").parse::<f64>() {
@@ -204,35 +247,37 @@ impl TryFrom<&GenshinArtifactScanResult> for GenshinArtifact {
     type Error = ();
 
     fn try_from(value: &GenshinArtifactScanResult) -> Result<Self, Self::Error> {
-        let set_name = ArtifactSetName::from_zh_cn(&value.name).ok_or(())?;
-        let slot = ArtifactSlot::from_zh_cn(&value.name).ok_or(())?;
+        GenshinArtifact::from_scan_result(value, 0).ok_or(())
+    }
+}
+
+impl GenshinArtifact {
+    /// Convert a scan result, the recognized names may be up to `max_distance` edits away from the known names
+    pub fn from_scan_result(value: &GenshinArtifactScanResult, max_distance: usize) -> Option<Self> {
+        let (set_name, slot) = ARTIFACT_NAME_MATCHER.find(&value.name, max_distance).cloned()?;
         let star = value.star;
         let lock = value.lock;
 
-        let main_stat = ArtifactStat::from_zh_cn_raw(
+        let main_stat = ArtifactStat::from_zh_cn_raw_fuzzy(
             (value.main_stat_name.clone() + "+" + value.main_stat_value.as_str()).as_str(),
-        )
-        .ok_or(())?;
+            max_distance,
+        )?;
 
-        let sub1 = ArtifactStat::from_zh_cn_raw(&value.sub_stat[0]);
-        let sub2 = ArtifactStat::from_zh_cn_raw(&value.sub_stat[1]);
-        let sub3 = ArtifactStat::from_zh_cn_raw(&value.sub_stat[2]);
-        let sub4 = ArtifactStat::from_zh_cn_raw(&value.sub_stat[3]);
+        let sub1 = ArtifactStat::from_zh_cn_raw_fuzzy(&value.sub_stat[0], max_distance);
+        let sub2 = ArtifactStat::from_zh_cn_raw_fuzzy(&value.sub_stat[1], max_distance);
+        let sub3 = ArtifactStat::from_zh_cn_raw_fuzzy(&value.sub_stat[2], max_distance);
+        let sub4 = ArtifactStat::from_zh_cn_raw_fuzzy(&value.sub_stat[3], max_distance);
 
         let equip = if value.equip.ends_with("已装备") {
             let chars = value.equip.chars().collect::<Vec<_>>();
             let equip_name = chars[..chars.len() - 3].iter().collect::<String>();
 
-            if CHARACTER_NAMES.contains(equip_name.as_str()) {
-                Some(equip_name)
-            } else {
-                None
-            }
+            CHARACTER_NAME_MATCHER.find(&equip_name, max_distance).map(|name| name.to_string())
         } else {
             None
         };
 
-        Ok(GenshinArtifact {
+        Some(GenshinArtifact {
             set_name,
             slot,
             star,
@@ -248,470 +293,88 @@ impl TryFrom<&GenshinArtifactScanResult> for GenshinArtifact {
     }
 }
 
-impl ArtifactSetName {
-    #[rustfmt::skip]
-    pub fn from_zh_cn(s: &str) -> Option<ArtifactSetName> {
-        match s {
-            "磐陀裂生之花" => Some(ArtifactSetName::ArchaicPetra),
-            "嵯峨群峰之翼" => Some(ArtifactSetName::ArchaicPetra),
-            "星罗圭壁之晷" => Some(ArtifactSetName::ArchaicPetra),
-            // "壁" is different
-            "星罗圭璧之晷" => Some(ArtifactSetName::ArchaicPetra),
-            "巉岩琢塑之樽" => Some(ArtifactSetName::ArchaicPetra),
-            "不动玄石之相" => Some(ArtifactSetName::ArchaicPetra),
-            "历经风雪的思念" => Some(ArtifactSetName::BlizzardStrayer),
-            "摧冰而行的执望" => Some(ArtifactSetName::BlizzardStrayer),
-            "冰雪故园的终期" => Some(ArtifactSetName::BlizzardStrayer),
-            "遍结寒霜的傲骨" => Some(ArtifactSetName::BlizzardStrayer),
-            "破冰踏雪的回音" => Some(ArtifactSetName::BlizzardStrayer),
-            "染血的铁之心" => Some(ArtifactSetName::BloodstainedChivalry),
-            "染血的黑之羽" => Some(ArtifactSetName::BloodstainedChivalry),
-            "骑士染血之时" => Some(ArtifactSetName::BloodstainedChivalry),
-            "染血骑士之杯" => Some(ArtifactSetName::BloodstainedChivalry),
-            "染血的铁假面" => Some(ArtifactSetName::BloodstainedChivalry),
-            "魔女的炎之花" => Some(ArtifactSetName::CrimsonWitch),
-            "魔女常燃之羽" => Some(ArtifactSetName::CrimsonWitch),
-            "魔女破灭之时" => Some(ArtifactSetName::CrimsonWitch),
-            "魔女的心之火" => Some(ArtifactSetName::CrimsonWitch),
-            "焦灼的魔女帽" => Some(ArtifactSetName::CrimsonWitch),
-            "角斗士的留恋" => Some(ArtifactSetName::GladiatorFinale),
-            "角斗士的归宿" => Some(ArtifactSetName::GladiatorFinale),
-            "角斗士的希冀" => Some(ArtifactSetName::GladiatorFinale),
-            "角斗士的酣醉" => Some(ArtifactSetName::GladiatorFinale),
-            "角斗士的凯旋" => Some(ArtifactSetName::GladiatorFinale),
-            "饰金胸花" => Some(ArtifactSetName::HeartOfDepth),
-            "追忆之风" => Some(ArtifactSetName::HeartOfDepth),
-            "坚铜罗盘" => Some(ArtifactSetName::HeartOfDepth),
-            "沉波之盏" => Some(ArtifactSetName::HeartOfDepth),
-            "酒渍船帽" => Some(ArtifactSetName::HeartOfDepth),
-            "渡火者的决绝" => Some(ArtifactSetName::LavaWalker),
-            "渡火者的解脱" => Some(ArtifactSetName::LavaWalker),
-            "渡火者的煎熬" => Some(ArtifactSetName::LavaWalker),
-            "渡火者的醒悟" => Some(ArtifactSetName::LavaWalker),
-            "渡火者的智慧" => Some(ArtifactSetName::LavaWalker),
-            "远方的少女之心" => Some(ArtifactSetName::MaidenBeloved),
-            "少女飘摇的思念" => Some(ArtifactSetName::MaidenBeloved),
-            "少女苦短的良辰" => Some(ArtifactSetName::MaidenBeloved),
-            "少女片刻的闲暇" => Some(ArtifactSetName::MaidenBeloved),
-            "少女易逝的芳颜" => Some(ArtifactSetName::MaidenBeloved),
-            "宗室之花" => Some(ArtifactSetName::NoblesseOblige),
-            "宗室之翎" => Some(ArtifactSetName::NoblesseOblige),
-            "宗室时计" => Some(ArtifactSetName::NoblesseOblige),
-            "宗室银瓮" => Some(ArtifactSetName::NoblesseOblige),
-            "宗室面具" => Some(ArtifactSetName::NoblesseOblige),
-            "夏祭之花" => Some(ArtifactSetName::RetracingBolide),
-            "夏祭终末" => Some(ArtifactSetName::RetracingBolide),
-            "夏祭之刻" => Some(ArtifactSetName::RetracingBolide),
-            "夏祭水玉" => Some(ArtifactSetName::RetracingBolide),
-            "夏祭之面" => Some(ArtifactSetName::RetracingBolide),
-            "平雷之心" => Some(ArtifactSetName::ThunderSmoother),
-            "平雷之羽" => Some(ArtifactSetName::ThunderSmoother),
-            "平雷之刻" => Some(ArtifactSetName::ThunderSmoother),
-            "平雷之器" => Some(ArtifactSetName::ThunderSmoother),
-            "平雷之冠" => Some(ArtifactSetName::ThunderSmoother),
-            "雷鸟的怜悯" => Some(ArtifactSetName::ThunderingFury),
-            "雷灾的孑遗" => Some(ArtifactSetName::ThunderingFury),
-            "雷霆的时计" => Some(ArtifactSetName::ThunderingFury),
-            "降雷的凶兆" => Some(ArtifactSetName::ThunderingFury),
-            "唤雷的头冠" => Some(ArtifactSetName::ThunderingFury),
-            "野花记忆的绿野" => Some(ArtifactSetName::ViridescentVenerer),
-            "猎人青翠的箭羽" => Some(ArtifactSetName::ViridescentVenerer),
-            "翠绿猎人的笃定" => Some(ArtifactSetName::ViridescentVenerer),
-            "翠绿猎人的容器" => Some(ArtifactSetName::ViridescentVenerer),
-            "翠绿的猎人之冠" => Some(ArtifactSetName::ViridescentVenerer),
-            "乐团的晨光" => Some(ArtifactSetName::WandererTroupe),
-            "琴师的箭羽" => Some(ArtifactSetName::WandererTroupe),
-            "终幕的时计" => Some(ArtifactSetName::WandererTroupe),
-            "终末的时计" => Some(ArtifactSetName::WandererTroupe),
-            "吟游者之壶" => Some(ArtifactSetName::WandererTroupe),
-            "指挥的礼帽" => Some(ArtifactSetName::WandererTroupe),
-            "战狂的蔷薇" => Some(ArtifactSetName::Berserker),
-            "战狂的翎羽" => Some(ArtifactSetName::Berserker),
-            "战狂的时计" => Some(ArtifactSetName::Berserker),
-            "战狂的骨杯" => Some(ArtifactSetName::Berserker),
-            "战狂的鬼面" => Some(ArtifactSetName::Berserker),
-            "勇士的勋章" => Some(ArtifactSetName::BraveHeart),
-            "勇士的期许" => Some(ArtifactSetName::BraveHeart),
-            "勇士的坚毅" => Some(ArtifactSetName::BraveHeart),
-            "勇士的壮行" => Some(ArtifactSetName::BraveHeart),
-            "勇士的冠冕" => Some(ArtifactSetName::BraveHeart),
-            "守护之花" => Some(ArtifactSetName::DefenderWill),
-            "守护徽印" => Some(ArtifactSetName::DefenderWill),
-            "守护座钟" => Some(ArtifactSetName::DefenderWill),
-            "守护之皿" => Some(ArtifactSetName::DefenderWill),
-            "守护束带" => Some(ArtifactSetName::DefenderWill),
-            "流放者之花" => Some(ArtifactSetName::Exile),
-            "流放者之羽" => Some(ArtifactSetName::Exile),
-            "流放者怀表" => Some(ArtifactSetName::Exile),
-            "流放者之杯" => Some(ArtifactSetName::Exile),
-            "流放者头冠" => Some(ArtifactSetName::Exile),
-            "赌徒的胸花" => Some(ArtifactSetName::Gambler),
-            "赌徒的羽饰" => Some(ArtifactSetName::Gambler),
-            "赌徒的怀表" => Some(ArtifactSetName::Gambler),
-            "赌徒的骰盅" => Some(ArtifactSetName::Gambler),
-            "赌徒的耳环" => Some(ArtifactSetName::Gambler),
-            "教官的胸花" => Some(ArtifactSetName::Instructor),
-            "教官的羽饰" => Some(ArtifactSetName::Instructor),
-            "教官的怀表" => Some(ArtifactSetName::Instructor),
-            "教官的茶杯" => Some(ArtifactSetName::Instructor),
-            "教官的帽子" => Some(ArtifactSetName::Instructor),
-            "武人的红花" => Some(ArtifactSetName::MartialArtist),
-            "武人的羽饰" => Some(ArtifactSetName::MartialArtist),
-            "武人的水漏" => Some(ArtifactSetName::MartialArtist),
-            "武人的酒杯" => Some(ArtifactSetName::MartialArtist),
-            "武人的头巾" => Some(ArtifactSetName::MartialArtist),
-            "祭水礼冠" => Some(ArtifactSetName::PrayersForDestiny),
-            "祭火礼冠" => Some(ArtifactSetName::PrayersForIllumination),
-            "祭雷礼冠" => Some(ArtifactSetName::PrayersForWisdom),
-            "祭冰礼冠" => Some(ArtifactSetName::PrayersToSpringtime),
-            "故人之心" => Some(ArtifactSetName::ResolutionOfSojourner),
-            "归乡之羽" => Some(ArtifactSetName::ResolutionOfSojourner),
-            "逐光之石" => Some(ArtifactSetName::ResolutionOfSojourner),
-            "异国之盏" => Some(ArtifactSetName::ResolutionOfSojourner),
-            "感别之冠" => Some(ArtifactSetName::ResolutionOfSojourner),
-            "学士的书签" => Some(ArtifactSetName::Scholar),
-            "学士的羽笔" => Some(ArtifactSetName::Scholar),
-            "学士的时钟" => Some(ArtifactSetName::Scholar),
-            "学士的墨杯" => Some(ArtifactSetName::Scholar),
-            "学士的镜片" => Some(ArtifactSetName::Scholar),
-            "奇迹之花" => Some(ArtifactSetName::TinyMiracle),
-            "奇迹之羽" => Some(ArtifactSetName::TinyMiracle),
-            "奇迹之沙" => Some(ArtifactSetName::TinyMiracle),
-            "奇迹之杯" => Some(ArtifactSetName::TinyMiracle),
-            "奇迹耳坠" => Some(ArtifactSetName::TinyMiracle),
-            "冒险家之花" => Some(ArtifactSetName::Adventurer),
-            "冒险家尾羽" => Some(ArtifactSetName::Adventurer),
-            "冒险家怀表" => Some(ArtifactSetName::Adventurer),
-            "冒险家金杯" => Some(ArtifactSetName::Adventurer),
-            "冒险家头带" => Some(ArtifactSetName::Adventurer),
-            "幸运儿绿花" => Some(ArtifactSetName::LuckyDog),
-            "幸运儿鹰羽" => Some(ArtifactSetName::LuckyDog),
-            "幸运儿沙漏" => Some(ArtifactSetName::LuckyDog),
-            "幸运儿之杯" => Some(ArtifactSetName::LuckyDog),
-            "幸运儿银冠" => Some(ArtifactSetName::LuckyDog),
-            "游医的银莲" => Some(ArtifactSetName::TravelingDoctor),
-            "游医的枭羽" => Some(ArtifactSetName::TravelingDoctor),
-            "游医的怀钟" => Some(ArtifactSetName::TravelingDoctor),
-            "游医的药壶" => Some(ArtifactSetName::TravelingDoctor),
-            "游医的方巾" => Some(ArtifactSetName::TravelingDoctor),
-            "勋绩之花" => Some(ArtifactSetName::TenacityOfTheMillelith),
-            "昭武翎羽" => Some(ArtifactSetName::TenacityOfTheMillelith),
-            "金铜时晷" => Some(ArtifactSetName::TenacityOfTheMillelith),
-            "盟誓金爵" => Some(ArtifactSetName::TenacityOfTheMillelith),
-            "将帅兜鍪" => Some(ArtifactSetName::TenacityOfTheMillelith),
-            "无垢之花" => Some(ArtifactSetName::PaleFlame),
-            "贤医之羽" => Some(ArtifactSetName::PaleFlame),
-            "停摆之刻" => Some(ArtifactSetName::PaleFlame),
-            "超越之盏" => Some(ArtifactSetName::PaleFlame),
-            "嗤笑之面" => Some(ArtifactSetName::PaleFlame),
-            "明威之镡" => Some(ArtifactSetName::EmblemOfSeveredFate),
-            "切落之羽" => Some(ArtifactSetName::EmblemOfSeveredFate),
-            "雷云之笼" => Some(ArtifactSetName::EmblemOfSeveredFate),
-            "绯花之壶" => Some(ArtifactSetName::EmblemOfSeveredFate),
-            "华饰之兜" => Some(ArtifactSetName::EmblemOfSeveredFate),
-            "羁缠之花" => Some(ArtifactSetName::ShimenawaReminiscence),
-            "思忆之矢" => Some(ArtifactSetName::ShimenawaReminiscence),
-            "朝露之时" => Some(ArtifactSetName::ShimenawaReminiscence),
-            "祈望之心" => Some(ArtifactSetName::ShimenawaReminiscence),
-            "无常之面" => Some(ArtifactSetName::ShimenawaReminiscence),
-            "荣花之期" => Some(ArtifactSetName::HuskOfOpulentDreams),
-            "华馆之羽" => Some(ArtifactSetName::HuskOfOpulentDreams),
-            "众生之谣" => Some(ArtifactSetName::HuskOfOpulentDreams),
-            "梦醒之瓢" => Some(ArtifactSetName::HuskOfOpulentDreams),
-            "形骸之笠" => Some(ArtifactSetName::HuskOfOpulentDreams),
-            "海染之花" => Some(ArtifactSetName::OceanHuedClam),
-            "渊宫之羽" => Some(ArtifactSetName::OceanHuedClam),
-            "离别之贝" => Some(ArtifactSetName::OceanHuedClam),
-            "真珠之笼" => Some(ArtifactSetName::OceanHuedClam),
-            "海祇之冠" => Some(ArtifactSetName::OceanHuedClam),
-            "生灵之华" | "阳辔之遗" | "潜光片羽" | "结契之刻" | "虺雷之姿" => Some(ArtifactSetName::VermillionHereafter),
-            "魂香之花" | "祝祀之凭" | "垂玉之叶" | "涌泉之盏" | "浮溯之珏" => Some(ArtifactSetName::EchoesOfAnOffering),
-            "迷宫的游人" | "翠蔓的智者" | "贤智的定期" | "迷误者之灯" | "月桂的宝冠" => Some(ArtifactSetName::DeepwoodMemories),
-            "梦中的铁花" | "裁断的翎羽" | "沉金的岁月" | "如蜜的终宴" | "沙王的投影" => Some(ArtifactSetName::GildedDreams),
-            "月女的华彩" | "谢落的筵席" | "凝结的时刻" | "守秘的魔瓶" | "紫晶的花冠" => Some(ArtifactSetName::FlowerOfParadiseLost),
-            "众王之都的开端" | "黄金邦国的结末" | "失落迷途的机芯" | "迷醉长梦的守护" | "流沙贵嗣的遗宝" => Some(ArtifactSetName::DesertPavilionChronicle),
-            "恶龙的单片镜" | "坏巫师的羽杖" | "旅途中的鲜花" | "水仙的时时刻刻" | "勇者们的茶会" => Some(ArtifactSetName::NymphsDream),
-            "灵光明烁之心" | "琦色灵彩之羽" | "灵光源起之蕊" | "久远花落之时" | "无边酣乐之筵" => Some(ArtifactSetName::VourukashasGlow),
-            "猎人的胸花" | "杰作的序曲" | "裁判的时刻" | "遗忘的容器" | "老兵的容颜" => Some(ArtifactSetName::MarechausseeHunter),
-            "黄金乐曲的变奏" | "黄金飞鸟的落羽" | "黄金时代的先声" | "黄金之夜的喧嚣" | "黄金剧团的奖赏" => Some(ArtifactSetName::GoldenTroupe),
-            "昔时传奏之诗" | "昔时浮想之思" | "昔时遗落之誓" | "昔时回映之音" | "昔时应许之梦" => Some(ArtifactSetName::SongOfDaysPast),
-            "慈爱的淑女帽" | "诚恳的蘸水笔" | "无私的妆饰花" | "忠实的砂时计" | "慷慨的墨水瓶" => Some(ArtifactSetName::NighttimeWhispersInTheEchoingWoods),
-            "异想零落的圆舞" | "古海玄幽的夜想" | "谐律交响的前奏" | "命途轮转的谐谑" | "灵露倾洒的狂诗" => Some(ArtifactSetName::FragmentOfHarmonicWhimsy),
-            "失冕的宝冠" | "褪光的翠尾" | "暗结的明花" | "举业的识刻" | "筹谋的共樽" => Some(ArtifactSetName::UnfinishedReverie),
-            "魔战士的羽面" | "巡山客的信标" | "驯兽师的护符" | "秘术家的金盘" | "游学者的爪杯" => Some(ArtifactSetName::ScrollOfTheHeroOfCinderCity),
-            "诸圣的礼冠" | "灵髓的根脉" | "异种的期许" | "夜域的迷思" | "纷争的前宴" => Some(ArtifactSetName::ObsidianCodex),
-            "深廊的遂失之冕" | "深廊的漫远之约" | "深廊的回奏之歌" | "深廊的湮落之刻" | "深廊的饫赐之宴" => Some(ArtifactSetName::LongNightsOath),
-            "被浸染的缨盔" | "夜鸣莺的尾羽" | "执灯人的誓词" | "不死者的哀铃" | "未吹响的号角" => Some(ArtifactSetName::FinaleOfTheDeepGalleries),
-            _ => None,
+/// Generates `ArtifactSetName::ZH_CN_NAMES`, `ArtifactSetName::from_zh_cn` and `ArtifactSlot::from_zh_cn`
+/// from one list of the artifact names of each set
+macro_rules! artifact_names {
+    ($($set:ident { $($name:literal => $slot:ident),* $(,)? })*) => {
+        impl ArtifactSetName {
+            /// The artifact names accepted by `from_zh_cn` and `ArtifactSlot::from_zh_cn`
+            pub const ZH_CN_NAMES: &'static [&'static str] = &[$($($name),*),*];
+
+            pub fn from_zh_cn(s: &str) -> Option<ArtifactSetName> {
+                match s {
+                    $($($name)|* => Some(ArtifactSetName::$set),)*
+                    _ => None,
+                }
+            }
         }
-    }
-}
 
-impl ArtifactSlot {
-    pub fn from_zh_cn(s: &str) -> Option<ArtifactSlot> {
-        match s {
-            "磐陀裂生之花" => Some(ArtifactSlot::Flower),
-            "嵯峨群峰之翼" => Some(ArtifactSlot::Feather),
-            "星罗圭壁之晷" => Some(ArtifactSlot::Sand),
-            "星罗圭璧之晷" => Some(ArtifactSlot::Sand),
-            "巉岩琢塑之樽" => Some(ArtifactSlot::Goblet),
-            "不动玄石之相" => Some(ArtifactSlot::Head),
-            "历经风雪的思念" => Some(ArtifactSlot::Flower),
-            "摧冰而行的执望" => Some(ArtifactSlot::Feather),
-            "冰雪故园的终期" => Some(ArtifactSlot::Sand),
-            "遍结寒霜的傲骨" => Some(ArtifactSlot::Goblet),
-            "破冰踏雪的回音" => Some(ArtifactSlot::Head),
-            "染血的铁之心" => Some(ArtifactSlot::Flower),
-            "染血的黑之羽" => Some(ArtifactSlot::Feather),
-            "骑士染血之时" => Some(ArtifactSlot::Sand),
-            "染血骑士之杯" => Some(ArtifactSlot::Goblet),
-            "染血的铁假面" => Some(ArtifactSlot::Head),
-            "魔女的炎之花" => Some(ArtifactSlot::Flower),
-            "魔女常燃之羽" => Some(ArtifactSlot::Feather),
-            "魔女破灭之时" => Some(ArtifactSlot::Sand),
-            "魔女的心之火" => Some(ArtifactSlot::Goblet),
-            "焦灼的魔女帽" => Some(ArtifactSlot::Head),
-            "角斗士的留恋" => Some(ArtifactSlot::Flower),
-            "角斗士的归宿" => Some(ArtifactSlot::Feather),
-            "角斗士的希冀" => Some(ArtifactSlot::Sand),
-            "角斗士的酣醉" => Some(ArtifactSlot::Goblet),
-            "角斗士的凯旋" => Some(ArtifactSlot::Head),
-            "饰金胸花" => Some(ArtifactSlot::Flower),
-            "追忆之风" => Some(ArtifactSlot::Feather),
-            "坚铜罗盘" => Some(ArtifactSlot::Sand),
-            "沉波之盏" => Some(ArtifactSlot::Goblet),
-            "酒渍船帽" => Some(ArtifactSlot::Head),
-            "渡火者的决绝" => Some(ArtifactSlot::Flower),
-            "渡火者的解脱" => Some(ArtifactSlot::Feather),
-            "渡火者的煎熬" => Some(ArtifactSlot::Sand),
-            "渡火者的醒悟" => Some(ArtifactSlot::Goblet),
-            "渡火者的智慧" => Some(ArtifactSlot::Head),
-            "远方的少女之心" => Some(ArtifactSlot::Flower),
-            "少女飘摇的思念" => Some(ArtifactSlot::Feather),
-            "少女苦短的良辰" => Some(ArtifactSlot::Sand),
-            "少女片刻的闲暇" => Some(ArtifactSlot::Goblet),
-            "少女易逝的芳颜" => Some(ArtifactSlot::Head),
-            "宗室之花" => Some(ArtifactSlot::Flower),
-            "宗室之翎" => Some(ArtifactSlot::Feather),
-            "宗室时计" => Some(ArtifactSlot::Sand),
-            "宗室银瓮" => Some(ArtifactSlot::Goblet),
-            "宗室面具" => Some(ArtifactSlot::Head),
-            "夏祭之花" => Some(ArtifactSlot::Flower),
-            "夏祭终末" => Some(ArtifactSlot::Feather),
-            "夏祭之刻" => Some(ArtifactSlot::Sand),
-            "夏祭水玉" => Some(ArtifactSlot::Goblet),
-            "夏祭之面" => Some(ArtifactSlot::Head),
-            "平雷之心" => Some(ArtifactSlot::Flower),
-            "平雷之羽" => Some(ArtifactSlot::Feather),
-            "平雷之刻" => Some(ArtifactSlot::Sand),
-            "平雷之器" => Some(ArtifactSlot::Goblet),
-            "平雷之冠" => Some(ArtifactSlot::Head),
-            "雷鸟的怜悯" => Some(ArtifactSlot::Flower),
-            "雷灾的孑遗" => Some(ArtifactSlot::Feather),
-            "雷霆的时计" => Some(ArtifactSlot::Sand),
-            "降雷的凶兆" => Some(ArtifactSlot::Goblet),
-            "唤雷的头冠" => Some(ArtifactSlot::Head),
-            "野花记忆的绿野" => Some(ArtifactSlot::Flower),
-            "猎人青翠的箭羽" => Some(ArtifactSlot::Feather),
-            "翠绿猎人的笃定" => Some(ArtifactSlot::Sand),
-            "翠绿猎人的容器" => Some(ArtifactSlot::Goblet),
-            "翠绿的猎人之冠" => Some(ArtifactSlot::Head),
-            "乐团的晨光" => Some(ArtifactSlot::Flower),
-            "琴师的箭羽" => Some(ArtifactSlot::Feather),
-            "终幕的时计" => Some(ArtifactSlot::Sand),
-            "终末的时计" => Some(ArtifactSlot::Sand),
-            "吟游者之壶" => Some(ArtifactSlot::Goblet),
-            "指挥的礼帽" => Some(ArtifactSlot::Head),
-            "战狂的蔷薇" => Some(ArtifactSlot::Flower),
-            "战狂的翎羽" => Some(ArtifactSlot::Feather),
-            "战狂的时计" => Some(ArtifactSlot::Sand),
-            "战狂的骨杯" => Some(ArtifactSlot::Goblet),
-            "战狂的鬼面" => Some(ArtifactSlot::Head),
-            "勇士的勋章" => Some(ArtifactSlot::Flower),
-            "勇士的期许" => Some(ArtifactSlot::Feather),
-            "勇士的坚毅" => Some(ArtifactSlot::Sand),
-            "勇士的壮行" => Some(ArtifactSlot::Goblet),
-            "勇士的冠冕" => Some(ArtifactSlot::Head),
-            "守护之花" => Some(ArtifactSlot::Flower),
-            "守护徽印" => Some(ArtifactSlot::Feather),
-            "守护座钟" => Some(ArtifactSlot::Sand),
-            "守护之皿" => Some(ArtifactSlot::Goblet),
-            "守护束带" => Some(ArtifactSlot::Head),
-            "流放者之花" => Some(ArtifactSlot::Flower),
-            "流放者之羽" => Some(ArtifactSlot::Feather),
-            "流放者怀表" => Some(ArtifactSlot::Sand),
-            "流放者之杯" => Some(ArtifactSlot::Goblet),
-            "流放者头冠" => Some(ArtifactSlot::Head),
-            "赌徒的胸花" => Some(ArtifactSlot::Flower),
-            "赌徒的羽饰" => Some(ArtifactSlot::Feather),
-            "赌徒的怀表" => Some(ArtifactSlot::Sand),
-            "赌徒的骰盅" => Some(ArtifactSlot::Goblet),
-            "赌徒的耳环" => Some(ArtifactSlot::Head),
-            "教官的胸花" => Some(ArtifactSlot::Flower),
-            "教官的羽饰" => Some(ArtifactSlot::Feather),
-            "教官的怀表" => Some(ArtifactSlot::Sand),
-            "教官的茶杯" => Some(ArtifactSlot::Goblet),
-            "教官的帽子" => Some(ArtifactSlot::Head),
-            "武人的红花" => Some(ArtifactSlot::Flower),
-            "武人的羽饰" => Some(ArtifactSlot::Feather),
-            "武人的水漏" => Some(ArtifactSlot::Sand),
-            "武人的酒杯" => Some(ArtifactSlot::Goblet),
-            "武人的头巾" => Some(ArtifactSlot::Head),
-            "祭水礼冠" => Some(ArtifactSlot::Head),
-            "祭火礼冠" => Some(ArtifactSlot::Head),
-            "祭雷礼冠" => Some(ArtifactSlot::Head),
-            "祭冰礼冠" => Some(ArtifactSlot::Head),
-            "故人之心" => Some(ArtifactSlot::Flower),
-            "归乡之羽" => Some(ArtifactSlot::Feather),
-            "逐光之石" => Some(ArtifactSlot::Sand),
-            "异国之盏" => Some(ArtifactSlot::Goblet),
-            "感别之冠" => Some(ArtifactSlot::Head),
-            "学士的书签" => Some(ArtifactSlot::Flower),
-            "学士的羽笔" => Some(ArtifactSlot::Feather),
-            "学士的时钟" => Some(ArtifactSlot::Sand),
-            "学士的墨杯" => Some(ArtifactSlot::Goblet),
-            "学士的镜片" => Some(ArtifactSlot::Head),
-            "奇迹之花" => Some(ArtifactSlot::Flower),
-            "奇迹之羽" => Some(ArtifactSlot::Feather),
-            "奇迹之沙" => Some(ArtifactSlot::Sand),
-            "奇迹之杯" => Some(ArtifactSlot::Goblet),
-            "奇迹耳坠" => Some(ArtifactSlot::Head),
-            "冒险家之花" => Some(ArtifactSlot::Flower),
-            "冒险家尾羽" => Some(ArtifactSlot::Feather),
-            "冒险家怀表" => Some(ArtifactSlot::Sand),
-            "冒险家金杯" => Some(ArtifactSlot::Goblet),
-            "冒险家头带" => Some(ArtifactSlot::Head),
-            "幸运儿绿花" => Some(ArtifactSlot::Flower),
-            "幸运儿鹰羽" => Some(ArtifactSlot::Feather),
-            "幸运儿沙漏" => Some(ArtifactSlot::Sand),
-            "幸运儿之杯" => Some(ArtifactSlot::Goblet),
-            "幸运儿银冠" => Some(ArtifactSlot::Head),
-            "游医的银莲" => Some(ArtifactSlot::Flower),
-            "游医的枭羽" => Some(ArtifactSlot::Feather),
-            "游医的怀钟" => Some(ArtifactSlot::Sand),
-            "游医的药壶" => Some(ArtifactSlot::Goblet),
-            "游医的方巾" => Some(ArtifactSlot::Head),
-            "勋绩之花" => Some(ArtifactSlot::Flower),
-            "昭武翎羽" => Some(ArtifactSlot::Feather),
-            "金铜时晷" => Some(ArtifactSlot::Sand),
-            "盟誓金爵" => Some(ArtifactSlot::Goblet),
-            "将帅兜鍪" => Some(ArtifactSlot::Head),
-            "无垢之花" => Some(ArtifactSlot::Flower),
-            "贤医之羽" => Some(ArtifactSlot::Feather),
-            "停摆之刻" => Some(ArtifactSlot::Sand),
-            "超越之盏" => Some(ArtifactSlot::Goblet),
-            "嗤笑之面" => Some(ArtifactSlot::Head),
-            "明威之镡" => Some(ArtifactSlot::Flower),
-            "切落之羽" => Some(ArtifactSlot::Feather),
-            "雷云之笼" => Some(ArtifactSlot::Sand),
-            "绯花之壶" => Some(ArtifactSlot::Goblet),
-            "华饰之兜" => Some(ArtifactSlot::Head),
-            "羁缠之花" => Some(ArtifactSlot::Flower),
-            "思忆之矢" => Some(ArtifactSlot::Feather),
-            "朝露之时" => Some(ArtifactSlot::Sand),
-            "祈望之心" => Some(ArtifactSlot::Goblet),
-            "无常之面" => Some(ArtifactSlot::Head),
-            "荣花之期" => Some(ArtifactSlot::Flower),
-            "华馆之羽" => Some(ArtifactSlot::Feather),
-            "众生之谣" => Some(ArtifactSlot::Sand),
-            "梦醒之瓢" => Some(ArtifactSlot::Goblet),
-            "形骸之笠" => Some(ArtifactSlot::Head),
-            "海染之花" => Some(ArtifactSlot::Flower),
-            "渊宫之羽" => Some(ArtifactSlot::Feather),
-            "离别之贝" => Some(ArtifactSlot::Sand),
-            "真珠之笼" => Some(ArtifactSlot::Goblet),
-            "海祇之冠" => Some(ArtifactSlot::Head),
-            "生灵之华" => Some(ArtifactSlot::Flower),
-            "阳辔之遗" => Some(ArtifactSlot::Sand),
-            "潜光片羽" => Some(ArtifactSlot::Feather),
-            "结契之刻" => Some(ArtifactSlot::Goblet),
-            "虺雷之姿" => Some(ArtifactSlot::Head),
-            "魂香之花" => Some(ArtifactSlot::Flower),
-            "祝祀之凭" => Some(ArtifactSlot::Sand),
-            "垂玉之叶" => Some(ArtifactSlot::Feather),
-            "涌泉之盏" => Some(ArtifactSlot::Goblet),
-            "浮溯之珏" => Some(ArtifactSlot::Head),
-            "迷宫的游人" => Some(ArtifactSlot::Flower),
-            "翠蔓的智者" => Some(ArtifactSlot::Feather),
-            "贤智的定期" => Some(ArtifactSlot::Sand),
-            "迷误者之灯" => Some(ArtifactSlot::Goblet),
-            "月桂的宝冠" => Some(ArtifactSlot::Head),
-            "梦中的铁花" => Some(ArtifactSlot::Flower),
-            "裁断的翎羽" => Some(ArtifactSlot::Feather),
-            "沉金的岁月" => Some(ArtifactSlot::Sand),
-            "如蜜的终宴" => Some(ArtifactSlot::Goblet),
-            "沙王的投影" => Some(ArtifactSlot::Head),
-            "月女的华彩" => Some(ArtifactSlot::Flower),
-            "谢落的筵席" => Some(ArtifactSlot::Feather),
-            "凝结的时刻" => Some(ArtifactSlot::Sand),
-            "守秘的魔瓶" => Some(ArtifactSlot::Goblet),
-            "紫晶的花冠" => Some(ArtifactSlot::Head),
-            "众王之都的开端" => Some(ArtifactSlot::Flower),
-            "黄金邦国的结末" => Some(ArtifactSlot::Feather),
-            "失落迷途的机芯" => Some(ArtifactSlot::Sand),
-            "迷醉长梦的守护" => Some(ArtifactSlot::Goblet),
-            "流沙贵嗣的遗宝" => Some(ArtifactSlot::Head),
-            "旅途中的鲜花" => Some(ArtifactSlot::Flower),
-            "坏巫师的羽杖" => Some(ArtifactSlot::Feather),
-            "水仙的时时刻刻" => Some(ArtifactSlot::Sand),
-            "勇者们的茶会" => Some(ArtifactSlot::Goblet),
-            "恶龙的单片镜" => Some(ArtifactSlot::Head),
-            "灵光源起之蕊" => Some(ArtifactSlot::Flower),
-            "琦色灵彩之羽" => Some(ArtifactSlot::Feather),
-            "久远花落之时" => Some(ArtifactSlot::Sand),
-            "无边酣乐之筵" => Some(ArtifactSlot::Goblet),
-            "灵光明烁之心" => Some(ArtifactSlot::Head),
-            "猎人的胸花" => Some(ArtifactSlot::Flower),
-            "杰作的序曲" => Some(ArtifactSlot::Feather),
-            "裁判的时刻" => Some(ArtifactSlot::Sand),
-            "遗忘的容器" => Some(ArtifactSlot::Goblet),
-            "老兵的容颜" => Some(ArtifactSlot::Head),
-            "黄金乐曲的变奏" => Some(ArtifactSlot::Flower),
-            "黄金飞鸟的落羽" => Some(ArtifactSlot::Feather),
-            "黄金时代的先声" => Some(ArtifactSlot::Sand),
-            "黄金之夜的喧嚣" => Some(ArtifactSlot::Goblet),
-            "黄金剧团的奖赏" => Some(ArtifactSlot::Head),
-            "昔时遗落之誓" => Some(ArtifactSlot::Flower),
-            "昔时浮想之思" => Some(ArtifactSlot::Feather),
-            "昔时回映之音" => Some(ArtifactSlot::Sand),
-            "昔时应许之梦" => Some(ArtifactSlot::Goblet),
-            "昔时传奏之诗" => Some(ArtifactSlot::Head),
-            "无私的妆饰花" => Some(ArtifactSlot::Flower),
-            "诚恳的蘸水笔" => Some(ArtifactSlot::Feather),
-            "忠实的砂时计" => Some(ArtifactSlot::Sand),
-            "慷慨的墨水瓶" => Some(ArtifactSlot::Goblet),
-            "慈爱的淑女帽" => Some(ArtifactSlot::Head),
-            "异想零落的圆舞" => Some(ArtifactSlot::Head),
-            "古海玄幽的夜想" => Some(ArtifactSlot::Feather),
-            "谐律交响的前奏" => Some(ArtifactSlot::Flower),
-            "命途轮转的谐谑" => Some(ArtifactSlot::Sand),
-            "灵露倾洒的狂诗" => Some(ArtifactSlot::Goblet),
-            "失冕的宝冠" => Some(ArtifactSlot::Head),
-            "褪光的翠尾" => Some(ArtifactSlot::Feather),
-            "暗结的明花" => Some(ArtifactSlot::Flower),
-            "举业的识刻" => Some(ArtifactSlot::Sand),
-            "筹谋的共樽" => Some(ArtifactSlot::Goblet),
-            "魔战士的羽面" => Some(ArtifactSlot::Head),
-            "巡山客的信标" => Some(ArtifactSlot::Feather),
-            "驯兽师的护符" => Some(ArtifactSlot::Flower),
-            "秘术家的金盘" => Some(ArtifactSlot::Sand),
-            "游学者的爪杯" => Some(ArtifactSlot::Goblet),
-            "诸圣的礼冠" => Some(ArtifactSlot::Head),
-            "灵髓的根脉" => Some(ArtifactSlot::Feather),
-            "异种的期许" => Some(ArtifactSlot::Flower),
-            "夜域的迷思" => Some(ArtifactSlot::Sand),
-            "纷争的前宴" => Some(ArtifactSlot::Goblet),
-            "被浸染的缨盔" => Some(ArtifactSlot::Head),
-            "夜鸣莺的尾羽" => Some(ArtifactSlot::Feather),
-            "执灯人的誓词" => Some(ArtifactSlot::Flower),
-            "不死者的哀铃" => Some(ArtifactSlot::Sand),
-            "未吹响的号角" => Some(ArtifactSlot::Goblet),
-            "深廊的遂失之冕" => Some(ArtifactSlot::Head),
-            "深廊的漫远之约" => Some(ArtifactSlot::Feather),
-            "深廊的回奏之歌" => Some(ArtifactSlot::Flower),
-            "深廊的湮落之刻" => Some(ArtifactSlot::Sand),
-            "深廊的饫赐之宴" => Some(ArtifactSlot::Goblet),
-            _ => None,
+        impl ArtifactSlot {
+            pub fn from_zh_cn(s: &str) -> Option<ArtifactSlot> {
+                match s {
+                    $($($name => Some(ArtifactSlot::$slot),)*)*
+                    _ => None,
+                }
+            }
         }
-    }
+    };
+}
+
+artifact_names! {
+    // "壁" and "璧" are both seen in the sands name
+    ArchaicPetra { "磐陀裂生之花" => Flower, "嵯峨群峰之翼" => Feather, "星罗圭壁之晷" => Sand, "星罗圭璧之晷" => Sand, "巉岩琢塑之樽" => Goblet, "不动玄石之相" => Head }
+    BlizzardStrayer { "历经风雪的思念" => Flower, "摧冰而行的执望" => Feather, "冰雪故园的终期" => Sand, "遍结寒霜的傲骨" => Goblet, "破冰踏雪的回音" => Head }
+    BloodstainedChivalry { "染血的铁之心" => Flower, "染血的黑之羽" => Feather, "骑士染血之时" => Sand, "染血骑士之杯" => Goblet, "染血的铁假面" => Head }
+    CrimsonWitch { "魔女的炎之花" => Flower, "魔女常燃之羽" => Feather, "魔女破灭之时" => Sand, "魔女的心之火" => Goblet, "焦灼的魔女帽" => Head }
+    GladiatorFinale { "角斗士的留恋" => Flower, "角斗士的归宿" => Feather, "角斗士的希冀" => Sand, "角斗士的酣醉" => Goblet, "角斗士的凯旋" => Head }
+    HeartOfDepth { "饰金胸花" => Flower, "追忆之风" => Feather, "坚铜罗盘" => Sand, "沉波之盏" => Goblet, "酒渍船帽" => Head }
+    LavaWalker { "渡火者的决绝" => Flower, "渡火者的解脱" => Feather, "渡火者的煎熬" => Sand, "渡火者的醒悟" => Goblet, "渡火者的智慧" => Head }
+    MaidenBeloved { "远方的少女之心" => Flower, "少女飘摇的思念" => Feather, "少女苦短的良辰" => Sand, "少女片刻的闲暇" => Goblet, "少女易逝的芳颜" => Head }
+    NoblesseOblige { "宗室之花" => Flower, "宗室之翎" => Feather, "宗室时计" => Sand, "宗室银瓮" => Goblet, "宗室面具" => Head }
+    RetracingBolide { "夏祭之花" => Flower, "夏祭终末" => Feather, "夏祭之刻" => Sand, "夏祭水玉" => Goblet, "夏祭之面" => Head }
+    ThunderSmoother { "平雷之心" => Flower, "平雷之羽" => Feather, "平雷之刻" => Sand, "平雷之器" => Goblet, "平雷之冠" => Head }
+    ThunderingFury { "雷鸟的怜悯" => Flower, "雷灾的孑遗" => Feather, "雷霆的时计" => Sand, "降雷的凶兆" => Goblet, "唤雷的头冠" => Head }
+    ViridescentVenerer { "野花记忆的绿野" => Flower, "猎人青翠的箭羽" => Feather, "翠绿猎人的笃定" => Sand, "翠绿猎人的容器" => Goblet, "翠绿的猎人之冠" => Head }
+    WandererTroupe { "乐团的晨光" => Flower, "琴师的箭羽" => Feather, "终幕的时计" => Sand, "终末的时计" => Sand, "吟游者之壶" => Goblet, "指挥的礼帽" => Head }
+    Berserker { "战狂的蔷薇" => Flower, "战狂的翎羽" => Feather, "战狂的时计" => Sand, "战狂的骨杯" => Goblet, "战狂的鬼面" => Head }
+    BraveHeart { "勇士的勋章" => Flower, "勇士的期许" => Feather, "勇士的坚毅" => Sand, "勇士的壮行" => Goblet, "勇士的冠冕" => Head }
+    DefenderWill { "守护之花" => Flower, "守护徽印" => Feather, "守护座钟" => Sand, "守护之皿" => Goblet, "守护束带" => Head }
+    Exile { "流放者之花" => Flower, "流放者之羽" => Feather, "流放者怀表" => Sand, "流放者之杯" => Goblet, "流放者头冠" => Head }
+    Gambler { "赌徒的胸花" => Flower, "赌徒的羽饰" => Feather, "赌徒的怀表" => Sand, "赌徒的骰盅" => Goblet, "赌徒的耳环" => Head }
+    Instructor { "教官的胸花" => Flower, "教官的羽饰" => Feather, "教官的怀表" => Sand, "教官的茶杯" => Goblet, "教官的帽子" => Head }
+    MartialArtist { "武人的红花" => Flower, "武人的羽饰" => Feather, "武人的水漏" => Sand, "武人的酒杯" => Goblet, "武人的头巾" => Head }
+    PrayersForDestiny { "祭水礼冠" => Head }
+    PrayersForIllumination { "祭火礼冠" => Head }
+    PrayersForWisdom { "祭雷礼冠" => Head }
+    PrayersToSpringtime { "祭冰礼冠" => Head }
+    ResolutionOfSojourner { "故人之心" => Flower, "归乡之羽" => Feather, "逐光之石" => Sand, "异国之盏" => Goblet, "感别之冠" => Head }
+    Scholar { "学士的书签" => Flower, "学士的羽笔" => Feather, "学士的时钟" => Sand, "学士的墨杯" => Goblet, "学士的镜片" => Head }
+    TinyMiracle { "奇迹之花" => Flower, "奇迹之羽" => Feather, "奇迹之沙" => Sand, "奇迹之杯" => Goblet, "奇迹耳坠" => Head }
+    Adventurer { "冒险家之花" => Flower, "冒险家尾羽" => Feather, "冒险家怀表" => Sand, "冒险家金杯" => Goblet, "冒险家头带" => Head }
+    LuckyDog { "幸运儿绿花" => Flower, "幸运儿鹰羽" => Feather, "幸运儿沙漏" => Sand, "幸运儿之杯" => Goblet, "幸运儿银冠" => Head }
+    TravelingDoctor { "游医的银莲" => Flower, "游医的枭羽" => Feather, "游医的怀钟" => Sand, "游医的药壶" => Goblet, "游医的方巾" => Head }
+    TenacityOfTheMillelith { "勋绩之花" => Flower, "昭武翎羽" => Feather, "金铜时晷" => Sand, "盟誓金爵" => Goblet, "将帅兜鍪" => Head }
+    PaleFlame { "无垢之花" => Flower, "贤医之羽" => Feather, "停摆之刻" => Sand, "超越之盏" => Goblet, "嗤笑之面" => Head }
+    EmblemOfSeveredFate { "明威之镡" => Flower, "切落之羽" => Feather, "雷云之笼" => Sand, "绯花之壶" => Goblet, "华饰之兜" => Head }
+    ShimenawaReminiscence { "羁缠之花" => Flower, "思忆之矢" => Feather, "朝露之时" => Sand, "祈望之心" => Goblet, "无常之面" => Head }
+    HuskOfOpulentDreams { "荣花之期" => Flower, "华馆之羽" => Feather, "众生之谣" => Sand, "梦醒之瓢" => Goblet, "形骸之笠" => Head }
+    OceanHuedClam { "海染之花" => Flower, "渊宫之羽" => Feather, "离别之贝" => Sand, "真珠之笼" => Goblet, "海祇之冠" => Head }
+    VermillionHereafter { "生灵之华" => Flower, "阳辔之遗" => Sand, "潜光片羽" => Feather, "结契之刻" => Goblet, "虺雷之姿" => Head }
+    EchoesOfAnOffering { "魂香之花" => Flower, "祝祀之凭" => Sand, "垂玉之叶" => Feather, "涌泉之盏" => Goblet, "浮溯之珏" => Head }
+    DeepwoodMemories { "迷宫的游人" => Flower, "翠蔓的智者" => Feather, "贤智的定期" => Sand, "迷误者之灯" => Goblet, "月桂的宝冠" => Head }
+    GildedDreams { "梦中的铁花" => Flower, "裁断的翎羽" => Feather, "沉金的岁月" => Sand, "如蜜的终宴" => Goblet, "沙王的投影" => Head }
+    FlowerOfParadiseLost { "月女的华彩" => Flower, "谢落的筵席" => Feather, "凝结的时刻" => Sand, "守秘的魔瓶" => Goblet, "紫晶的花冠" => Head }
+    DesertPavilionChronicle { "众王之都的开端" => Flower, "黄金邦国的结末" => Feather, "失落迷途的机芯" => Sand, "迷醉长梦的守护" => Goblet, "流沙贵嗣的遗宝" => Head }
+    NymphsDream { "恶龙的单片镜" => Head, "坏巫师的羽杖" => Feather, "旅途中的鲜花" => Flower, "水仙的时时刻刻" => Sand, "勇者们的茶会" => Goblet }
+    VourukashasGlow { "灵光明烁之心" => Head, "琦色灵彩之羽" => Feather, "灵光源起之蕊" => Flower, "久远花落之时" => Sand, "无边酣乐之筵" => Goblet }
+    MarechausseeHunter { "猎人的胸花" => Flower, "杰作的序曲" => Feather, "裁判的时刻" => Sand, "遗忘的容器" => Goblet, "老兵的容颜" => Head }
+    GoldenTroupe { "黄金乐曲的变奏" => Flower, "黄金飞鸟的落羽" => Feather, "黄金时代的先声" => Sand, "黄金之夜的喧嚣" => Goblet, "黄金剧团的奖赏" => Head }
+    SongOfDaysPast { "昔时传奏之诗" => Head, "昔时浮想之思" => Feather, "昔时遗落之誓" => Flower, "昔时回映之音" => Sand, "昔时应许之梦" => Goblet }
+    NighttimeWhispersInTheEchoingWoods { "慈爱的淑女帽" => Head, "诚恳的蘸水笔" => Feather, "无私的妆饰花" => Flower, "忠实的砂时计" => Sand, "慷慨的墨水瓶" => Goblet }
+    FragmentOfHarmonicWhimsy { "异想零落的圆舞" => Head, "古海玄幽的夜想" => Feather, "谐律交响的前奏" => Flower, "命途轮转的谐谑" => Sand, "灵露倾洒的狂诗" => Goblet }
+    UnfinishedReverie { "失冕的宝冠" => Head, "褪光的翠尾" => Feather, "暗结的明花" => Flower, "举业的识刻" => Sand, "筹谋的共樽" => Goblet }
+    ScrollOfTheHeroOfCinderCity { "魔战士的羽面" => Head, "巡山客的信标" => Feather, "驯兽师的护符" => Flower, "秘术家的金盘" => Sand, "游学者的爪杯" => Goblet }
+    ObsidianCodex { "诸圣的礼冠" => Head, "灵髓的根脉" => Feather, "异种的期许" => Flower, "夜域的迷思" => Sand, "纷争的前宴" => Goblet }
+    LongNightsOath { "深廊的遂失之冕" => Head, "深廊的漫远之约" => Feather, "深廊的回奏之歌" => Flower, "深廊的湮落之刻" => Sand, "深廊的饫赐之宴" => Goblet }
+    FinaleOfTheDeepGalleries { "被浸染的缨盔" => Head, "夜鸣莺的尾羽" => Feather, "执灯人的誓词" => Flower, "不死者的哀铃" => Sand, "未吹响的号角" => Goblet }
 }
@@ -1,15 +1,15 @@
 use std::{cell::RefCell, ops::{Coroutine, CoroutineState}, pin::Pin, rc::Rc, sync::mpsc::{self, Sender}, time::SystemTime};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::FromArgMatches;
 use image::RgbImage;
 use log::{error, info};
 
-use yas::capture::{Capturer, GenericCapturer};
-use yas::game_info::GameInfo;
-use yas::ocr::{ImageToText, yas_ocr_model};
-use yas::positioning::Pos;
-use yas::window_info::FromWindowInfoRepository;
+use yas::capture::{CapturedFrame, Capturer, CapturerConfig, retry_capture};
+use yas::game_info::{GameInfo, Platform, UI};
+use yas::ocr::{ImageToText, OcrModelConfig, yas_ocr_model};
+use yas::positioning::{Pos, Size};
+use yas::window_info::{merge_required_keys, merge_window_info_values, FromWindowInfoRepository, RequiredWindowInfoKey, RequiredWindowInfoKeys, WindowInfoType, WindowInfoValues};
 use yas::window_info::WindowInfoRepository;
 
 use crate::{scanner::artifact_scanner::artifact_scanner_worker::ArtifactScannerWorker};
@@ -17,19 +17,14 @@ use crate::scanner::artifact_scanner::message_items::SendItem;
 use crate::scanner::artifact_scanner::scan_result::GenshinArtifactScanResult;
 use crate::scanner_controller::repository_layout::{
     GenshinRepositoryScanController,
+    GenshinRepositoryScanControllerWindowInfo,
     GenshinRepositoryScannerLogicConfig,
     ReturnResult as GenshinRepositoryControllerReturnResult,
 };
 
 use super::artifact_scanner_config::GenshinArtifactScannerConfig;
 use super::ArtifactScannerWindowInfo;
-
-fn color_distance(c1: &image::Rgb<u8>, c2: &image::Rgb<u8>) -> usize {
-    let x = c1.0[0] as i32 - c2.0[0] as i32;
-    let y = c1.0[1] as i32 - c2.0[1] as i32;
-    let z = c1.0[2] as i32 - c2.0[2] as i32;
-    (x * x + y * y + z * z) as usize
-}
+use super::OCR_MODEL_NAME;
 
 pub struct GenshinArtifactScanner {
     scanner_config: GenshinArtifactScannerConfig,
@@ -38,31 +33,63 @@ pub struct GenshinArtifactScanner {
     image_to_text: Box<dyn ImageToText<RgbImage> + Send>,
     controller: Rc<RefCell<GenshinRepositoryScanController>>,
     capturer: Rc<dyn Capturer<RgbImage>>,
+    model_config: OcrModelConfig,
 }
 
 impl GenshinArtifactScanner {
     pub const MAX_COUNT: usize = 2100;
+    pub const MAX_CAPTURE_RETRY: usize = 3;
 }
 
 // constructor
 impl GenshinArtifactScanner {
-    fn get_image_to_text() -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
+    pub fn get_image_to_text(model_config: &OcrModelConfig) -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
         let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(
-            yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json")?
+            model_config.build_yas_model(OCR_MODEL_NAME, |backend| {
+                yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json", backend)
+            })?
         );
         Ok(model)
     }
 
-    fn get_capturer() -> Result<Rc<dyn Capturer<RgbImage>>> {
-        Ok(Rc::new(GenericCapturer::new()?))
+    /// The window info keys read by the scanner and its controller, each key once
+    pub fn required_window_info_keys() -> Vec<RequiredWindowInfoKey> {
+        merge_required_keys(
+            ArtifactScannerWindowInfo::required_window_info_keys(),
+            GenshinRepositoryScanControllerWindowInfo::required_window_info_keys(),
+        )
+    }
+
+    /// The window info of the scanner and its controller for a window size, each key once
+    pub fn window_info_values(
+        window_info_repo: &WindowInfoRepository,
+        window_size: Size<usize>,
+        ui: UI,
+        platform: Platform,
+    ) -> Result<Vec<(String, WindowInfoType)>> {
+        Ok(merge_window_info_values(
+            ArtifactScannerWindowInfo::from_window_info_repository(window_size, ui, platform, window_info_repo)?
+                .window_info_values(),
+            GenshinRepositoryScanControllerWindowInfo::from_window_info_repository(window_size, ui, platform, window_info_repo)?
+                .window_info_values(),
+        ))
+    }
+
+    fn get_capturer(config: &CapturerConfig, game_info: &GameInfo) -> Result<Rc<dyn Capturer<RgbImage>>> {
+        let capturer: Rc<dyn Capturer<RgbImage> + Send> = Rc::from(config.build_capturer(game_info, "genshin_artifact")?);
+        Ok(capturer)
     }
 
     pub fn new(
         window_info_repo: &WindowInfoRepository,
         config: GenshinArtifactScannerConfig,
         controller_config: GenshinRepositoryScannerLogicConfig,
+        capturer_config: CapturerConfig,
+        model_config: OcrModelConfig,
         game_info: GameInfo,
     ) -> Result<Self> {
+        // the controller shares the capturer, so that a replay is answered in the recorded order
+        let capturer = Self::get_capturer(&capturer_config, &game_info)?;
         Ok(Self {
             scanner_config: config,
             window_info: ArtifactScannerWindowInfo::from_window_info_repository(
@@ -72,12 +99,13 @@ impl GenshinArtifactScanner {
                 window_info_repo,
             )?,
             controller: Rc::new(RefCell::new(
-                GenshinRepositoryScanController::new(window_info_repo, controller_config, game_info.clone(), true)?
+                GenshinRepositoryScanController::new(window_info_repo, controller_config, game_info.clone(), true, capturer.clone())?
             )),
             game_info,
-            image_to_text: Self::get_image_to_text()?,
+            image_to_text: Self::get_image_to_text(&model_config)?,
             // item count will be set later, once the scan starts
-            capturer: Self::get_capturer()?,
+            capturer,
+            model_config,
         })
     }
 
@@ -92,53 +120,51 @@ impl GenshinArtifactScanner {
             game_info.platform,
             window_info_repo,
         )?;
+        let capturer = Self::get_capturer(&CapturerConfig::from_arg_matches(arg_matches)?, &game_info)?;
+        let model_config = OcrModelConfig::from_arg_matches(arg_matches)?;
         Ok(GenshinArtifactScanner {
             scanner_config: GenshinArtifactScannerConfig::from_arg_matches(arg_matches)?,
             window_info,
             controller: Rc::new(RefCell::new(
-                GenshinRepositoryScanController::from_arg_matches(window_info_repo, arg_matches, game_info.clone(), true)?
+                GenshinRepositoryScanController::from_arg_matches(window_info_repo, arg_matches, game_info.clone(), true, capturer.clone())?
             )),
             game_info,
-            image_to_text: Self::get_image_to_text()?,
-            capturer: Self::get_capturer()?,
+            image_to_text: Self::get_image_to_text(&model_config)?,
+            capturer,
+            model_config,
         })
     }
 }
 
 impl GenshinArtifactScanner {
-    pub fn capture_panel(&self) -> Result<RgbImage> {
-        self.capturer.capture_relative_to(
-            self.window_info.panel_rect.to_rect_i32(),
+    /// Grab the whole game window once, all the reads of an item are cropped from this frame
+    pub fn capture_frame(&self) -> Result<CapturedFrame<RgbImage>> {
+        retry_capture(Self::MAX_CAPTURE_RETRY, || self.capturer.capture_frame(self.game_info.window))
+    }
+
+    pub fn capture_panel(&self, frame: &CapturedFrame<RgbImage>) -> Result<RgbImage> {
+        frame.crop_relative_to(
+            self.window_info.repository.panel_rect.to_rect_i32(),
             self.game_info.window.origin(),
         )
     }
 
-    pub fn get_star(&self) -> Result<usize> {
+    pub fn get_star(&self, frame: &CapturedFrame<RgbImage>) -> Result<usize> {
         let pos: Pos<i32> = Pos {
             x: self.game_info.window.left + self.window_info.star_pos.x as i32,
             y: self.game_info.window.top + self.window_info.star_pos.y as i32,
         };
-        let color = self.capturer.capture_color(pos)?;
-
-        let match_colors = [
-            image::Rgb([113, 119, 139]),
-            image::Rgb([42, 143, 114]),
-            image::Rgb([81, 127, 203]),
-            image::Rgb([161, 86, 224]),
-            image::Rgb([188, 105, 50]),
-        ];
-
-        let mut min_dis: usize = 0xdeadbeef;
-        let mut ret: usize = 1;
-        for (i, match_color) in match_colors.iter().enumerate() {
-            let dis2 = color_distance(match_color, &color);
-            if dis2 < min_dis {
-                min_dis = dis2;
-                ret = i + 1;
-            }
-        }
+        let color = frame.get_color(pos)?;
 
-        anyhow::Ok(ret)
+        let (index, _) = self.window_info.star_colors.nearest(&color.into())
+            .ok_or_else(|| anyhow!("genshin_artifact_star_colors is empty"))?;
+
+        anyhow::Ok(index + 1)
+    }
+
+    /// Read the panel image and the star of the selected artifact
+    fn read_item(&self, frame: &CapturedFrame<RgbImage>) -> Result<(RgbImage, usize)> {
+        Ok((self.capture_panel(frame)?, self.get_star(frame)?))
     }
 
     pub fn get_item_count(&self) -> Result<i32> {
@@ -178,12 +204,15 @@ impl GenshinArtifactScanner {
         let (tx, rx) = mpsc::channel::<Option<SendItem>>();
         // let token = self.cancellation_token.clone();
         let count = self.get_item_count()?;
-        let worker = ArtifactScannerWorker::new(
-            self.window_info.clone(),
-            self.scanner_config.clone(),
-        )?;
-
-        let join_handle = worker.run(rx);
+        let workers = (0..self.model_config.thread_count())
+            .map(|_| ArtifactScannerWorker::new(
+                self.window_info.clone(),
+                self.scanner_config.clone(),
+                &self.model_config,
+            ))
+            .collect::<Result<Vec<_>>>()?;
+
+        let join_handle = ArtifactScannerWorker::run(workers, rx);
         info!("Worker created");
 
         self.send(&tx, count);
@@ -210,8 +239,8 @@ impl GenshinArtifactScanner {
     }
 
     fn is_page_first_artifact(&self, cur_index: i32) -> bool {
-        let col = self.window_info.col;
-        let row = self.window_info.row;
+        let col = self.window_info.repository.item_col;
+        let row = self.window_info.repository.item_row;
 
         let page_size = col * row;
         return cur_index % page_size == 0;
@@ -221,8 +250,8 @@ impl GenshinArtifactScanner {
     /// max count: total count
     /// cur_index: current item index (starting from 0)
     fn get_start_row(&self, max_count: i32, cur_index: i32) -> i32 {
-        let col = self.window_info.col;
-        let row = self.window_info.row;
+        let col = self.window_info.repository.item_col;
+        let row = self.window_info.repository.item_row;
 
         let page_size = col * row;
         if max_count - cur_index >= page_size {
@@ -243,14 +272,27 @@ impl GenshinArtifactScanner {
             let pinned_generator = Pin::new(&mut generator);
             match pinned_generator.resume(()) {
                 CoroutineState::Yielded(_) => {
-                    let image = self.capture_panel().unwrap();
-                    let star = self.get_star().unwrap();
+                    let frame = match self.capture_frame() {
+                        Ok(frame) => frame,
+                        Err(e) => {
+                            error!("截图失败，停止扫描：{}", e);
+                            break;
+                        }
+                    };
+                    // the worker counts items to match the lock states of a page, so an item cannot be skipped
+                    let (image, star) = match self.read_item(&frame) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            error!("读取物品失败，请检查窗口位置与分辨率：{}", e);
+                            break;
+                        }
+                    };
 
                     let list_image = if self.is_page_first_artifact(artifact_index) {
                         let origin = self.game_info.window;
-                        let margin = self.window_info.scan_margin_pos;
-                        let gap = self.window_info.item_gap_size;
-                        let size = self.window_info.item_size;
+                        let margin = self.window_info.repository.scan_margin_pos;
+                        let gap = self.window_info.repository.item_gap_size;
+                        let size = self.window_info.repository.item_size;
 
                         let left = (origin.left as f64 + margin.x) as i32;
                         let top = (origin.top as f64
@@ -265,16 +307,19 @@ impl GenshinArtifactScanner {
                             * self.get_start_row(count, artifact_index) as f64)
                             as i32;
 
-                        let game_image = self
-                            .capturer
-                            .capture_rect(yas::positioning::Rect {
-                                left,
-                                top,
-                                width,
-                                height,
-                            })
-                            .unwrap();
-                        Some(game_image)
+                        let game_image = frame.crop_rect(yas::positioning::Rect {
+                            left,
+                            top,
+                            width,
+                            height,
+                        });
+                        match game_image {
+                            Ok(im) => Some(im),
+                            Err(e) => {
+                                error!("读取物品列表失败，请检查窗口位置与分辨率：{}", e);
+                                break;
+                            }
+                        }
                     } else {
                         None
                     };
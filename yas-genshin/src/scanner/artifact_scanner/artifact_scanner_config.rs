@@ -19,4 +19,8 @@ pub struct GenshinArtifactScannerConfig {
     /// the exact amount to scan
     #[arg(id = "number", long, help = "指定圣遗物数量", value_name = "NUMBER", default_value_t = -1)]
     pub number: i32,
+
+    /// Locate the sub stat lines with a text detection model, instead of the fixed regions of the layout
+    #[arg(id = "detect-sub-stats", long = "detect-sub-stats", help = "使用文本检测模型定位副词条（需要 --model-dir 中的检测模型）")]
+    pub detect_sub_stats: bool,
 }
@@ -1,66 +1,59 @@
-use yas::positioning::{Pos, Rect, Size};
+use yas::common::color::{Color, ColorPalette};
+use yas::positioning::{Pos, Rect};
+use yas::window_info::RepositoryLayoutWindowInfo;
 
 #[derive(Clone, yas_derive::YasWindowInfo, Debug)]
 pub struct ArtifactScannerWindowInfo {
     /// the position of artifact title relative to window
-    #[window_info(rename = "genshin_artifact_title_rect")]
+    #[window_info(rename = "genshin_artifact_title_rect", anchor = "Right", scale = "Height")]
     pub title_rect: Rect<f64>,
 
     /// the main stat name position of artifact relative to window
-    #[window_info(rename = "genshin_artifact_main_stat_name_rect")]
+    #[window_info(rename = "genshin_artifact_main_stat_name_rect", anchor = "Right", scale = "Height")]
     pub main_stat_name_rect: Rect<f64>,
 
     /// the main stat value position of artifact relative to window
-    #[window_info(rename = "genshin_artifact_main_stat_value_rect")]
+    #[window_info(rename = "genshin_artifact_main_stat_value_rect", anchor = "Right", scale = "Height")]
     pub main_stat_value_rect: Rect<f64>,
 
     /// the sub stats positions relative to window
-    #[window_info(rename = "genshin_artifact_sub_stat1_rect")]
+    #[window_info(rename = "genshin_artifact_sub_stat1_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_1: Rect<f64>,
-    #[window_info(rename = "genshin_artifact_sub_stat2_rect")]
+    #[window_info(rename = "genshin_artifact_sub_stat2_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_2: Rect<f64>,
-    #[window_info(rename = "genshin_artifact_sub_stat3_rect")]
+    #[window_info(rename = "genshin_artifact_sub_stat3_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_3: Rect<f64>,
-    #[window_info(rename = "genshin_artifact_sub_stat4_rect")]
+    #[window_info(rename = "genshin_artifact_sub_stat4_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_4: Rect<f64>,
 
     /// the level of the artifact relative to window
-    #[window_info(rename = "genshin_artifact_level_rect")]
+    #[window_info(rename = "genshin_artifact_level_rect", anchor = "Right", scale = "Height")]
     pub level_rect: Rect<f64>,
 
     /// equip status of the artifact relative to window
-    #[window_info(rename = "genshin_artifact_item_equip_rect")]
+    #[window_info(rename = "genshin_artifact_item_equip_rect", anchor = "Right", scale = "Height")]
     pub item_equip_rect: Rect<f64>,
 
     /// the count of artifacts relative to window
-    #[window_info(rename = "genshin_artifact_item_count_rect")]
+    #[window_info(rename = "genshin_artifact_item_count_rect", anchor = "Right", scale = "Height")]
     pub item_count_rect: Rect<f64>,
 
     /// the sample position of star, relative to window
-    #[window_info(rename = "genshin_artifact_star_pos")]
+    #[window_info(rename = "genshin_artifact_star_pos", anchor = "Right", scale = "Height")]
     pub star_pos: Pos<f64>,
 
-    /// the whole panel of the artifact, relative to window
-    #[window_info(rename = "genshin_repository_panel_rect")]
-    pub panel_rect: Rect<f64>,
+    /// the color sampled at `star_pos` for 1 to 5 stars
+    #[window_info(rename = "genshin_artifact_star_colors", anchor = "Right", scale = "Height")]
+    pub star_colors: ColorPalette,
 
-    /// how many columns in this layout
-    #[window_info(rename = "genshin_repository_item_col")]
-    pub col: i32,
+    /// the layout of the repository, including the whole panel of the artifact
+    #[window_info(flatten, prefix = "genshin_repository_")]
+    pub repository: RepositoryLayoutWindowInfo,
 
-    /// how many rows in this layout
-    #[window_info(rename = "genshin_repository_item_row")]
-    pub row: i32,
-
-    #[window_info(rename = "genshin_repository_item_gap_size")]
-    pub item_gap_size: Size<f64>,
-
-    #[window_info(rename = "genshin_repository_item_size")]
-    pub item_size: Size<f64>,
-
-    #[window_info(rename = "genshin_repository_scan_margin_pos")]
-    pub scan_margin_pos: Pos<f64>,
-
-    #[window_info(rename = "genshin_repository_lock_pos")]
+    #[window_info(rename = "genshin_repository_lock_pos", anchor = "Left", scale = "Height")]
     pub lock_pos: Pos<f64>,
+
+    /// the color of the lock icon of a locked artifact in the list
+    #[window_info(rename = "genshin_artifact_lock_color", anchor = "Right", scale = "Height")]
+    pub lock_color: Color,
 }
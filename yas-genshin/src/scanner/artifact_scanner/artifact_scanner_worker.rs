@@ -4,14 +4,18 @@ use std::thread::JoinHandle;
 
 use anyhow::Result;
 use image::Rgb;
-use image::{GenericImageView, RgbImage};
+use image::RgbImage;
 use log::{error, info, warn};
 
-use yas::ocr::ImageToText;
+use yas::common::worker_pool::OrderedWorkerPool;
+use yas::ocr::{crop_image, recognize_fields, DecodeConstraint, ImageTextDetection, ImageToText, OcrModelConfig, PPOCRDetModel, TextBox, STAT_VALUE_PATTERN};
 use yas::ocr::yas_ocr_model;
-use yas::positioning::{Pos, Rect};
+use yas::positioning::Rect;
 use yas::utils::color_distance;
 
+use crate::artifact::{ArtifactSetName, ArtifactStatName};
+use crate::character::CHARACTER_NAMES;
+use super::OCR_MODEL_NAME;
 use crate::scanner::artifact_scanner::artifact_scanner_window_info::ArtifactScannerWindowInfo;
 use crate::scanner::artifact_scanner::GenshinArtifactScannerConfig;
 use crate::scanner::artifact_scanner::message_items::SendItem;
@@ -29,16 +33,55 @@ fn parse_level(s: &str) -> Result<i32> {
     anyhow::Ok(level)
 }
 
-fn get_image_to_text() -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
+fn get_image_to_text(model_config: &OcrModelConfig) -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
     let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(
-        yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json")?
+        model_config.build_yas_model(OCR_MODEL_NAME, |backend| {
+            yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json", backend)
+        })?
     );
     Ok(model)
 }
 
+fn get_text_detection(model_config: &OcrModelConfig) -> Result<Box<dyn ImageTextDetection<RgbImage> + Send>> {
+    let model: Box<dyn ImageTextDetection<RgbImage> + Send> = Box::new(
+        model_config.build_ppocr_det_model(PPOCRDetModel::MODEL_NAME)?
+    );
+    Ok(model)
+}
+
+/// Restricts the text of the panel fields, a misread character is corrected to the most probable valid text
+struct FieldConstraints {
+    title: DecodeConstraint,
+    main_stat_name: DecodeConstraint,
+    stat_value: DecodeConstraint,
+    // the name and the value of a sub stat are in the same region
+    sub_stat: DecodeConstraint,
+    level: DecodeConstraint,
+    // e.g. "纳西妲已装备"
+    equip: DecodeConstraint,
+}
+
+impl FieldConstraints {
+    fn new() -> Result<Self> {
+        Ok(Self {
+            title: DecodeConstraint::lexicon(ArtifactSetName::ZH_CN_NAMES.iter().copied()),
+            main_stat_name: DecodeConstraint::lexicon(ArtifactStatName::ZH_CN_NAMES.iter().copied()),
+            stat_value: DecodeConstraint::stat_value()?,
+            sub_stat: DecodeConstraint::regex(&format!(
+                r"^(?:{})\+{}$", ArtifactStatName::ZH_CN_NAMES.join("|"), STAT_VALUE_PATTERN
+            ))?,
+            level: DecodeConstraint::level()?,
+            equip: DecodeConstraint::lexicon(CHARACTER_NAMES.iter().map(|name| format!("{}已装备", name))),
+        })
+    }
+}
+
 /// run in a separate thread, accept captured image and get an artifact
 pub struct ArtifactScannerWorker {
     model: Box<dyn ImageToText<RgbImage> + Send>,
+    /// locates the sub stat lines, the fixed regions are used if None
+    detector: Option<Box<dyn ImageTextDetection<RgbImage> + Send>>,
+    constraints: FieldConstraints,
     window_info: ArtifactScannerWindowInfo,
     config: GenshinArtifactScannerConfig,
 }
@@ -47,45 +90,81 @@ impl ArtifactScannerWorker {
     pub fn new(
         window_info: ArtifactScannerWindowInfo,
         config: GenshinArtifactScannerConfig,
+        model_config: &OcrModelConfig,
     ) -> Result<Self> {
+        let detector = if config.detect_sub_stats {
+            Some(get_text_detection(model_config)?)
+        } else {
+            None
+        };
+
         Ok(ArtifactScannerWorker {
-            model: get_image_to_text()?,
+            model: get_image_to_text(model_config)?,
+            detector,
+            constraints: FieldConstraints::new()?,
             window_info,
             config,
         })
     }
 
-    /// the captured_img is a panel of the artifact, the rect is a region of the panel
-    fn model_inference(&self, rect: Rect<f64>, captured_img: &RgbImage) -> Result<String> {
-        let relative_rect = rect.translate(Pos {
-            x: -self.window_info.panel_rect.left,
-            y: -self.window_info.panel_rect.top,
-        });
-
-        let raw_img = captured_img.view(
-            relative_rect.left as u32, relative_rect.top as u32, relative_rect.width as u32, relative_rect.height as u32,
-        ).to_image();
-
-        let inference_result = self.model.image_to_text(&raw_img, false);
-
-        inference_result
+    /// The regions of the sub stat lines, relative to the window. The detector finds the lines actually shown,
+    /// so an artifact with 3 sub stats or a shifted layout is still read correctly
+    fn sub_stat_rects(&self, captured_img: &RgbImage) -> Result<Vec<Rect<f64>>> {
+        let fixed_rects = [
+            self.window_info.sub_stat_1,
+            self.window_info.sub_stat_2,
+            self.window_info.sub_stat_3,
+            self.window_info.sub_stat_4,
+        ];
+
+        let detector = match self.detector.as_ref() {
+            Some(detector) => detector,
+            None => return Ok(fixed_rects.to_vec()),
+        };
+
+        // search the whole sub stat area
+        let left = fixed_rects.iter().map(|r| r.left).fold(f64::INFINITY, f64::min);
+        let top = fixed_rects.iter().map(|r| r.top).fold(f64::INFINITY, f64::min);
+        let right = fixed_rects.iter().map(|r| r.left + r.width).fold(f64::NEG_INFINITY, f64::max);
+        let bottom = fixed_rects.iter().map(|r| r.top + r.height).fold(f64::NEG_INFINITY, f64::max);
+        let area = Rect::new(left, top, right - left, bottom - top);
+
+        let panel_origin = self.window_info.repository.panel_rect.origin();
+        let boxes = detector.detect_text(&crop_image(captured_img, panel_origin, area))?;
+        let rects = TextBox::merge_into_lines(boxes)
+            .into_iter()
+            .take(fixed_rects.len())
+            .map(|line| line.rect.translate(area.origin()))
+            .collect();
+        Ok(rects)
     }
 
-    /// Parse the captured result (of type SendItem) to a scanned artifact
-    fn scan_item_image(&self, item: SendItem, lock: bool) -> Result<GenshinArtifactScanResult> {
+    /// Parse the captured result (of type SendItem) to a scanned artifact, the lock state is left for `run`
+    fn scan_item_image(&self, item: &SendItem) -> Result<GenshinArtifactScanResult> {
         let image = &item.panel_image;
 
-        let str_title = self.model_inference(self.window_info.title_rect, image)?;
-        let str_main_stat_name = self.model_inference(self.window_info.main_stat_name_rect, image)?;
-        let str_main_stat_value = self.model_inference(self.window_info.main_stat_value_rect, image)?;
-
-        let str_sub_stat0 = self.model_inference(self.window_info.sub_stat_1, image)?;
-        let str_sub_stat1 = self.model_inference(self.window_info.sub_stat_2, image)?;
-        let str_sub_stat2 = self.model_inference(self.window_info.sub_stat_3, image)?;
-        let str_sub_stat3 = self.model_inference(self.window_info.sub_stat_4, image)?;
-
-        let str_level = self.model_inference(self.window_info.level_rect, image)?;
-        let str_equip = self.model_inference(self.window_info.item_equip_rect, image)?;
+        let constraints = &self.constraints;
+
+        let sub_stat_rects = self.sub_stat_rects(image)?;
+        let mut fields = vec![
+            (self.window_info.title_rect, Some(&constraints.title)),
+            (self.window_info.main_stat_name_rect, Some(&constraints.main_stat_name)),
+            (self.window_info.main_stat_value_rect, Some(&constraints.stat_value)),
+            (self.window_info.level_rect, Some(&constraints.level)),
+            (self.window_info.item_equip_rect, Some(&constraints.equip)),
+        ];
+        fields.extend(sub_stat_rects.iter().map(|&rect| (rect, Some(&constraints.sub_stat))));
+
+        let panel_origin = self.window_info.repository.panel_rect.origin();
+        let mut texts = recognize_fields(&*self.model, image, panel_origin, &fields)?.into_iter();
+        let mut next_text = || texts.next().unwrap_or_default();
+        let str_title = next_text();
+        let str_main_stat_name = next_text();
+        let str_main_stat_value = next_text();
+        let str_level = next_text();
+        let str_equip = next_text();
+        // missing sub stat lines are empty
+        let [str_sub_stat0, str_sub_stat1, str_sub_stat2, str_sub_stat3] = std::array::from_fn(|_| next_text());
 
         anyhow::Ok(GenshinArtifactScanResult {
             name: str_title,
@@ -100,19 +179,26 @@ impl ArtifactScannerWorker {
             level: parse_level(&str_level)?,
             equip: str_equip,
             star: item.star as i32,
-            lock,
+            lock: false,
         })
     }
 
+    /// Scan an item, and the lock states of the page if the item comes with the list image
+    fn scan_item(&self, item: SendItem) -> (Option<Vec<bool>>, Result<GenshinArtifactScanResult>) {
+        let page_locks = item.list_image.as_ref().map(|list_image| self.get_page_locks(list_image));
+        (page_locks, self.scan_item_image(&item))
+    }
+
     /// Get all lock state from a list image
     fn get_page_locks(&self, list_image: &RgbImage) -> Vec<bool> {
         let mut result = Vec::new();
 
-        let row = self.window_info.row;
-        let col = self.window_info.col;
-        let gap = self.window_info.item_gap_size;
-        let size = self.window_info.item_size;
+        let row = self.window_info.repository.item_row;
+        let col = self.window_info.repository.item_col;
+        let gap = self.window_info.repository.item_gap_size;
+        let size = self.window_info.repository.item_size;
         let lock_pos = self.window_info.lock_pos;
+        let lock_color: Rgb<u8> = self.window_info.lock_color.into();
 
         for r in 0..row {
             if ((gap.height + size.height) * (r as f64)) as u32 > list_image.height() {
@@ -132,7 +218,7 @@ impl ArtifactScannerWorker {
                         let color = list_image
                             .get_pixel((pos_x as i32 + dx) as u32, (pos_y as i32 + dy) as u32);
 
-                        if color_distance(color, &Rgb([255, 138, 117])) < 30 {
+                        if color_distance(color, &lock_color) < 30 {
                             locked = true;
                             break 'sq;
                         }
@@ -144,42 +230,43 @@ impl ArtifactScannerWorker {
         result
     }
 
-    pub fn run(self, rx: Receiver<Option<SendItem>>) -> JoinHandle<Vec<GenshinArtifactScanResult>> {
+    /// Scan the items with the workers in parallel, the results are checked in the order of the items
+    pub fn run(workers: Vec<Self>, rx: Receiver<Option<SendItem>>) -> JoinHandle<Vec<GenshinArtifactScanResult>> {
+        let config = workers[0].config.clone();
+        let info = workers[0].window_info.clone();
+
         std::thread::spawn(move || {
             let mut results = Vec::new();
             let mut hash: HashSet<GenshinArtifactScanResult> = HashSet::new();
             // if too many artifacts are same in consecutive, then an error has occurred
             let mut consecutive_dup_count = 0;
 
-            let is_verbose = self.config.verbose;
-            let min_level = self.config.min_level;
-            let info = self.window_info.clone();
+            let is_verbose = config.verbose;
+            let min_level = config.min_level;
             // todo remove dump mode to another scanner
             // let dump_mode = false;
             // let model = self.model.clone();
-            // let panel_origin = Pos { x: self.window_info.panel_rect.left, y: self.window_info.panel_rect.top };
+            // let panel_origin = Pos { x: self.window_info.repository.panel_rect.left, y: self.window_info.repository.panel_rect.top };
 
             let mut locks = Vec::new();
             let mut artifact_index: i32 = 0;
 
-            for item in rx.into_iter() {
-                // receiving None, which means the worker should end
-                let item = match item {
-                    Some(v) => v,
-                    None => break,
-                };
+            // receiving None, which means the workers should end
+            let items = rx.into_iter().map_while(|item| item);
+            let pool = OrderedWorkerPool::spawn(workers, items, |worker: &Self, item| worker.scan_item(item));
 
+            for (page_locks, result) in pool {
                 // if there is a list image, then parse the lock state
-                match item.list_image.as_ref() {
-                    Some(v) => {
-                        locks = vec![locks, self.get_page_locks(v)].concat()
-                    }
-                    None => {}
-                };
+                if let Some(page_locks) = page_locks {
+                    locks.extend(page_locks);
+                }
 
                 artifact_index += 1;
-                let result = match self.scan_item_image(item, locks[artifact_index as usize - 1]) {
-                    Ok(v) => v,
+                let result = match result {
+                    Ok(mut v) => {
+                        v.lock = locks[artifact_index as usize - 1];
+                        v
+                    },
                     Err(e) => {
                         error!("识别错误: {}", e);
                         continue;
@@ -207,7 +294,7 @@ impl ArtifactScannerWorker {
                     results.push(result);
                 }
 
-                if consecutive_dup_count >= info.col && !self.config.ignore_dup {
+                if consecutive_dup_count >= info.repository.item_col && !config.ignore_dup {
                     error!("识别到连续多个重复物品，可能为翻页错误，或者为非背包顶部开始扫描");
                     // token.cancel();
                     break;
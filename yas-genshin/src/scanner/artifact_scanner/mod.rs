@@ -9,3 +9,6 @@ mod scan_result;
 mod artifact_scanner_worker;
 mod artifact_scanner_window_info;
 mod message_items;
+
+/// Name of the OCR model in a model directory manifest
+const OCR_MODEL_NAME: &str = "genshin_artifact";
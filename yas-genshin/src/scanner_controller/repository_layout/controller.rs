@@ -8,7 +8,7 @@ use clap::{ArgMatches, FromArgMatches};
 use image::RgbImage;
 use log::{error, info};
 
-use yas::capture::{Capturer, GenericCapturer};
+use yas::capture::Capturer;
 use yas::game_info::GameInfo;
 use yas::positioning::{Pos, Size};
 use yas::system_control::SystemControl;
@@ -55,10 +55,6 @@ fn calc_pool(row: &[u8]) -> f32 {
     pool
 }
 
-fn get_capturer() -> Result<Rc<dyn Capturer<RgbImage>>> {
-    Ok(Rc::new(GenericCapturer::new()?))
-}
-
 fn color_distance(c1: &image::Rgb<u8>, c2: &image::Rgb<u8>) -> usize {
     let x = c1.0[0] as i32 - c2.0[0] as i32;
     let y = c1.0[1] as i32 - c2.0[1] as i32;
@@ -73,6 +69,7 @@ impl GenshinRepositoryScanController {
         config: GenshinRepositoryScannerLogicConfig,
         game_info: GameInfo,
         is_artifact: bool,
+        capturer: Rc<dyn Capturer<RgbImage>>,
     ) -> Result<Self> {
         let window_info = GenshinRepositoryScanControllerWindowInfo::from_window_info_repository(
             game_info.window.to_rect_usize().size(),
@@ -80,8 +77,8 @@ impl GenshinRepositoryScanController {
             game_info.platform,
             window_info_repo,
         )?;
-        let row = window_info.genshin_repository_item_row;
-        let col = window_info.genshin_repository_item_col;
+        let row = window_info.repository.item_row;
+        let col = window_info.repository.item_col;
 
         Ok(GenshinRepositoryScanController {
             system_control: SystemControl::new(),
@@ -105,7 +102,7 @@ impl GenshinRepositoryScanController {
             game_info,
             scanned_count: 0,
 
-            capturer: get_capturer()?,
+            capturer,
 
             is_artifact,
         })
@@ -116,12 +113,14 @@ impl GenshinRepositoryScanController {
         arg_matches: &ArgMatches,
         game_info: GameInfo,
         is_artifact: bool,
+        capturer: Rc<dyn Capturer<RgbImage>>,
     ) -> Result<Self> {
         Self::new(
             window_info_repo,
             GenshinRepositoryScannerLogicConfig::from_arg_matches(arg_matches)?,
             game_info,
             is_artifact,
+            capturer,
         )
     }
 }
@@ -272,9 +271,9 @@ impl GenshinRepositoryScanController {
         let (row, col) = (row as u32, col as u32);
         let origin = self.game_info.window.to_rect_f64().origin();
 
-        let gap = self.window_info.item_gap_size;
-        let mut margin = self.window_info.scan_margin_pos;
-        let size = self.window_info.item_size;
+        let gap = self.window_info.repository.item_gap_size;
+        let mut margin = self.window_info.repository.scan_margin_pos;
+        let size = self.window_info.repository.item_size;
         if self.is_artifact {
             margin = margin + self.window_info.artifact_panel_offset;
         }
@@ -364,7 +363,7 @@ impl GenshinRepositoryScanController {
         let mut diff_flag = false;
         while now.elapsed().unwrap().as_millis() < self.config.max_wait_switch_item as u128 {
             let im = self.capturer.capture_relative_to(
-                self.window_info.pool_rect.to_rect_i32(),
+                self.window_info.repository.pool_rect.to_rect_i32(),
                 self.game_info.window.origin(),
             )?;
 
@@ -1,29 +1,17 @@
-use yas::positioning::{Pos, Rect, Size};
+use yas::positioning::{Pos, Size};
+use yas::window_info::RepositoryLayoutWindowInfo;
 use yas_derive::YasWindowInfo;
 
 #[derive(Clone, YasWindowInfo)]
 pub struct GenshinRepositoryScanControllerWindowInfo {
-    #[window_info(rename = "genshin_repository_panel_rect")]
-    pub panel_rect: Rect<f64>,
+    #[window_info(flatten, prefix = "genshin_repository_")]
+    pub repository: RepositoryLayoutWindowInfo,
 
-    #[window_info(rename = "genshin_repository_flag_pos")]
+    #[window_info(rename = "genshin_repository_flag_pos", anchor = "Left", scale = "Height")]
     pub flag_pos: Pos<f64>,
 
-    #[window_info(rename = "genshin_repository_item_gap_size")]
-    pub item_gap_size: Size<f64>,
-
-    #[window_info(rename = "genshin_repository_item_size")]
-    pub item_size: Size<f64>,
-
-    #[window_info(rename = "genshin_repository_scan_margin_pos")]
-    pub scan_margin_pos: Pos<f64>,
-
-    #[window_info(rename = "genshin_repository_pool_rect")]
-    pub pool_rect: Rect<f64>,
-
-    #[window_info(rename = "genshin_artifact_offset")]
+    /// how far the grid of the artifact list is moved from the common layout,
+    /// sizes without a recorded value are scaled or projected from the other sizes, or have no offset
+    #[window_info(rename = "genshin_artifact_offset", anchor = "Left", scale = "Height", default)]
     pub artifact_panel_offset: Size<f64>,
-
-    pub genshin_repository_item_row: i32,
-    pub genshin_repository_item_col: i32,
 }
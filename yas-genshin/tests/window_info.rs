@@ -0,0 +1,32 @@
+use yas::game_info::{Platform, UI};
+use yas::positioning::{Rect, Size};
+use yas::window_info::{validate_window_info_path, WindowInfoType};
+use yas_scanner_genshin::application::ArtifactScannerApplication;
+use yas_scanner_genshin::scanner::GenshinArtifactScanner;
+
+/// Every bundled window info file has the keys the scanner reads
+#[test]
+fn bundled_window_info_is_complete() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/window_info");
+    if let Err(e) = validate_window_info_path(dir, &GenshinArtifactScanner::required_window_info_keys()) {
+        panic!("{}", e);
+    }
+}
+
+/// The bundled window info is read at an aspect ratio without a file of its own,
+/// the item grid keeps to the left edge and the panel to the right edge
+#[test]
+fn bundled_window_info_is_projected_onto_other_aspect_ratios() {
+    let repo = ArtifactScannerApplication::get_window_info_repository();
+    let values = GenshinArtifactScanner::window_info_values(&repo, Size::new(3840, 1080), UI::Desktop, Platform::Windows).unwrap();
+    for key in GenshinArtifactScanner::required_window_info_keys().iter().filter(|k| !k.optional) {
+        assert!(values.iter().any(|(k, _)| *k == key.key), "missing {}", key.key);
+    }
+
+    // projected from 3440x1440, the closest aspect ratio
+    let panel = values.iter().find(|(k, _)| k == "genshin_repository_panel_rect").map(|(_, v)| v);
+    match panel {
+        Some(WindowInfoType::Rect(rect)) => assert_eq!(*rect, Rect::new(3156.0, 120.0, 492.75, 840.0)),
+        other => panic!("unexpected panel rect {:?}", other),
+    }
+}
@@ -1,6 +1,9 @@
-use clap::{command, ArgMatches, Args};
-use yas::game_info::{GameInfo, GameInfoBuilder};
-use yas::window_info::{load_window_info_repo, WindowInfoRepository};
+use clap::{command, ArgMatches, Args, FromArgMatches};
+use yas::capture::CapturerConfig;
+use yas::ocr::OcrModelConfig;
+use yas::text::TextMatchConfig;
+use yas::game_info::{GameInfo, GameInfoBuilder, GameInfoConfig};
+use yas::window_info::{load_window_info_repo, WindowInfoConfig, WindowInfoRepository};
 use crate::export::{ExportRelicConfig, StarRailRelicExporter};
 use crate::scanner::relic_scanner::{StarRailRelicScanner, StarRailRelicScannerConfig};
 use crate::scanner_controller::repository_layout::StarRailRepositoryScannerLogicConfig;
@@ -25,10 +28,16 @@ impl RelicScannerApplication {
         cmd = <StarRailRelicScannerConfig as Args>::augment_args_for_update(cmd);
         cmd = <StarRailRepositoryScannerLogicConfig as Args>::augment_args_for_update(cmd);
         cmd = <ExportRelicConfig as Args>::augment_args_for_update(cmd);
+        cmd = <CapturerConfig as Args>::augment_args_for_update(cmd);
+        cmd = <GameInfoConfig as Args>::augment_args_for_update(cmd);
+        cmd = <WindowInfoConfig as Args>::augment_args_for_update(cmd);
+        cmd = <OcrModelConfig as Args>::augment_args_for_update(cmd);
+        cmd = <TextMatchConfig as Args>::augment_args_for_update(cmd);
         cmd
     }
 
-    fn get_window_info_repository() -> WindowInfoRepository {
+    /// The built-in window info
+    pub fn get_window_info_repository() -> WindowInfoRepository {
         load_window_info_repo!(
             "../../window_info/windows1920x1080.json"
         )
@@ -40,13 +49,15 @@ impl RelicScannerApplication {
     //         .init();
     // }
 
-    fn get_game_info() -> Result<GameInfo> {
-        let game_info = GameInfoBuilder::new()
+    fn get_game_info(arg_matches: &ArgMatches) -> Result<GameInfo> {
+        let mut builder = GameInfoBuilder::new();
+        builder
             .add_local_window_name("崩坏：星穹铁道")
             .add_local_window_name("Honkai: Star Rail")
             .add_cloud_window_name("云·星穹铁道")
-            .build();
-        game_info
+            .add_window_class("starrail.exe");
+        GameInfoConfig::from_arg_matches(arg_matches)?.apply_to(&mut builder);
+        builder.build()
     }
 }
 
@@ -54,8 +65,13 @@ impl RelicScannerApplication {
     pub fn run(&self) -> Result<()> {
         // Self::init();
         let arg_matches = &self.arg_matches;
-        let window_info_repository = Self::get_window_info_repository();
-        let game_info = Self::get_game_info()?;
+        let mut window_info_repository = Self::get_window_info_repository();
+        WindowInfoConfig::from_arg_matches(arg_matches)?.merge_into(&mut window_info_repository)?;
+        let capturer_config = CapturerConfig::from_arg_matches(arg_matches)?;
+        let game_info = match capturer_config.replay_game_info()? {
+            Some(game_info) => game_info,
+            None => Self::get_game_info(arg_matches)?,
+        };
 
         info!("window: {:?}", game_info.window);
         info!("ui: {:?}", game_info.ui);
@@ -76,11 +92,10 @@ impl RelicScannerApplication {
             game_info.clone()
         )?;
 
+        let text_match_config = TextMatchConfig::from_arg_matches(arg_matches)?;
         let results = scanner.scan()?;
         let starrail_relics = results.iter()
-            .map(|x| StarRailRelic::try_from(x))
-            .filter(|x| x.is_ok())
-            .map(|x| x.unwrap())
+            .filter_map(|x| StarRailRelic::from_scan_result(x, text_match_config.max_distance))
             .collect::<Vec<_>>();
         let exporter = StarRailRelicExporter::new(&arg_matches, &starrail_relics)?;
         let mut export_assets = ExportAssets::new();
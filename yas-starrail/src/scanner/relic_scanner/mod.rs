@@ -9,4 +9,6 @@ mod relic_scanner_window_info;
 mod scan_result;
 mod relic_scanner_worker;
 mod message_items;
-mod match_colors;
+
+/// Name of the OCR model in a model directory manifest
+const OCR_MODEL_NAME: &str = "starrail_relic";
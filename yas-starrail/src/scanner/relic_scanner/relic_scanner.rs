@@ -1,23 +1,22 @@
 use std::{cell::RefCell, ops::{Coroutine, CoroutineState}, pin::Pin, rc::Rc, sync::mpsc::{self, Sender}, time::SystemTime};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use clap::FromArgMatches;
 use image::RgbImage;
-use log::{error, info};
+use log::{error, info, warn};
 
-use yas::capture::{Capturer, GenericCapturer};
-use yas::game_info::GameInfo;
-use yas::ocr::{ImageToText, yas_ocr_model};
-use yas::positioning::Pos;
-use yas::utils::color_distance;
-use yas::window_info::{FromWindowInfoRepository, WindowInfoRepository};
+use yas::capture::{CapturedFrame, CaptureError, Capturer, CapturerConfig, retry_capture};
+use yas::game_info::{GameInfo, Platform, UI};
+use yas::ocr::{ImageToText, OcrModelConfig, yas_ocr_model};
+use yas::positioning::{Pos, Size};
+use yas::window_info::{merge_required_keys, merge_window_info_values, FromWindowInfoRepository, WindowInfoRepository, RequiredWindowInfoKey, RequiredWindowInfoKeys, WindowInfoType, WindowInfoValues};
 
-use crate::scanner::relic_scanner::match_colors::{MATCH_COLORS, MatchColors};
+use crate::scanner::relic_scanner::OCR_MODEL_NAME;
 use crate::scanner::relic_scanner::message_items::SendItem;
 use crate::scanner::relic_scanner::relic_scanner_window_info::RelicScannerWindowInfo;
 use crate::scanner::relic_scanner::relic_scanner_worker::RelicScannerWorker;
 use crate::scanner::relic_scanner::scan_result::StarRailRelicScanResult;
-use crate::scanner_controller::repository_layout::{ReturnResult, StarRailRepositoryScanController, StarRailRepositoryScannerLogicConfig};
+use crate::scanner_controller::repository_layout::{ReturnResult, StarRailRepositoryScanController, StarRailRepositoryScanControllerWindowInfo, StarRailRepositoryScannerLogicConfig};
 
 use super::relic_scanner_config::StarRailRelicScannerConfig;
 
@@ -28,30 +27,62 @@ pub struct StarRailRelicScanner {
     image_to_text: Box<dyn ImageToText<RgbImage> + Send>,
     controller: Rc<RefCell<StarRailRepositoryScanController>>,
     capturer: Rc<dyn Capturer<RgbImage>>,
+    model_config: OcrModelConfig,
+}
 
-    match_colors: MatchColors,
+impl StarRailRelicScanner {
+    pub const MAX_CAPTURE_RETRY: usize = 3;
 }
 
 // constructor
 impl StarRailRelicScanner {
-    fn get_image_to_text() -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
+    pub fn get_image_to_text(model_config: &OcrModelConfig) -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
         let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(
-            yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json")?
+            model_config.build_yas_model(OCR_MODEL_NAME, |backend| {
+                yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json", backend)
+            })?
         );
         // let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(yas::ocr::PPOCRChV4RecInfer::new()?);
         Ok(model)
     }
 
-    fn get_capturer() -> Result<Rc<dyn Capturer<RgbImage>>> {
-        Ok(Rc::new(GenericCapturer::new()?))
+    /// The window info keys read by the scanner and its controller, each key once
+    pub fn required_window_info_keys() -> Vec<RequiredWindowInfoKey> {
+        merge_required_keys(
+            RelicScannerWindowInfo::required_window_info_keys(),
+            StarRailRepositoryScanControllerWindowInfo::required_window_info_keys(),
+        )
+    }
+
+    /// The window info of the scanner and its controller for a window size, each key once
+    pub fn window_info_values(
+        window_info_repo: &WindowInfoRepository,
+        window_size: Size<usize>,
+        ui: UI,
+        platform: Platform,
+    ) -> Result<Vec<(String, WindowInfoType)>> {
+        Ok(merge_window_info_values(
+            RelicScannerWindowInfo::from_window_info_repository(window_size, ui, platform, window_info_repo)?
+                .window_info_values(),
+            StarRailRepositoryScanControllerWindowInfo::from_window_info_repository(window_size, ui, platform, window_info_repo)?
+                .window_info_values(),
+        ))
+    }
+
+    fn get_capturer(config: &CapturerConfig, game_info: &GameInfo) -> Result<Rc<dyn Capturer<RgbImage>>> {
+        let capturer: Rc<dyn Capturer<RgbImage> + Send> = Rc::from(config.build_capturer(game_info, "starrail_relic")?);
+        Ok(capturer)
     }
 
     pub fn new(
         window_info_repo: &WindowInfoRepository,
         config: StarRailRelicScannerConfig,
         controller_config: StarRailRepositoryScannerLogicConfig,
+        capturer_config: CapturerConfig,
+        model_config: OcrModelConfig,
         game_info: GameInfo
     ) -> Result<Self> {
+        let capturer = Self::get_capturer(&capturer_config, &game_info)?;
         Ok(StarRailRelicScanner {
             scanner_config: config,
             window_info: RelicScannerWindowInfo::from_window_info_repository(
@@ -63,13 +94,13 @@ impl StarRailRelicScanner {
             controller: Rc::new(RefCell::new(StarRailRepositoryScanController::new(
                 window_info_repo,
                 controller_config,
-                game_info.clone()
+                game_info.clone(),
+                capturer.clone(),
             )?)),
             game_info,
-            image_to_text: Self::get_image_to_text()?,
-            capturer: Self::get_capturer()?,
-
-            match_colors: MATCH_COLORS,
+            image_to_text: Self::get_image_to_text(&model_config)?,
+            capturer,
+            model_config,
         })
     }
 
@@ -84,91 +115,97 @@ impl StarRailRelicScanner {
             game_info.platform,
             window_info_repo
         )?;
+        let capturer = Self::get_capturer(&CapturerConfig::from_arg_matches(arg_matches)?, &game_info)?;
+        let model_config = OcrModelConfig::from_arg_matches(arg_matches)?;
         Ok(StarRailRelicScanner {
             scanner_config: StarRailRelicScannerConfig::from_arg_matches(arg_matches)?,
             window_info,
             controller: Rc::new(RefCell::new(
-                StarRailRepositoryScanController::from_arg_matches(window_info_repo, arg_matches, game_info.clone())?
+                StarRailRepositoryScanController::from_arg_matches(window_info_repo, arg_matches, game_info.clone(), capturer.clone())?
             )),
             game_info,
-            image_to_text: Self::get_image_to_text()?,
-            capturer: Self::get_capturer()?,
-            match_colors: MATCH_COLORS,
+            image_to_text: Self::get_image_to_text(&model_config)?,
+            capturer,
+            model_config,
         })
     }
 }
 
 impl StarRailRelicScanner {
-    pub fn capture_panel(&self) -> Result<RgbImage> {
-        self.capturer.capture_relative_to(
+    /// Grab the whole game window once, all the reads of a relic are cropped from this frame
+    pub fn capture_frame(&self) -> Result<CapturedFrame<RgbImage>> {
+        retry_capture(Self::MAX_CAPTURE_RETRY, || self.capturer.capture_frame(self.game_info.window))
+    }
+
+    pub fn capture_panel(&self, frame: &CapturedFrame<RgbImage>) -> Result<RgbImage> {
+        frame.crop_relative_to(
             self.window_info.panel_rect.to_rect_i32(),
             self.game_info.window.origin()
         )
     }
 
-    pub fn get_star(&self) -> Result<usize> {
+    pub fn get_star(&self, frame: &CapturedFrame<RgbImage>) -> Result<usize> {
         let pos: Pos<i32> = Pos {
             x: self.game_info.window.left + self.window_info.star_pos.x as i32,
             y: self.game_info.window.top + self.window_info.star_pos.y as i32,
         };
-        let color = self.capturer.capture_color(pos)?;
+        let color = frame.get_color(pos)?;
 
-        let (index, _) = self.match_colors.match_colors_star
-            .iter()
-            .enumerate()
-            .min_by_key(|&(_, match_color)| color_distance(match_color, &color))
-            .unwrap();
+        let (index, _) = self.window_info.star_colors.nearest(&color.into())
+            .ok_or_else(|| anyhow!("starrail_relic_star_colors is empty"))?;
 
         Ok(index + 1)
     }
 
-    pub fn get_lock(&self) -> Result<bool> {
+    pub fn get_lock(&self, frame: &CapturedFrame<RgbImage>) -> Result<bool> {
         let pos: Pos<i32> = Pos {
             x: self.game_info.window.left + self.window_info.lock_pos.x as i32,
             y: self.game_info.window.top + self.window_info.lock_pos.y as i32,
         };
-        let color = self.capturer.capture_color(pos)?;
+        let color = frame.get_color(pos)?;
 
-        let (index, _) = self.match_colors.match_colors_lock
-            .iter()
-            .enumerate()
-            .min_by_key(|&(_, match_color)| color_distance(match_color, &color))
-            .unwrap();
+        let (index, _) = self.window_info.lock_colors.nearest(&color.into())
+            .ok_or_else(|| anyhow!("starrail_relic_lock_colors is empty"))?;
 
         Ok(index == 0)
     }
 
-    pub fn get_discard(&self) -> Result<bool> {
+    pub fn get_discard(&self, frame: &CapturedFrame<RgbImage>) -> Result<bool> {
         let pos: Pos<i32> = Pos {
             x: self.game_info.window.left + self.window_info.discard_pos.x as i32,
             y: self.game_info.window.top + self.window_info.discard_pos.y as i32,
         };
-        let color = self.capturer.capture_color(pos)?;
+        let color = frame.get_color(pos)?;
 
-        let (index, _) = self.match_colors.match_colors_discard
-            .iter()
-            .enumerate()
-            .min_by_key(|&(_, match_color)| color_distance(match_color, &color))
-            .unwrap();
+        let (index, _) = self.window_info.discard_colors.nearest(&color.into())
+            .ok_or_else(|| anyhow!("starrail_relic_discard_colors is empty"))?;
 
         Ok(index == 0)
     }
 
-    pub fn get_equipper(&self) -> Result<String> {
+    pub fn get_equipper(&self, frame: &CapturedFrame<RgbImage>) -> Result<String> {
         let pos: Pos<i32> = Pos {
             x: self.game_info.window.left + self.window_info.equipper_pos.x as i32,
             y: self.game_info.window.top + self.window_info.equipper_pos.y as i32,
         };
-        let color = self.capturer.capture_color(pos)?;
+        let color = frame.get_color(pos)?;
 
-        let (name, _) = self.match_colors.match_colors_equipper
-            .iter()
-            .min_by_key(|&(_, match_color)| color_distance(match_color, &color))
-            .unwrap();
+        let (_, name) = self.window_info.equipper_colors.nearest(&color.into())
+            .ok_or_else(|| anyhow!("starrail_relic_equipper_colors is empty"))?;
 
         Ok(name.to_string())
     }
 
+    fn read_item(&self, frame: &CapturedFrame<RgbImage>) -> Result<SendItem> {
+        Ok(SendItem {
+            panel_image: self.capture_panel(frame)?,
+            equip: self.get_equipper(frame)?,
+            star: self.get_star(frame)?,
+            lock: self.get_lock(frame)?,
+            discard: self.get_discard(frame)?,
+        })
+    }
+
     pub fn get_item_count(&self) -> Result<i32> {
         let count = self.scanner_config.number;
         let item_name = "遗器数量";
@@ -205,12 +242,15 @@ impl StarRailRelicScanner {
         let (tx, rx) = mpsc::channel::<Option<SendItem>>();
         // let token = self.cancellation_token.clone();
         let count = self.get_item_count()?;
-        let worker = RelicScannerWorker::new(
-            self.window_info.clone(),
-            self.scanner_config.clone()
-        )?;
-
-        let join_handle = worker.run(rx);
+        let workers = (0..self.model_config.thread_count())
+            .map(|_| RelicScannerWorker::new(
+                self.window_info.clone(),
+                self.scanner_config.clone(),
+                &self.model_config,
+            ))
+            .collect::<Result<Vec<_>>>()?;
+
+        let join_handle = RelicScannerWorker::run(workers, rx);
         info!("Worker created");
 
         self.send(&tx, count);
@@ -246,11 +286,26 @@ impl StarRailRelicScanner {
             match pinned_generator.resume(()) {
                 CoroutineState::Yielded(_) => {
                     // let image = self.capture_panel().unwrap();
-                    let panel_image = self.capture_panel().unwrap();
-                    let equip = self.get_equipper().unwrap();
-                    let star = self.get_star().unwrap();
-                    let lock = self.get_lock().unwrap();
-                    let discard = self.get_discard().unwrap();
+                    let frame = match self.capture_frame() {
+                        Ok(frame) => frame,
+                        Err(e) => {
+                            error!("截图失败，停止扫描：{}", e);
+                            break;
+                        }
+                    };
+                    let item = match self.read_item(&frame) {
+                        Ok(item) => item,
+                        Err(e) => {
+                            // a region out of the window will not come back, other failures only affect this item
+                            if let Some(CaptureError::OutOfBounds { .. }) = e.downcast_ref::<CaptureError>() {
+                                error!("读取物品失败，请检查窗口位置与分辨率：{}", e);
+                                break;
+                            }
+                            warn!("读取物品失败，跳过：{}", e);
+                            continue;
+                        }
+                    };
+                    let star = item.star;
 
                     // todo normalize types
                     if (star as i32) < self.scanner_config.min_star {
@@ -261,7 +316,7 @@ impl StarRailRelicScanner {
                         break;
                     }
 
-                    if tx.send(Some(SendItem { panel_image, equip, star, lock, discard })).is_err() {
+                    if tx.send(Some(item)).is_err() {
                         break;
                     }
 
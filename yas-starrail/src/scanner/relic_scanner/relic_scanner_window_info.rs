@@ -1,60 +1,77 @@
+use yas::common::color::ColorPalette;
 use yas::positioning::{Pos, Rect};
 
 #[derive(Clone, yas_derive::YasWindowInfo, Debug)]
 pub struct RelicScannerWindowInfo {
-    #[window_info(rename = "starrail_relic_title_rect")]
+    #[window_info(rename = "starrail_relic_title_rect", anchor = "Right", scale = "Height")]
     pub title_rect: Rect<f64>,
 
-    #[window_info(rename = "starrail_relic_main_stat_name_rect")]
+    #[window_info(rename = "starrail_relic_main_stat_name_rect", anchor = "Right", scale = "Height")]
     pub main_stat_name_rect: Rect<f64>,
 
-    #[window_info(rename = "starrail_relic_main_stat_value_rect")]
+    #[window_info(rename = "starrail_relic_main_stat_value_rect", anchor = "Right", scale = "Height")]
     pub main_stat_value_rect: Rect<f64>,
 
     /// the sub stat name positions relative to window
-    #[window_info(rename = "starrail_relic_sub_stat0_name_rect")]
+    #[window_info(rename = "starrail_relic_sub_stat0_name_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_name_1: Rect<f64>,
-    #[window_info(rename = "starrail_relic_sub_stat1_name_rect")]
+    #[window_info(rename = "starrail_relic_sub_stat1_name_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_name_2: Rect<f64>,
-    #[window_info(rename = "starrail_relic_sub_stat2_name_rect")]
+    #[window_info(rename = "starrail_relic_sub_stat2_name_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_name_3: Rect<f64>,
-    #[window_info(rename = "starrail_relic_sub_stat3_name_rect")]
+    #[window_info(rename = "starrail_relic_sub_stat3_name_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_name_4: Rect<f64>,
 
     /// the sub stat value positions relative to window
-    #[window_info(rename = "starrail_relic_sub_stat0_value_rect")]
+    #[window_info(rename = "starrail_relic_sub_stat0_value_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_value_1: Rect<f64>,
-    #[window_info(rename = "starrail_relic_sub_stat1_value_rect")]
+    #[window_info(rename = "starrail_relic_sub_stat1_value_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_value_2: Rect<f64>,
-    #[window_info(rename = "starrail_relic_sub_stat2_value_rect")]
+    #[window_info(rename = "starrail_relic_sub_stat2_value_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_value_3: Rect<f64>,
-    #[window_info(rename = "starrail_relic_sub_stat3_value_rect")]
+    #[window_info(rename = "starrail_relic_sub_stat3_value_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_value_4: Rect<f64>,
 
-    #[window_info(rename = "starrail_relic_level_rect")]
+    #[window_info(rename = "starrail_relic_level_rect", anchor = "Right", scale = "Height")]
     pub level_rect: Rect<f64>,
 
-    #[window_info(rename = "starrail_relic_equip_rect")]
+    #[window_info(rename = "starrail_relic_equip_rect", anchor = "Right", scale = "Height")]
     pub equip_rect: Rect<f64>,
 
-    #[window_info(rename = "starrail_relic_equipper_pos")]
+    #[window_info(rename = "starrail_relic_equipper_pos", anchor = "Right", scale = "Height")]
     pub equipper_pos: Pos<f64>,
 
-    #[window_info(rename = "starrail_relic_item_count_rect")]
+    #[window_info(rename = "starrail_relic_item_count_rect", anchor = "Right", scale = "Height")]
     pub item_count_rect: Rect<f64>,
 
-    #[window_info(rename = "starrail_relic_star_pos")]
+    #[window_info(rename = "starrail_relic_star_pos", anchor = "Right", scale = "Height")]
     pub star_pos: Pos<f64>,
 
-    #[window_info(rename = "starrail_relic_lock_pos")]
+    #[window_info(rename = "starrail_relic_lock_pos", anchor = "Right", scale = "Height")]
     pub lock_pos: Pos<f64>,
 
-    #[window_info(rename = "starrail_relic_discard_pos")]
+    #[window_info(rename = "starrail_relic_discard_pos", anchor = "Right", scale = "Height")]
     pub discard_pos: Pos<f64>,
 
-    #[window_info(rename = "starrail_repository_panel_rect")]
+    /// the color sampled at `star_pos` for 1 to 5 stars
+    #[window_info(rename = "starrail_relic_star_colors", anchor = "Right", scale = "Height")]
+    pub star_colors: ColorPalette,
+
+    /// the colors sampled at `lock_pos`, the first one is locked
+    #[window_info(rename = "starrail_relic_lock_colors", anchor = "Right", scale = "Height")]
+    pub lock_colors: ColorPalette,
+
+    /// the colors sampled at `discard_pos`, the first one is discarded
+    #[window_info(rename = "starrail_relic_discard_colors", anchor = "Right", scale = "Height")]
+    pub discard_colors: ColorPalette,
+
+    /// the color sampled at `equipper_pos` for each character
+    #[window_info(rename = "starrail_relic_equipper_colors", anchor = "Right", scale = "Height")]
+    pub equipper_colors: ColorPalette,
+
+    #[window_info(rename = "starrail_repository_panel_rect", anchor = "Right", scale = "Height")]
     pub panel_rect: Rect<f64>,
 
-    #[window_info(rename = "starrail_repository_item_col")]
+    #[window_info(rename = "starrail_repository_item_col", anchor = "Left", scale = "Height")]
     pub col: i32,
 }
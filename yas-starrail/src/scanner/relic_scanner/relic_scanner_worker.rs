@@ -3,19 +3,41 @@ use std::sync::mpsc::Receiver;
 use std::thread::JoinHandle;
 
 use anyhow::Result;
-use image::{GenericImageView, RgbImage};
+use image::RgbImage;
 use log::{error, info, warn};
 
-use yas::ocr::{yas_ocr_model, ImageToText};
-use yas::positioning::{Pos, Rect};
+use yas::ocr::{recognize_field_array, yas_ocr_model, DecodeConstraint, ImageToText, OcrModelConfig};
+use yas::common::worker_pool::OrderedWorkerPool;
 
+use crate::relic::{RelicSetName, RelicStatName};
+use super::OCR_MODEL_NAME;
 use crate::scanner::relic_scanner::message_items::SendItem;
 use crate::scanner::relic_scanner::relic_scanner_window_info::RelicScannerWindowInfo;
 use crate::scanner::relic_scanner::scan_result::StarRailRelicScanResult;
 use crate::scanner::relic_scanner::StarRailRelicScannerConfig;
 
+/// Restricts the text of the panel fields, a misread character is corrected to the most probable valid text
+struct FieldConstraints {
+    title: DecodeConstraint,
+    stat_name: DecodeConstraint,
+    stat_value: DecodeConstraint,
+    level: DecodeConstraint,
+}
+
+impl FieldConstraints {
+    fn new() -> Result<Self> {
+        Ok(Self {
+            title: DecodeConstraint::lexicon(RelicSetName::ZH_CN_NAMES.iter().copied()),
+            stat_name: DecodeConstraint::lexicon(RelicStatName::ZH_CN_NAMES.iter().copied()),
+            stat_value: DecodeConstraint::stat_value()?,
+            level: DecodeConstraint::level()?,
+        })
+    }
+}
+
 pub struct RelicScannerWorker {
     model: Box<dyn ImageToText<RgbImage> + Send>,
+    constraints: FieldConstraints,
     window_info: RelicScannerWindowInfo,
     config: StarRailRelicScannerConfig,
 }
@@ -32,9 +54,11 @@ fn parse_level(s: &str) -> Result<i32> {
     return Ok(level);
 }
 
-fn get_image_to_text() -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
+fn get_image_to_text(model_config: &OcrModelConfig) -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
     let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(
-        yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json")?
+        model_config.build_yas_model(OCR_MODEL_NAME, |backend| {
+            yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json", backend)
+        })?
     );
     // let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(PPOCRChV4RecInfer::new()?);
     Ok(model)
@@ -44,47 +68,50 @@ impl RelicScannerWorker {
     pub fn new(
         window_info: RelicScannerWindowInfo,
         config: StarRailRelicScannerConfig,
+        model_config: &OcrModelConfig,
     ) -> Result<Self> {
         Ok(RelicScannerWorker {
-            model: get_image_to_text()?,
+            model: get_image_to_text(model_config)?,
+            constraints: FieldConstraints::new()?,
             window_info,
             config,
         })
     }
 
-    fn model_inference(&self, rect: Rect<f64>, captured_img: &RgbImage) -> Result<String> {
-        let relative_rect = rect.translate(Pos {
-            x: -self.window_info.panel_rect.left,
-            y: -self.window_info.panel_rect.top,
-        });
-
-        let raw_img = captured_img.view(
-            relative_rect.left as u32, relative_rect.top as u32, relative_rect.width as u32, relative_rect.height as u32
-        ).to_image();
-
-        let inference_result = self.model.image_to_text(&raw_img, false);
-
-        inference_result
-    }
-
     fn scan_item_image(&self, item: SendItem) -> Result<StarRailRelicScanResult> {
         let image = &item.panel_image;
 
-        let str_title = self.model_inference(self.window_info.title_rect, &image)?;
-        let str_main_stat_name = self.model_inference(self.window_info.main_stat_name_rect, &image)?;
-        let str_main_stat_value = self.model_inference(self.window_info.main_stat_value_rect, &image)?;
-
-        let str_sub_stat0_name = self.model_inference(self.window_info.sub_stat_name_1, &image)?;
-        let str_sub_stat1_name = self.model_inference(self.window_info.sub_stat_name_2, &image)?;
-        let str_sub_stat2_name = self.model_inference(self.window_info.sub_stat_name_3, &image)?;
-        let str_sub_stat3_name = self.model_inference(self.window_info.sub_stat_name_4, &image)?;
-        let str_sub_stat0_value = self.model_inference(self.window_info.sub_stat_value_1, &image)?;
-        let str_sub_stat1_value = self.model_inference(self.window_info.sub_stat_value_2, &image)?;
-        let str_sub_stat2_value = self.model_inference(self.window_info.sub_stat_value_3, &image)?;
-        let str_sub_stat3_value = self.model_inference(self.window_info.sub_stat_value_4, &image)?;
-
-        let str_level = self.model_inference(self.window_info.level_rect, &image)?;
-        let str_equip = self.model_inference(self.window_info.equip_rect, &image)?;
+        let constraints = &self.constraints;
+
+        let [
+            str_title,
+            str_main_stat_name,
+            str_main_stat_value,
+            str_sub_stat0_name,
+            str_sub_stat1_name,
+            str_sub_stat2_name,
+            str_sub_stat3_name,
+            str_sub_stat0_value,
+            str_sub_stat1_value,
+            str_sub_stat2_value,
+            str_sub_stat3_value,
+            str_level,
+            str_equip,
+        ] = recognize_field_array(&*self.model, image, self.window_info.panel_rect.origin(), [
+            (self.window_info.title_rect, Some(&constraints.title)),
+            (self.window_info.main_stat_name_rect, Some(&constraints.stat_name)),
+            (self.window_info.main_stat_value_rect, Some(&constraints.stat_value)),
+            (self.window_info.sub_stat_name_1, Some(&constraints.stat_name)),
+            (self.window_info.sub_stat_name_2, Some(&constraints.stat_name)),
+            (self.window_info.sub_stat_name_3, Some(&constraints.stat_name)),
+            (self.window_info.sub_stat_name_4, Some(&constraints.stat_name)),
+            (self.window_info.sub_stat_value_1, Some(&constraints.stat_value)),
+            (self.window_info.sub_stat_value_2, Some(&constraints.stat_value)),
+            (self.window_info.sub_stat_value_3, Some(&constraints.stat_value)),
+            (self.window_info.sub_stat_value_4, Some(&constraints.stat_value)),
+            (self.window_info.level_rect, Some(&constraints.level)),
+            (self.window_info.equip_rect, None),
+        ])?;
 
         Ok(StarRailRelicScanResult {
             name: str_title,
@@ -110,23 +137,24 @@ impl RelicScannerWorker {
         })
     }
 
-    pub fn run(self, rx: Receiver<Option<SendItem>>) -> JoinHandle<Vec<StarRailRelicScanResult>> {
+    /// Scan the items with the workers in parallel, the results are checked in the order of the items
+    pub fn run(workers: Vec<Self>, rx: Receiver<Option<SendItem>>) -> JoinHandle<Vec<StarRailRelicScanResult>> {
+        let config = workers[0].config.clone();
+        let info = workers[0].window_info.clone();
+
         std::thread::spawn(move || {
             let mut results = Vec::new();
             let mut hash = HashSet::new();
             let mut consecutive_dup_count = 0;
 
-            let is_verbose = self.config.verbose;
-            let min_level = self.config.min_level;
-            let info = self.window_info.clone();
+            let is_verbose = config.verbose;
+            let min_level = config.min_level;
 
-            for (_cnt, item) in rx.into_iter().enumerate() {
-                let item = match item {
-                    Some(v) => v,
-                    None => break,
-                };
+            let items = rx.into_iter().map_while(|item| item);
+            let pool = OrderedWorkerPool::spawn(workers, items, |worker: &Self, item| worker.scan_item_image(item));
 
-                let result = match self.scan_item_image(item) {
+            for result in pool {
+                let result = match result {
                     Ok(v) => v,
                     Err(e) => {
                         error!("识别错误: {}", e);
@@ -156,7 +184,7 @@ impl RelicScannerWorker {
                     results.push(result);
                 }
 
-                if consecutive_dup_count >= info.col && !self.config.ignore_dup {
+                if consecutive_dup_count >= info.col && !config.ignore_dup {
                     error!("识别到连续多个重复物品，可能为翻页错误，或者为非背包顶部开始扫描");
                     break;
                 }
@@ -7,7 +7,7 @@ use crate::scanner_controller::repository_layout::config::StarRailRepositoryScan
 use yas::utils;
 use log::{info, error};
 use std::time::SystemTime;
-use yas::capture::{Capturer, GenericCapturer};
+use yas::capture::Capturer;
 use yas::system_control::SystemControl;
 use crate::scanner_controller::repository_layout::window_info::StarRailRepositoryScanControllerWindowInfo;
 use anyhow::{anyhow, Result};
@@ -52,16 +52,13 @@ fn calc_pool(row: &Vec<u8>) -> f32 {
     pool
 }
 
-fn get_capturer() -> Result<Rc<dyn Capturer<RgbImage>>> {
-    Ok(Rc::new(GenericCapturer::new()?))
-}
-
 // constructor
 impl StarRailRepositoryScanController {
     pub fn new(
         window_info_repo: &WindowInfoRepository,
         config: StarRailRepositoryScannerLogicConfig,
-        game_info: GameInfo
+        game_info: GameInfo,
+        capturer: Rc<dyn Capturer<RgbImage>>,
     ) -> Result<Self> {
         let window_info = StarRailRepositoryScanControllerWindowInfo::from_window_info_repository(
             game_info.window.to_rect_usize().size(),
@@ -70,8 +67,8 @@ impl StarRailRepositoryScanController {
             window_info_repo
         )?;
 
-        let row_count = window_info.starrail_repository_item_row;
-        let col_count = window_info.starrail_repository_item_col;
+        let row_count = window_info.repository.item_row;
+        let col_count = window_info.repository.item_col;
 
         Ok(StarRailRepositoryScanController {
             system_control: SystemControl::new(),
@@ -94,19 +91,21 @@ impl StarRailRepositoryScanController {
             game_info,
             scanned_count: 0,
 
-            capturer: get_capturer()?,
+            capturer,
         })
     }
 
     pub fn from_arg_matches(
         window_info_repo: &WindowInfoRepository,
         arg_matches: &ArgMatches,
-        game_info: GameInfo
+        game_info: GameInfo,
+        capturer: Rc<dyn Capturer<RgbImage>>,
     ) -> Result<Self> {
         Self::new(
             window_info_repo,
             StarRailRepositoryScannerLogicConfig::from_arg_matches(arg_matches)?,
-            game_info
+            game_info,
+            capturer,
         )
     }
 }
@@ -274,9 +273,9 @@ impl StarRailRepositoryScanController {
         let (row, col) = (row as u32, col as u32);
         let origin = self.game_info.window.to_rect_f64().origin();
 
-        let gap = self.window_info.item_gap_size;
-        let margin = self.window_info.scan_margin_pos;
-        let size = self.window_info.item_size;
+        let gap = self.window_info.repository.item_gap_size;
+        let margin = self.window_info.repository.scan_margin_pos;
+        let size = self.window_info.repository.item_size;
 
         let left = origin.x + margin.x + (gap.width + size.width) * (col as f64) + size.width / 2.0;
         let top = origin.y + margin.y + (gap.height + size.height) * (row as f64) + size.height / 2.0;
@@ -359,7 +358,7 @@ impl StarRailRepositoryScanController {
         let mut diff_flag = false;
         while now.elapsed()?.as_millis() < self.config.max_wait_switch_item as u128 {
             let im = self.capturer.capture_relative_to(
-                self.window_info.pool_rect.to_rect_i32(),
+                self.window_info.repository.pool_rect.to_rect_i32(),
                 self.game_info.window.origin()
             )?;
 
@@ -1,9 +1,13 @@
 use anyhow::anyhow;
-use clap::{ArgMatches, Args, command};
-use log::info;
+use clap::{ArgMatches, Args, command, FromArgMatches};
+use yas::capture::{CapturerConfig, StreamingCapturerConfig};
+use yas::ocr::OcrModelConfig;
+use log::{info, warn};
 use yas::export::ExportAssets;
-use yas::game_info::{GameInfo, GameInfoBuilder};
-use yas::window_info::{load_window_info_repo, WindowInfoRepository};
+use yas::game_info::{GameInfo, GameInfoBuilder, GameInfoConfig};
+use yas::text::TextMatchConfig;
+use yas::window_info::{load_window_info_repo, WindowInfoConfig, WindowInfoRepository};
+use crate::echo::WWEcho;
 use crate::scanner::{WWEchoScanner, WWEchoScannerConfig};
 use crate::scanner_controller::WWRepositoryLayoutConfig;
 use anyhow::Result;
@@ -23,23 +27,32 @@ impl WWEchoScannerApplication {
         let mut cmd = command!();
         cmd = <WWEchoScannerConfig as Args>::augment_args_for_update(cmd);
         cmd = <WWRepositoryLayoutConfig as Args>::augment_args_for_update(cmd);
+        cmd = <CapturerConfig as Args>::augment_args_for_update(cmd);
+        cmd = <GameInfoConfig as Args>::augment_args_for_update(cmd);
+        cmd = <WindowInfoConfig as Args>::augment_args_for_update(cmd);
+        cmd = <OcrModelConfig as Args>::augment_args_for_update(cmd);
+        cmd = <TextMatchConfig as Args>::augment_args_for_update(cmd);
+        cmd = <StreamingCapturerConfig as Args>::augment_args_for_update(cmd);
         // cmd = <ExportRelicConfig as Args>::augment_args_for_update(cmd);
         cmd
     }
 
-    fn get_window_info_repository() -> WindowInfoRepository {
+    /// The built-in window info
+    pub fn get_window_info_repository() -> WindowInfoRepository {
         load_window_info_repo!(
             "../../window_info/windows2560x1440.json"
         )
     }
 
-    fn get_game_info() -> anyhow::Result<GameInfo> {
-        let game_info = GameInfoBuilder::new()
+    fn get_game_info(arg_matches: &ArgMatches) -> anyhow::Result<GameInfo> {
+        let mut builder = GameInfoBuilder::new();
+        builder
             .add_local_window_name("鸣潮")
             .add_local_window_name("Wuthering Waves")
             // .add_cloud_window_name("云·星穹铁道")
-            .build();
-        game_info
+            .add_window_class("client-win64-shipping.exe");
+        GameInfoConfig::from_arg_matches(arg_matches)?.apply_to(&mut builder);
+        builder.build()
     }
 }
 
@@ -48,8 +61,13 @@ impl WWEchoScannerApplication {
         println!("START");
         // Self::init();
         let arg_matches = &self.arg_matches;
-        let window_info_repository = Self::get_window_info_repository();
-        let game_info = Self::get_game_info()?;
+        let mut window_info_repository = Self::get_window_info_repository();
+        WindowInfoConfig::from_arg_matches(arg_matches)?.merge_into(&mut window_info_repository)?;
+        let capturer_config = CapturerConfig::from_arg_matches(arg_matches)?;
+        let game_info = match capturer_config.replay_game_info()? {
+            Some(game_info) => game_info,
+            None => Self::get_game_info(arg_matches)?,
+        };
 
         info!("window: {:?}", game_info.window);
         info!("ui: {:?}", game_info.ui);
@@ -70,10 +88,20 @@ impl WWEchoScannerApplication {
             game_info.clone()
         )?;
 
+        let text_match_config = TextMatchConfig::from_arg_matches(arg_matches)?;
         let results = scanner.scan()?;
 
+        let mut echoes = Vec::new();
         for item in results.iter() {
-            println!("{:?}", item);
+            match WWEcho::from_scan_result(item, text_match_config.max_distance) {
+                Some(echo) => echoes.push(echo),
+                None => warn!("无法解析声骸：{:?}", item),
+            }
+        }
+        info!("共识别到 {} 个声骸，其中 {} 个可以解析", results.len(), echoes.len());
+
+        for echo in echoes.iter() {
+            println!("{:?}", echo);
         }
 
         Ok(())
@@ -1,5 +1,7 @@
 use crate::echo::{WWEchoName, WWStat};
+use crate::scanner::WWEchoScanResult;
 
+#[derive(Debug)]
 pub struct WWEcho {
     pub name: WWEchoName,
     pub main_stat1: WWStat,
@@ -9,3 +11,37 @@ pub struct WWEcho {
     pub star: usize,
     pub lock: bool,
 }
+
+impl TryFrom<&WWEchoScanResult> for WWEcho {
+    type Error = ();
+
+    fn try_from(value: &WWEchoScanResult) -> Result<Self, Self::Error> {
+        WWEcho::from_scan_result(value, 0).ok_or(())
+    }
+}
+
+impl WWEcho {
+    /// Convert a scan result, the recognized names may be up to `max_distance` edits away from the known names
+    pub fn from_scan_result(value: &WWEchoScanResult, max_distance: usize) -> Option<Self> {
+        let name = WWEchoName::from_chs_fuzzy(&value.name, max_distance)?;
+        let main_stat1 = WWStat::from_chs_fuzzy(&value.main_stat1_name, &value.main_stat1_value, max_distance)?;
+        let main_stat2 = WWStat::from_chs_fuzzy(&value.main_stat2_name, &value.main_stat2_value, max_distance)?;
+
+        // an echo has fewer sub stats before it is tuned, the remaining rows are empty
+        let sub_stats = value.sub_stat_names.iter()
+            .zip(value.sub_stat_values.iter())
+            .filter(|(name, _)| !name.is_empty())
+            .filter_map(|(name, value)| WWStat::from_chs_fuzzy(name, value, max_distance))
+            .collect();
+
+        Some(WWEcho {
+            name,
+            main_stat1,
+            main_stat2,
+            sub_stats,
+            level: value.level,
+            star: value.star,
+            lock: false,
+        })
+    }
+}
@@ -1,4 +1,18 @@
 // use yas_derive_wuthering_waves::yas_wuthering_waves_echoes;
+use lazy_static::lazy_static;
+use yas::text::FuzzyMatcher;
 
 yas_derive_wuthering_waves::yas_wuthering_waves_echoes!("yas-wutheringwaves/data/echoes.json");
 
+
+lazy_static! {
+    static ref ECHO_NAME_MATCHER: FuzzyMatcher<WWEchoName> =
+        FuzzyMatcher::new(WWEchoName::CHS_NAMES.iter().filter_map(|&name| Some((name, WWEchoName::from_chs(name)?))));
+}
+
+impl WWEchoName {
+    /// Like `from_chs`, but a name within `max_distance` edits of a known name is accepted too
+    pub fn from_chs_fuzzy(chs: &str, max_distance: usize) -> Option<Self> {
+        ECHO_NAME_MATCHER.find(chs, max_distance).copied()
+    }
+}
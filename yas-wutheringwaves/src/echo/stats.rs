@@ -1,3 +1,7 @@
+use lazy_static::lazy_static;
+use log::error;
+use yas::text::FuzzyMatcher;
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, strum_macros::Display)]
 pub enum WWStatName {
     CriticalDamage,
@@ -23,6 +27,27 @@ pub enum WWStatName {
 }
 
 impl WWStatName {
+    /// The names accepted by `from_chs`
+    pub const CHS_NAMES: [&'static str; 17] = [
+        "暴击伤害",
+        "暴击率",
+        "冷凝伤害加成",
+        "气动伤害加成",
+        "热熔伤害加成",
+        "导电伤害加成",
+        "湮灭伤害加成",
+        "衍射伤害加成",
+        "共鸣效率",
+        "攻击",
+        "防御",
+        "生命",
+        "治疗效果加成",
+        "普攻伤害加成",
+        "重击伤害加成",
+        "共鸣技能伤害加成",
+        "共鸣解放伤害加成",
+    ];
+
     pub fn from_chs(chs: &str, is_percentage: bool) -> Option<Self> {
         let ret = match chs {
             "暴击伤害" => Self::CriticalDamage,
@@ -47,9 +72,42 @@ impl WWStatName {
 
         Some(ret)
     }
+
+    /// Like `from_chs`, but a name within `max_distance` edits of a known name is accepted too
+    pub fn from_chs_fuzzy(chs: &str, is_percentage: bool, max_distance: usize) -> Option<Self> {
+        let chs = STAT_NAME_MATCHER.find(chs, max_distance)?;
+        Self::from_chs(chs, is_percentage)
+    }
+}
+
+lazy_static! {
+    static ref STAT_NAME_MATCHER: FuzzyMatcher<&'static str> =
+        FuzzyMatcher::new(WWStatName::CHS_NAMES.map(|name| (name, name)));
 }
 
+#[derive(Debug)]
 pub struct WWStat {
     pub name: WWStatName,
     pub value: f64,
 }
+
+impl WWStat {
+    // e.g. "攻击", "12.5%"
+    pub fn from_chs_fuzzy(name: &str, value: &str, max_distance: usize) -> Option<Self> {
+        let is_percentage = value.contains('%');
+        let name = WWStatName::from_chs_fuzzy(name, is_percentage, max_distance)?;
+
+        let mut value = match value.replace(['%', ','], "").parse::<f64>() {
+            Ok(v) => v,
+            Err(_) => {
+                error!("stat `{}` value `{}` parse error", name, value);
+                return None;
+            },
+        };
+        if is_percentage {
+            value /= 100.0;
+        }
+
+        Some(WWStat { name, value })
+    }
+}
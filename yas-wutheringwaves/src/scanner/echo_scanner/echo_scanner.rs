@@ -2,7 +2,6 @@ use std::cell::RefCell;
 use std::ops::{CoroutineState, Coroutine};
 use std::pin::Pin;
 use std::rc::Rc;
-use std::sync::mpsc;
 use std::sync::mpsc::Sender;
 use std::time::SystemTime;
 
@@ -12,17 +11,19 @@ use log::{error, info};
 use regex::Regex;
 use clap::FromArgMatches;
 
-use yas::capture::{Capturer, GenericCapturer, StreamingCapturer};
-use yas::game_info::GameInfo;
-use yas::ocr::{ImageToText, yas_ocr_model};
-use yas::window_info::{WindowInfoRepository, FromWindowInfoRepository};
+use yas::capture::{Capturer, CapturerConfig, StreamingCapturer, StreamingCapturerConfig};
+use yas::game_info::{GameInfo, Platform, UI};
+use yas::positioning::Size;
+use yas::ocr::{ImageToText, OcrModelConfig, yas_ocr_model};
+use yas::window_info::{merge_required_keys, merge_window_info_values, WindowInfoRepository, FromWindowInfoRepository, RequiredWindowInfoKey, RequiredWindowInfoKeys, WindowInfoType, WindowInfoValues};
 
+use crate::scanner::echo_scanner::OCR_MODEL_NAME;
 use crate::scanner::echo_scanner::echo_scanner_config::WWEchoScannerConfig;
 use crate::scanner::echo_scanner::echo_scanner_window_info::EchoScannerWindowInfo;
 use crate::scanner::echo_scanner::echo_scanner_worker::WWEchoScannerWorker;
 use crate::scanner::echo_scanner::message_item::SendItem;
 use crate::scanner::echo_scanner::scan_result::WWEchoScanResult;
-use crate::scanner_controller::{ReturnResult, WWRepositoryLayoutConfig, WWRepositoryLayoutScanController};
+use crate::scanner_controller::{ReturnResult, WWRepositoryLayoutConfig, WWRepositoryLayoutScanController, WWRepositoryLayoutWindowinfo};
 
 pub struct WWEchoScanner {
     scanner_config: WWEchoScannerConfig,
@@ -31,27 +32,60 @@ pub struct WWEchoScanner {
     image_to_text: Box<dyn ImageToText<RgbImage> + Send>,
     controller: Rc<RefCell<WWRepositoryLayoutScanController>>,
     capturer: Rc<dyn Capturer<RgbImage>>,
+    model_config: OcrModelConfig,
+    capturer_config: CapturerConfig,
+    streaming_config: StreamingCapturerConfig,
 }
 
 impl WWEchoScanner {
-    fn get_image_to_text() -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
+    pub fn get_image_to_text(model_config: &OcrModelConfig) -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
         let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(
-            yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json")?
+            model_config.build_yas_model(OCR_MODEL_NAME, |backend| {
+                yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json", backend)
+            })?
         );
         // let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(yas::ocr::PPOCRChV4RecInfer::new()?);
         Ok(model)
     }
 
-    fn get_capturer() -> Result<Rc<dyn Capturer<RgbImage>>> {
-        Ok(Rc::new(GenericCapturer::new()?))
+    /// The window info keys read by the scanner and its controller, each key once
+    pub fn required_window_info_keys() -> Vec<RequiredWindowInfoKey> {
+        merge_required_keys(
+            EchoScannerWindowInfo::required_window_info_keys(),
+            WWRepositoryLayoutWindowinfo::required_window_info_keys(),
+        )
+    }
+
+    /// The window info of the scanner and its controller for a window size, each key once
+    pub fn window_info_values(
+        window_info_repo: &WindowInfoRepository,
+        window_size: Size<usize>,
+        ui: UI,
+        platform: Platform,
+    ) -> Result<Vec<(String, WindowInfoType)>> {
+        Ok(merge_window_info_values(
+            EchoScannerWindowInfo::from_window_info_repository(window_size, ui, platform, window_info_repo)?
+                .window_info_values(),
+            WWRepositoryLayoutWindowinfo::from_window_info_repository(window_size, ui, platform, window_info_repo)?
+                .window_info_values(),
+        ))
+    }
+
+    fn get_capturer(config: &CapturerConfig, game_info: &GameInfo) -> Result<Rc<dyn Capturer<RgbImage>>> {
+        let capturer: Rc<dyn Capturer<RgbImage> + Send> = Rc::from(config.build_capturer(game_info, "ww_echo")?);
+        Ok(capturer)
     }
 
     pub fn new(
         window_info_repo: &WindowInfoRepository,
         config: WWEchoScannerConfig,
         controller_config: WWRepositoryLayoutConfig,
+        capturer_config: CapturerConfig,
+        streaming_config: StreamingCapturerConfig,
+        model_config: OcrModelConfig,
         game_info: GameInfo
     ) -> anyhow::Result<Self> {
+        let capturer = Self::get_capturer(&capturer_config, &game_info)?;
         Ok(Self {
             scanner_config: config,
             window_info: EchoScannerWindowInfo::from_window_info_repository(
@@ -63,11 +97,15 @@ impl WWEchoScanner {
             controller: Rc::new(RefCell::new(WWRepositoryLayoutScanController::new(
                 window_info_repo,
                 controller_config,
-                game_info.clone()
+                game_info.clone(),
+                capturer.clone(),
             )?)),
             game_info,
-            image_to_text: Self::get_image_to_text()?,
-            capturer: Self::get_capturer()?,
+            image_to_text: Self::get_image_to_text(&model_config)?,
+            capturer,
+            capturer_config,
+            streaming_config,
+            model_config,
         })
     }
 
@@ -82,15 +120,21 @@ impl WWEchoScanner {
             game_info.platform,
             window_info_repo
         )?;
+        let capturer_config = CapturerConfig::from_arg_matches(arg_matches)?;
+        let capturer = Self::get_capturer(&capturer_config, &game_info)?;
+        let model_config = OcrModelConfig::from_arg_matches(arg_matches)?;
         Ok(Self {
             scanner_config: WWEchoScannerConfig::from_arg_matches(arg_matches)?,
             window_info,
             controller: Rc::new(RefCell::new(
-                WWRepositoryLayoutScanController::from_arg_matches(window_info_repo, arg_matches, game_info.clone())?
+                WWRepositoryLayoutScanController::from_arg_matches(window_info_repo, arg_matches, game_info.clone(), capturer.clone())?
             )),
             game_info,
-            image_to_text: Self::get_image_to_text()?,
-            capturer: Self::get_capturer()?,
+            image_to_text: Self::get_image_to_text(&model_config)?,
+            capturer,
+            capturer_config,
+            streaming_config: StreamingCapturerConfig::from_arg_matches(arg_matches)?,
+            model_config,
         })
     }
 }
@@ -132,18 +176,24 @@ impl WWEchoScanner {
 
         let now = SystemTime::now();
 
-        let (image_tx, image_rx) = mpsc::channel::<SendItem>();
         let count = self.get_item_count()?;
 
-        let worker = WWEchoScannerWorker::new(
-            self.window_info.clone(),
-            self.scanner_config.clone()
-        )?;
+        let workers = (0..self.model_config.thread_count())
+            .map(|_| WWEchoScannerWorker::new(
+                self.window_info.clone(),
+                self.scanner_config.clone(),
+                &self.model_config,
+            ))
+            .collect::<Result<Vec<_>>>()?;
 
-        let worker_join_handle = worker.run(image_rx);
         let panel_rect = self.window_info.panel_rect.to_rect_i32().translate(self.game_info.window.origin());
-        let streaming_capturer = StreamingCapturer::new(panel_rect);
-        let (capturer_join_handle, cancel_image_capturer) = streaming_capturer.start_transform(image_tx, |x| SendItem { panel_image: x });
+        let streaming_capturer = StreamingCapturer::new(
+            panel_rect,
+            self.capturer_config.build_capturer(&self.game_info, "ww_echo_panel_stream")?,
+            self.streaming_config.clone(),
+        );
+        let (image_rx, capturer_join_handle, cancel_image_capturer) = streaming_capturer.start_transform(|x| SendItem { panel_image: x });
+        let worker_join_handle = WWEchoScannerWorker::run(workers, image_rx);
 
         // self.send(&tx, count);
         //
@@ -3,60 +3,60 @@ use yas_derive::YasWindowInfo;
 
 #[derive(YasWindowInfo, Debug, Clone)]
 pub struct EchoScannerWindowInfo {
-    #[window_info(rename = "ww_echo_title_rect")]
+    #[window_info(rename = "ww_echo_title_rect", anchor = "Right", scale = "Height")]
     pub title_rect: Rect<f64>,
 
-    #[window_info(rename = "ww_echo_main_stat1_name_rect")]
+    #[window_info(rename = "ww_echo_main_stat1_name_rect", anchor = "Right", scale = "Height")]
     pub main_stat1_name_rect: Rect<f64>,
-    #[window_info(rename = "ww_echo_main_stat1_value_rect")]
+    #[window_info(rename = "ww_echo_main_stat1_value_rect", anchor = "Right", scale = "Height")]
     pub main_stat1_value_rect: Rect<f64>,
-    #[window_info(rename = "ww_echo_main_stat2_name_rect")]
+    #[window_info(rename = "ww_echo_main_stat2_name_rect", anchor = "Right", scale = "Height")]
     pub main_stat2_name_rect: Rect<f64>,
-    #[window_info(rename = "ww_echo_main_stat2_value_rect")]
+    #[window_info(rename = "ww_echo_main_stat2_value_rect", anchor = "Right", scale = "Height")]
     pub main_stat2_value_rect: Rect<f64>,
 
     // the sub stat name positions relative to window
-    #[window_info(rename = "ww_echo_sub_stat0_name_rect")]
+    #[window_info(rename = "ww_echo_sub_stat0_name_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_name_1: Rect<f64>,
-    #[window_info(rename = "ww_echo_sub_stat1_name_rect")]
+    #[window_info(rename = "ww_echo_sub_stat1_name_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_name_2: Rect<f64>,
-    #[window_info(rename = "ww_echo_sub_stat2_name_rect")]
+    #[window_info(rename = "ww_echo_sub_stat2_name_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_name_3: Rect<f64>,
-    #[window_info(rename = "ww_echo_sub_stat3_name_rect")]
+    #[window_info(rename = "ww_echo_sub_stat3_name_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_name_4: Rect<f64>,
-    #[window_info(rename = "ww_echo_sub_stat4_name_rect")]
+    #[window_info(rename = "ww_echo_sub_stat4_name_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_name_5: Rect<f64>,
 
     // the sub stat value positions relative to window
-    #[window_info(rename = "ww_echo_sub_stat0_value_rect")]
+    #[window_info(rename = "ww_echo_sub_stat0_value_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_value_1: Rect<f64>,
-    #[window_info(rename = "ww_echo_sub_stat1_value_rect")]
+    #[window_info(rename = "ww_echo_sub_stat1_value_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_value_2: Rect<f64>,
-    #[window_info(rename = "ww_echo_sub_stat2_value_rect")]
+    #[window_info(rename = "ww_echo_sub_stat2_value_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_value_3: Rect<f64>,
-    #[window_info(rename = "ww_echo_sub_stat3_value_rect")]
+    #[window_info(rename = "ww_echo_sub_stat3_value_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_value_4: Rect<f64>,
-    #[window_info(rename = "ww_echo_sub_stat4_value_rect")]
+    #[window_info(rename = "ww_echo_sub_stat4_value_rect", anchor = "Right", scale = "Height")]
     pub sub_stat_value_5: Rect<f64>,
 
-    #[window_info(rename = "ww_echo_level_rect")]
+    #[window_info(rename = "ww_echo_level_rect", anchor = "Right", scale = "Height")]
     pub level_rect: Rect<f64>,
 
     // #[window_info(rename = "ww_echo_equip_rect")]
     // pub equip_rect: Rect<f64>,
 
-    #[window_info(rename = "ww_echo_item_count_rect")]
+    #[window_info(rename = "ww_echo_item_count_rect", anchor = "Left", scale = "Height")]
     pub item_count_rect: Rect<f64>,
 
-    #[window_info(rename = "ww_echo_star_pos")]
+    #[window_info(rename = "ww_echo_star_pos", anchor = "Right", scale = "Height")]
     pub star_pos: Pos<f64>,
 
     // #[window_info(rename = "ww_echo_lock_pos")]
     // pub lock_pos: Pos<f64>,
 
-    #[window_info(rename = "ww_repository_panel_rect")]
+    #[window_info(rename = "ww_repository_panel_rect", anchor = "Right", scale = "Height")]
     pub panel_rect: Rect<f64>,
 
-    #[window_info(rename = "ww_repository_item_col")]
+    #[window_info(rename = "ww_repository_item_col", anchor = "Left", scale = "Height")]
     pub col: i32,
 }
@@ -1,19 +1,40 @@
 use std::collections::HashSet;
 use std::sync::mpsc::Receiver;
 use std::thread::JoinHandle;
-use image::{GenericImageView, RgbImage};
-use yas::ocr::{ImageToText, yas_ocr_model};
+use image::RgbImage;
+use yas::ocr::{recognize_field_array, DecodeConstraint, ImageToText, OcrModelConfig, yas_ocr_model};
+use crate::echo::{WWEchoName, WWStatName};
+use super::OCR_MODEL_NAME;
 use crate::scanner::echo_scanner::echo_scanner_config::WWEchoScannerConfig;
 use crate::scanner::echo_scanner::echo_scanner_window_info::EchoScannerWindowInfo;
 use anyhow::Result;
 use log::{error, info, warn};
-use rayon::iter::ParallelBridge;
-use yas::positioning::{Pos, Rect};
+use yas::common::worker_pool::OrderedWorkerPool;
 use crate::scanner::echo_scanner::message_item::SendItem;
 use crate::scanner::echo_scanner::scan_result::WWEchoScanResult;
 
+/// Restricts the text of the panel fields, a misread character is corrected to the most probable valid text
+struct FieldConstraints {
+    title: DecodeConstraint,
+    stat_name: DecodeConstraint,
+    stat_value: DecodeConstraint,
+    level: DecodeConstraint,
+}
+
+impl FieldConstraints {
+    fn new() -> Result<Self> {
+        Ok(Self {
+            title: DecodeConstraint::lexicon(WWEchoName::CHS_NAMES),
+            stat_name: DecodeConstraint::lexicon(WWStatName::CHS_NAMES),
+            stat_value: DecodeConstraint::stat_value()?,
+            level: DecodeConstraint::level()?,
+        })
+    }
+}
+
 pub struct WWEchoScannerWorker {
     model: Box<dyn ImageToText<RgbImage> + Send>,
+    constraints: FieldConstraints,
     window_info: EchoScannerWindowInfo,
     config: WWEchoScannerConfig,
 }
@@ -30,9 +51,11 @@ fn parse_level(s: &str) -> Result<usize> {
     return Ok(level);
 }
 
-fn get_image_to_text() -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
+fn get_image_to_text(model_config: &OcrModelConfig) -> Result<Box<dyn ImageToText<RgbImage> + Send>> {
     let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(
-        yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json")?
+        model_config.build_yas_model(OCR_MODEL_NAME, |backend| {
+            yas_ocr_model!("./models/model_training.onnx", "./models/index_2_word.json", backend)
+        })?
     );
     // let model: Box<dyn ImageToText<RgbImage> + Send> = Box::new(PPOCRChV4RecInfer::new()?);
     Ok(model)
@@ -42,29 +65,16 @@ impl WWEchoScannerWorker {
     pub fn new(
         window_info: EchoScannerWindowInfo,
         config: WWEchoScannerConfig,
+        model_config: &OcrModelConfig,
     ) -> Result<Self> {
         Ok(Self {
-            model: get_image_to_text()?,
+            model: get_image_to_text(model_config)?,
+            constraints: FieldConstraints::new()?,
             window_info,
             config,
         })
     }
 
-    fn model_inference(&self, rect: Rect<f64>, captured_img: &RgbImage) -> Result<String> {
-        let relative_rect = rect.translate(Pos {
-            x: -self.window_info.panel_rect.left,
-            y: -self.window_info.panel_rect.top,
-        });
-
-        let raw_img = captured_img.view(
-            relative_rect.left as u32, relative_rect.top as u32, relative_rect.width as u32, relative_rect.height as u32
-        ).to_image();
-
-        let inference_result = self.model.image_to_text(&raw_img, false);
-
-        inference_result
-    }
-
     fn determine_star(&self, im: &RgbImage) -> Result<usize> {
         let pos_relative_to_panel = self.window_info.star_pos - self.window_info.panel_rect.origin();
 
@@ -80,24 +90,43 @@ impl WWEchoScannerWorker {
     fn parse_item(&self, item: SendItem) -> Result<WWEchoScanResult> {
         let image = &item.panel_image;
 
-        let str_title = self.model_inference(self.window_info.title_rect, &image)?;
-        let str_main_stat1_name = self.model_inference(self.window_info.main_stat1_name_rect, &image)?;
-        let str_main_stat1_value = self.model_inference(self.window_info.main_stat1_value_rect, &image)?;
-        let str_main_stat2_name = self.model_inference(self.window_info.main_stat2_name_rect, &image)?;
-        let str_main_stat2_value = self.model_inference(self.window_info.main_stat2_value_rect, &image)?;
-
-        let str_sub_stat0_name = self.model_inference(self.window_info.sub_stat_name_1, &image)?;
-        let str_sub_stat1_name = self.model_inference(self.window_info.sub_stat_name_2, &image)?;
-        let str_sub_stat2_name = self.model_inference(self.window_info.sub_stat_name_3, &image)?;
-        let str_sub_stat3_name = self.model_inference(self.window_info.sub_stat_name_4, &image)?;
-        let str_sub_stat4_name = self.model_inference(self.window_info.sub_stat_name_5, &image)?;
-        let str_sub_stat0_value = self.model_inference(self.window_info.sub_stat_value_1, &image)?;
-        let str_sub_stat1_value = self.model_inference(self.window_info.sub_stat_value_2, &image)?;
-        let str_sub_stat2_value = self.model_inference(self.window_info.sub_stat_value_3, &image)?;
-        let str_sub_stat3_value = self.model_inference(self.window_info.sub_stat_value_4, &image)?;
-        let str_sub_stat4_value = self.model_inference(self.window_info.sub_stat_value_5, &image)?;
-
-        let str_level = self.model_inference(self.window_info.level_rect, &image)?;
+        let constraints = &self.constraints;
+
+        let [
+            str_title,
+            str_main_stat1_name,
+            str_main_stat1_value,
+            str_main_stat2_name,
+            str_main_stat2_value,
+            str_sub_stat0_name,
+            str_sub_stat1_name,
+            str_sub_stat2_name,
+            str_sub_stat3_name,
+            str_sub_stat4_name,
+            str_sub_stat0_value,
+            str_sub_stat1_value,
+            str_sub_stat2_value,
+            str_sub_stat3_value,
+            str_sub_stat4_value,
+            str_level,
+        ] = recognize_field_array(&*self.model, image, self.window_info.panel_rect.origin(), [
+            (self.window_info.title_rect, Some(&constraints.title)),
+            (self.window_info.main_stat1_name_rect, Some(&constraints.stat_name)),
+            (self.window_info.main_stat1_value_rect, Some(&constraints.stat_value)),
+            (self.window_info.main_stat2_name_rect, Some(&constraints.stat_name)),
+            (self.window_info.main_stat2_value_rect, Some(&constraints.stat_value)),
+            (self.window_info.sub_stat_name_1, Some(&constraints.stat_name)),
+            (self.window_info.sub_stat_name_2, Some(&constraints.stat_name)),
+            (self.window_info.sub_stat_name_3, Some(&constraints.stat_name)),
+            (self.window_info.sub_stat_name_4, Some(&constraints.stat_name)),
+            (self.window_info.sub_stat_name_5, Some(&constraints.stat_name)),
+            (self.window_info.sub_stat_value_1, Some(&constraints.stat_value)),
+            (self.window_info.sub_stat_value_2, Some(&constraints.stat_value)),
+            (self.window_info.sub_stat_value_3, Some(&constraints.stat_value)),
+            (self.window_info.sub_stat_value_4, Some(&constraints.stat_value)),
+            (self.window_info.sub_stat_value_5, Some(&constraints.stat_value)),
+            (self.window_info.level_rect, Some(&constraints.level)),
+        ])?;
         // let str_equip = self.model_inference(self.window_info., &image)?;
 
         let star = self.determine_star(&image)?;
@@ -129,18 +158,23 @@ impl WWEchoScannerWorker {
         })
     }
 
-    pub fn run(self, rx: Receiver<SendItem>) -> JoinHandle<Vec<WWEchoScanResult>> {
+    /// Parse the items with the workers in parallel, the results are checked in the order of the items
+    pub fn run(workers: Vec<Self>, rx: Receiver<SendItem>) -> JoinHandle<Vec<WWEchoScanResult>> {
+        let config = workers[0].config.clone();
+        let info = workers[0].window_info.clone();
+
         std::thread::spawn(move || {
             let mut results = Vec::new();
             let mut hash = HashSet::new();
             let mut consecutive_dup_count = 0;
 
-            let is_verbose = self.config.verbose;
-            let min_level = self.config.min_level;
-            let info = self.window_info.clone();
+            let is_verbose = config.verbose;
+            let min_level = config.min_level;
+
+            let pool = OrderedWorkerPool::spawn(workers, rx.into_iter(), |worker: &Self, item| worker.parse_item(item));
 
-            for (_cnt, item) in rx.into_iter().enumerate() {
-                let result = match self.parse_item(item) {
+            for result in pool {
+                let result = match result {
                     Ok(v) => v,
                     Err(e) => {
                         // error!("识别错误: {}", e);
@@ -1,3 +1,3 @@
-pub use echo_scanner::{WWEchoScanner, WWEchoScannerConfig};
+pub use echo_scanner::{WWEchoScanner, WWEchoScannerConfig, WWEchoScanResult};
 
 mod echo_scanner;
@@ -9,7 +9,7 @@ use clap::{ArgMatches, FromArgMatches};
 use image::{Rgb, RgbImage};
 use log::{error, info};
 
-use yas::capture::{Capturer, GenericCapturer};
+use yas::capture::Capturer;
 use yas::game_info::GameInfo;
 use yas::positioning::Pos;
 use yas::profiler::Profiler;
@@ -60,7 +60,8 @@ impl WWRepositoryLayoutScanController {
     pub fn new(
         window_info_repo: &WindowInfoRepository,
         config: WWRepositoryLayoutConfig,
-        game_info: GameInfo
+        game_info: GameInfo,
+        capturer: Rc<dyn Capturer<RgbImage>>,
     ) -> Result<Self> {
         let window_info = WWRepositoryLayoutWindowinfo::from_window_info_repository(
             game_info.window.to_rect_usize().size(),
@@ -69,10 +70,8 @@ impl WWRepositoryLayoutScanController {
             window_info_repo
         )?;
 
-        let row_count = window_info.ww_repository_item_row;
-        let col_count = window_info.ww_repository_item_col;
-
-        let capturer = Rc::new(GenericCapturer::new()?);
+        let row_count = window_info.repository.item_row;
+        let col_count = window_info.repository.item_col;
 
         Ok(WWRepositoryLayoutScanController {
             system_control: SystemControl::new(),
@@ -103,12 +102,14 @@ impl WWRepositoryLayoutScanController {
     pub fn from_arg_matches(
         window_info_repo: &WindowInfoRepository,
         arg_matches: &ArgMatches,
-        game_info: GameInfo
+        game_info: GameInfo,
+        capturer: Rc<dyn Capturer<RgbImage>>,
     ) -> Result<Self> {
         Self::new(
             window_info_repo,
             WWRepositoryLayoutConfig::from_arg_matches(arg_matches)?,
-            game_info
+            game_info,
+            capturer,
         )
     }
 }
@@ -297,9 +298,9 @@ impl WWRepositoryLayoutScanController {
         let (row, col) = (row as u32, col as u32);
         let origin = self.game_info.window.to_rect_f64().origin();
 
-        let gap = self.window_info.item_gap_size;
-        let margin = self.window_info.scan_margin_pos;
-        let size = self.window_info.item_size;
+        let gap = self.window_info.repository.item_gap_size;
+        let margin = self.window_info.repository.scan_margin_pos;
+        let size = self.window_info.repository.item_size;
 
         let left = origin.x + margin.x + (gap.width + size.width) * (col as f64) + size.width / 2.0;
         let top = origin.y + margin.y + (gap.height + size.height) * (row as f64) + size.height / 2.0;
@@ -396,7 +397,7 @@ impl WWRepositoryLayoutScanController {
         while now.elapsed()?.as_millis() < self.config.max_wait_switch_item as u128 {
             self.profiler.borrow_mut().begin("capture_pool");
             let im = self.capturer.capture_relative_to(
-                self.window_info.pool_rect.to_rect_i32(),
+                self.window_info.repository.pool_rect.to_rect_i32(),
                 self.game_info.window.origin()
             )?;
             self.profiler.borrow_mut().end("capture_pool")?;
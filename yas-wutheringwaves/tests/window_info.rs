@@ -0,0 +1,32 @@
+use yas::game_info::{Platform, UI};
+use yas::positioning::{Rect, Size};
+use yas::window_info::{validate_window_info_path, WindowInfoType};
+use yas_wutheringwaves::application::WWEchoScannerApplication;
+use yas_wutheringwaves::scanner::WWEchoScanner;
+
+/// Every bundled window info file has the keys the scanner reads
+#[test]
+fn bundled_window_info_is_complete() {
+    let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/window_info");
+    if let Err(e) = validate_window_info_path(dir, &WWEchoScanner::required_window_info_keys()) {
+        panic!("{}", e);
+    }
+}
+
+/// The bundled window info is read at an aspect ratio without a file of its own,
+/// the item grid keeps to the left edge and the panel to the right edge
+#[test]
+fn bundled_window_info_is_projected_onto_other_aspect_ratios() {
+    let repo = WWEchoScannerApplication::get_window_info_repository();
+    let values = WWEchoScanner::window_info_values(&repo, Size::new(2560, 1080), UI::Desktop, Platform::Windows).unwrap();
+    for key in WWEchoScanner::required_window_info_keys().iter().filter(|k| !k.optional) {
+        assert!(values.iter().any(|(k, _)| *k == key.key), "missing {}", key.key);
+    }
+
+    // projected from 2560x1440
+    let panel = values.iter().find(|(k, _)| k == "ww_repository_panel_rect").map(|(_, v)| v);
+    match panel {
+        Some(WindowInfoType::Rect(rect)) => assert_eq!(*rect, Rect::new(1936.0, 115.5, 558.0, 817.5)),
+        other => panic!("unexpected panel rect {:?}", other),
+    }
+}
@@ -0,0 +1,286 @@
+//! Compares the Yas preprocessing with the original pixel by pixel implementation.
+//! The outputs are checked to be equal bit for bit before measuring
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use image::{ImageBuffer, Luma, Rgb, RgbImage};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+
+use yas_core::ocr::yas_model::preprocess::{self, Preprocessor};
+
+/// The preprocessing as it was before working on slices
+mod reference {
+    use image::{GenericImageView, ImageBuffer, Luma, RgbImage};
+    use image::imageops;
+
+    pub fn to_gray(raw: &RgbImage) -> ImageBuffer<Luma<f32>, Vec<f32>> {
+        let mut new_gray: ImageBuffer<Luma<f32>, Vec<f32>> = ImageBuffer::new(raw.width(), raw.height());
+        for x in 0..raw.width() {
+            for y in 0..raw.height() {
+                let rgb = raw.get_pixel(x, y);
+
+                let r = rgb[0] as f32 / 255.0;
+                let g = rgb[1] as f32 / 255.0;
+                let b = rgb[2] as f32 / 255.0;
+
+                let gray = r * 0.2989 + g * 0.5870 + b * 0.1140;
+                let grayp = new_gray.get_pixel_mut(x, y);
+                grayp[0] = gray;
+            }
+        }
+        new_gray
+    }
+
+    fn normalize(im: &mut ImageBuffer<Luma<f32>, Vec<f32>>, auto_inverse: bool) -> bool {
+        let width = im.width();
+        let height = im.height();
+
+        if width == 0 || height == 0 {
+            return false;
+        }
+
+        let mut max: f32 = 0.0;
+        let mut min: f32 = 256.0;
+
+        for i in 0..width {
+            for j in 0..height {
+                let p = im.get_pixel(i, j)[0];
+                if p > max {
+                    max = p;
+                }
+                if p < min {
+                    min = p;
+                }
+            }
+        }
+
+        if max == min {
+            return false;
+        }
+
+        let flag_pixel = if width >= 2 {
+            im.get_pixel(width - 2, height - 1)[0]
+        } else {
+            im.get_pixel(width - 1, height - 1)[0]
+        };
+        let flag_pixel = (flag_pixel - min) / (max - min);
+
+        for i in 0..width {
+            for j in 0..height {
+                let p = im.get_pixel_mut(i, j);
+                let pv = p[0];
+                let mut new_pv = (pv - min) / (max - min);
+                if auto_inverse && flag_pixel > 0.5 {
+                    new_pv = 1.0 - new_pv;
+                }
+                p[0] = new_pv;
+            }
+        }
+
+        true
+    }
+
+    fn crop(im: &ImageBuffer<Luma<f32>, Vec<f32>>) -> ImageBuffer<Luma<f32>, Vec<f32>> {
+        let width = im.width();
+        let height = im.height();
+
+        let mut min_col = width - 1;
+        let mut max_col = 0;
+        let mut min_row = height - 1;
+        let mut max_row = 0_u32;
+
+        for i in 0..width {
+            for j in 0..height {
+                let p = im.get_pixel(i, j)[0];
+                if p > 0.7 {
+                    if i < min_col {
+                        min_col = i;
+                    }
+                    if i > max_col {
+                        max_col = i;
+                    }
+                    break;
+                }
+            }
+        }
+
+        for j in 0..height {
+            for i in 0..width {
+                let p = im.get_pixel(i, j)[0];
+                if p > 0.7 {
+                    if j < min_row {
+                        min_row = j;
+                    }
+                    if j > max_row {
+                        max_row = j;
+                    }
+                    break;
+                }
+            }
+        }
+
+        if min_col > max_col || min_row > max_row {
+            return im.clone();
+        }
+
+        let new_height = max_row - min_row + 1;
+        let new_width = max_col - min_col + 1;
+
+        im.view(min_col, min_row, new_width, new_height).to_image()
+    }
+
+    fn resize_and_pad(im: &ImageBuffer<Luma<f32>, Vec<f32>>) -> ImageBuffer<Luma<f32>, Vec<f32>> {
+        let w = im.width();
+        let h = im.height();
+
+        let new_width = if w as f64 / (h as f64) > 384.0 / 32.0 {
+            384
+        } else {
+            std::cmp::min((32.0 / h as f64 * w as f64) as u32, 384)
+        };
+
+        let new_height = std::cmp::min((384.0 / w as f64 * h as f64) as u32, 32);
+
+        let img = imageops::resize(
+            im,
+            new_width,
+            new_height,
+            image::imageops::FilterType::Triangle,
+        );
+
+        let data: Vec<f32> = vec![0.0; 32 * 384];
+        let mut padded_im = ImageBuffer::from_vec(384, 32, data).unwrap();
+        imageops::overlay(&mut padded_im, &img, 0, 0);
+        padded_im
+    }
+
+    pub fn pre_process(im: ImageBuffer<Luma<f32>, Vec<f32>>) -> (ImageBuffer<Luma<f32>, Vec<f32>>, bool) {
+        let mut im = im;
+        if !normalize(&mut im, true) {
+            return (im, false);
+        }
+        let mut im = crop(&im);
+
+        normalize(&mut im, false);
+
+        let mut im = resize_and_pad(&im);
+
+        for i in 0..im.width() {
+            for j in 0..im.height() {
+                let p = im.get_pixel_mut(i, j);
+                let pv = p[0];
+                if pv < 0.53 {
+                    p[0] = 0.0;
+                } else {
+                    p[0] = 1.0;
+                }
+            }
+        }
+
+        (im, true)
+    }
+}
+
+/// A noisy image with blocks of "text", dark text on a light background if `inverse`
+fn text_image(rng: &mut StdRng, width: u32, height: u32, inverse: bool) -> RgbImage {
+    let mut im = RgbImage::from_fn(width, height, |_, _| {
+        let v = rng.gen_range(0..60);
+        Rgb([v, v + rng.gen_range(0..10), v])
+    });
+
+    let strokes = rng.gen_range(1..=(width / 8).max(1));
+    for _ in 0..strokes {
+        let left = rng.gen_range(width / 10..=width * 9 / 10);
+        let top = rng.gen_range(height / 5..=height * 3 / 5);
+        let right = (left + rng.gen_range(1..=4)).min(width);
+        let bottom = (top + rng.gen_range(1..=height / 2 + 1)).min(height);
+        for y in top..bottom {
+            for x in left..right {
+                let v = rng.gen_range(200..=255);
+                im.put_pixel(x, y, Rgb([v, v, rng.gen_range(180..=255)]));
+            }
+        }
+    }
+
+    if inverse {
+        for p in im.pixels_mut() {
+            p.0 = p.0.map(|c| 255 - c);
+        }
+    }
+    im
+}
+
+fn sample_images() -> Vec<RgbImage> {
+    let mut rng = StdRng::seed_from_u64(0x5ca9);
+    let mut images = Vec::new();
+    for &(width, height) in &[(320, 30), (200, 26), (640, 42), (90, 24), (1200, 20), (24, 60)] {
+        images.push(text_image(&mut rng, width, height, false));
+        images.push(text_image(&mut rng, width, height, true));
+    }
+    // single colored, tiny and almost all bright images hit the edge cases
+    images.push(RgbImage::from_pixel(100, 20, Rgb([40, 40, 40])));
+    images.push(RgbImage::from_pixel(1, 1, Rgb([255, 255, 255])));
+    images.push(RgbImage::from_fn(50, 10, |x, _| Rgb([if x == 0 { 0 } else { 255 }; 3])));
+    images.push(text_image(&mut rng, 1, 30, false));
+    images
+}
+
+fn assert_bits_eq(name: &str, index: usize, a: &ImageBuffer<Luma<f32>, Vec<f32>>, b: &ImageBuffer<Luma<f32>, Vec<f32>>) {
+    assert_eq!(a.dimensions(), b.dimensions(), "{} of image {}: size differs", name, index);
+    let differs = a.as_raw().iter().zip(b.as_raw()).position(|(x, y)| x.to_bits() != y.to_bits());
+    assert!(differs.is_none(), "{} of image {}: pixel {:?} differs", name, index, differs);
+}
+
+/// Panics if the new preprocessing differs from the reference on any sample
+fn check_outputs(images: &[RgbImage]) {
+    let mut preprocessor = Preprocessor::new();
+    for (index, im) in images.iter().enumerate() {
+        let reference_gray = reference::to_gray(im);
+        assert_bits_eq("to_gray", index, &reference_gray, &preprocess::to_gray(im));
+
+        let (reference_im, reference_flag) = reference::pre_process(reference_gray.clone());
+        let (im_rgb, flag_rgb) = preprocessor.preprocess_rgb(im);
+        let (im_gray, flag_gray) = preprocessor.preprocess_gray(&reference_gray);
+        assert_eq!(reference_flag, flag_rgb, "pre_process of image {}: flag differs", index);
+        assert_eq!(reference_flag, flag_gray, "pre_process of image {}: flag differs", index);
+        assert_bits_eq("preprocess_rgb", index, &reference_im, &im_rgb);
+        assert_bits_eq("preprocess_gray", index, &reference_im, &im_gray);
+    }
+}
+
+fn bench_preprocess(c: &mut Criterion) {
+    let images = sample_images();
+    check_outputs(&images);
+
+    let mut group = c.benchmark_group("to_gray");
+    group.bench_function("reference", |b| b.iter(|| {
+        for im in images.iter() {
+            black_box(reference::to_gray(black_box(im)));
+        }
+    }));
+    group.bench_function("slices", |b| b.iter(|| {
+        for im in images.iter() {
+            black_box(preprocess::to_gray(black_box(im)));
+        }
+    }));
+    group.finish();
+
+    let mut group = c.benchmark_group("pre_process");
+    group.bench_function("reference", |b| b.iter(|| {
+        for im in images.iter() {
+            black_box(reference::pre_process(reference::to_gray(black_box(im))));
+        }
+    }));
+    group.bench_function("slices", |b| {
+        let mut preprocessor = Preprocessor::new();
+        b.iter(|| {
+            for im in images.iter() {
+                black_box(preprocessor.preprocess_rgb(black_box(im)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_preprocess);
+criterion_main!(benches);
@@ -0,0 +1,66 @@
+use thiserror::Error;
+
+use crate::positioning::Rect;
+
+/// Errors of the capturers, the `anyhow::Error` returned by a `Capturer` can be downcast to this type
+#[derive(Error, Debug)]
+pub enum CaptureError {
+    #[error("invalid capture region: {0}")]
+    InvalidRect(Rect<i32>),
+
+    /// The requested region is not (entirely) inside what can be captured,
+    /// for example the screen, a captured frame, or a replayed image
+    #[error("capture region {rect} is out of {bounds}")]
+    OutOfBounds {
+        rect: Rect<i32>,
+        bounds: Rect<i32>,
+    },
+
+    #[error("replay exhausted after {count} captures")]
+    ReplayExhausted {
+        count: usize,
+    },
+
+    #[error("cannot read replay image: {0}")]
+    ReplayImage(String),
+
+    /// No capture backend can be created
+    #[error("no available capturer: {0}")]
+    Unavailable(String),
+
+    /// The backend failed to grab the screen, which is usually transient
+    #[error("capture failed: {0}")]
+    Backend(String),
+}
+
+impl CaptureError {
+    pub fn backend<E: std::fmt::Display>(e: E) -> Self {
+        CaptureError::Backend(e.to_string())
+    }
+
+    /// Whether capturing the same region again may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, CaptureError::Backend(_))
+    }
+}
+
+/// Call `capture` again while it fails with a retryable `CaptureError`, at most `max_retry` times
+pub fn retry_capture<T, F>(max_retry: usize, mut capture: F) -> anyhow::Result<T>
+where
+    F: FnMut() -> anyhow::Result<T>
+{
+    let mut retry = 0;
+    loop {
+        match capture() {
+            Err(e) if retry < max_retry && is_retryable(&e) => {
+                log::warn!("截图失败，重试：{}", e);
+                retry += 1;
+            },
+            result => return result,
+        }
+    }
+}
+
+fn is_retryable(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<CaptureError>().is_some_and(|e| e.is_retryable())
+}
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use image::{GenericImageView, ImageBuffer, Pixel};
+
+use crate::capture::CaptureError;
+use crate::positioning::{Pos, Rect};
+
+/// A single screen grab, from which many regions can be cropped.
+/// Reading all the regions of an item from one frame keeps them consistent,
+/// because the UI cannot change between the reads
+pub struct CapturedFrame<T> {
+    /// the region the image covers, in screen coordinates
+    region: Rect<i32>,
+    image: T,
+}
+
+impl<T> CapturedFrame<T> {
+    pub fn new(region: Rect<i32>, image: T) -> Self {
+        Self {
+            region,
+            image,
+        }
+    }
+
+    pub fn region(&self) -> Rect<i32> {
+        self.region
+    }
+
+    pub fn image(&self) -> &T {
+        &self.image
+    }
+
+    pub fn into_image(self) -> T {
+        self.image
+    }
+}
+
+impl<P> CapturedFrame<ImageBuffer<P, Vec<P::Subpixel>>> where P: Pixel + 'static {
+    /// Crop `rect` (in screen coordinates) out of the frame
+    pub fn crop_rect(&self, rect: Rect<i32>) -> Result<ImageBuffer<P, Vec<P::Subpixel>>> {
+        let left = rect.left - self.region.left;
+        let top = rect.top - self.region.top;
+        if rect.width <= 0 || rect.height <= 0 {
+            return Err(CaptureError::InvalidRect(rect).into());
+        }
+        if left < 0 || top < 0
+            || left + rect.width > self.image.width() as i32
+            || top + rect.height > self.image.height() as i32
+        {
+            return Err(CaptureError::OutOfBounds { rect, bounds: self.region }.into());
+        }
+
+        Ok(self.image.view(left as u32, top as u32, rect.width as u32, rect.height as u32).to_image())
+    }
+
+    pub fn crop_relative_to(&self, rect: Rect<i32>, relative_to: Pos<i32>) -> Result<ImageBuffer<P, Vec<P::Subpixel>>> {
+        self.crop_rect(rect.translate(relative_to))
+    }
+
+    /// Get the color at `pos` (in screen coordinates)
+    pub fn get_color(&self, pos: Pos<i32>) -> Result<P> {
+        let x = pos.x - self.region.left;
+        let y = pos.y - self.region.top;
+        if x < 0 || y < 0 || x >= self.image.width() as i32 || y >= self.image.height() as i32 {
+            let rect = Rect {
+                left: pos.x,
+                top: pos.y,
+                width: 1,
+                height: 1,
+            };
+            return Err(CaptureError::OutOfBounds { rect, bounds: self.region }.into());
+        }
+
+        Ok(*self.image.get_pixel(x as u32, y as u32))
+    }
+}
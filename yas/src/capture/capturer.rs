@@ -1,3 +1,4 @@
+use crate::capture::CapturedFrame;
 use crate::positioning::{Pos, Rect};
 use anyhow::Result;
 
@@ -24,4 +25,27 @@ pub trait Capturer<T: image::GenericImage> {
         };
         self.capture_rect(new_rect)
     }
+
+    /// Grab `rect` once, the regions of one item can then be cropped from the same frame
+    fn capture_frame(&self, rect: Rect<i32>) -> Result<CapturedFrame<T>> {
+        Ok(CapturedFrame::new(rect, self.capture_rect(rect)?))
+    }
+}
+
+impl<T, C> Capturer<T> for Box<C> where T: image::GenericImage, C: Capturer<T> + ?Sized {
+    fn capture_rect(&self, rect: Rect<i32>) -> Result<T> {
+        (**self).capture_rect(rect)
+    }
+
+    fn capture_color(&self, pos: Pos<i32>) -> Result<T::Pixel> {
+        (**self).capture_color(pos)
+    }
+
+    fn capture_relative_to(&self, rect: Rect<i32>, relative_to: Pos<i32>) -> Result<T> {
+        (**self).capture_relative_to(rect, relative_to)
+    }
+
+    fn capture_frame(&self, rect: Rect<i32>) -> Result<CapturedFrame<T>> {
+        (**self).capture_frame(rect)
+    }
 }
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use image::RgbImage;
+use log::info;
+
+use crate::capture::{Capturer, GenericCapturer, RecordingCapturer, RecordingSession, ReplayCapturer, ReplayManifest};
+use crate::game_info::GameInfo;
+
+#[derive(Clone, clap::Args, Default)]
+pub struct CapturerConfig {
+    /// Answer captures from a recorded directory instead of the screen
+    #[arg(id = "replay-dir", long = "replay-dir", help = "从录制的目录回放截图，而不是截取屏幕", value_name = "DIR")]
+    pub replay_dir: Option<PathBuf>,
+
+    /// Record every capture into a directory, which can be replayed with `--replay-dir`
+    #[arg(id = "record-session", long = "record-session", help = "将所有截图录制到目录中，可以使用 --replay-dir 回放", value_name = "DIR")]
+    pub record_session: Option<PathBuf>,
+
+    /// all the capturers built from this config record into the same session
+    #[arg(skip)]
+    recording_session: Arc<Mutex<Option<Arc<RecordingSession>>>>,
+}
+
+impl CapturerConfig {
+    /// Build a capturer, `tag` names the caller in recorded sessions,
+    /// and selects the entries to answer when replaying
+    pub fn build_capturer(&self, game_info: &GameInfo, tag: &str) -> Result<Box<dyn Capturer<RgbImage> + Send>> {
+        let capturer: Box<dyn Capturer<RgbImage> + Send> = match self.replay_dir.as_ref() {
+            Some(dir) => {
+                info!("从 {:?} 回放截图", dir);
+                Box::new(ReplayCapturer::new_with_tag(dir, tag)?)
+            },
+            None => Box::new(GenericCapturer::new()?),
+        };
+
+        match self.get_recording_session(game_info)? {
+            Some(session) => Ok(Box::new(RecordingCapturer::new(capturer, session, tag))),
+            None => Ok(capturer),
+        }
+    }
+
+    fn get_recording_session(&self, game_info: &GameInfo) -> Result<Option<Arc<RecordingSession>>> {
+        let dir = match self.record_session.as_ref() {
+            Some(dir) => dir,
+            None => return Ok(None),
+        };
+
+        let mut session = self.recording_session.lock().map_err(|_| anyhow!("recording session poisoned"))?;
+        if session.is_none() {
+            *session = Some(Arc::new(RecordingSession::new(dir, game_info)?));
+        }
+        Ok(session.clone())
+    }
+
+    /// When replaying, the game window is taken from the manifest instead of the OS
+    pub fn replay_game_info(&self) -> Result<Option<GameInfo>> {
+        match self.replay_dir.as_ref() {
+            Some(dir) => Ok(Some(ReplayManifest::from_dir(dir)?.game_info()?)),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use anyhow::{anyhow, Result};
+use image::RgbImage;
+use log::{info, warn};
+#[cfg(feature = "capturer_libwayshot")]
+use crate::capture::LibwayshotCapturer;
+use crate::capture::X11Capturer;
+use crate::capture::{CaptureError, Capturer};
+use crate::positioning::{Pos, Rect};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinuxSessionType {
+    X11,
+    Wayland,
+}
+
+impl LinuxSessionType {
+    /// Detect the session type from the environment, `XDG_SESSION_TYPE` takes precedence
+    pub fn current() -> Self {
+        match std::env::var("XDG_SESSION_TYPE") {
+            Ok(v) if v.eq_ignore_ascii_case("wayland") => return Self::Wayland,
+            Ok(v) if v.eq_ignore_ascii_case("x11") => return Self::X11,
+            _ => (),
+        }
+
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            Self::Wayland
+        } else {
+            Self::X11
+        }
+    }
+}
+
+/// Chooses a capturer backend at runtime according to the session type.
+/// If the preferred backend is not compiled in or fails to connect, the other one is tried
+pub enum LinuxCapturer {
+    X11(X11Capturer),
+    #[cfg(feature = "capturer_libwayshot")]
+    Libwayshot(LibwayshotCapturer),
+}
+
+impl LinuxCapturer {
+    pub fn new() -> Result<Self> {
+        let session_type = LinuxSessionType::current();
+        info!("Linux session type: {:?}", session_type);
+        Self::new_for_session(session_type)
+    }
+
+    pub fn new_for_session(session_type: LinuxSessionType) -> Result<Self> {
+        let order = match session_type {
+            LinuxSessionType::X11 => [LinuxSessionType::X11, LinuxSessionType::Wayland],
+            LinuxSessionType::Wayland => [LinuxSessionType::Wayland, LinuxSessionType::X11],
+        };
+
+        let mut errors = Vec::new();
+        for backend in order {
+            match Self::new_backend(backend) {
+                Ok(capturer) => return Ok(capturer),
+                Err(e) => {
+                    warn!("cannot create {:?} capturer: {}", backend, e);
+                    errors.push(format!("{:?}: {}", backend, e));
+                }
+            }
+        }
+
+        Err(CaptureError::Unavailable(errors.join("; ")).into())
+    }
+
+    fn new_backend(backend: LinuxSessionType) -> Result<Self> {
+        match backend {
+            LinuxSessionType::X11 => Ok(Self::X11(X11Capturer::new()?)),
+            #[cfg(feature = "capturer_libwayshot")]
+            LinuxSessionType::Wayland => Ok(Self::Libwayshot(LibwayshotCapturer::new()?)),
+            #[allow(unreachable_patterns)]
+            _ => Err(anyhow!("capturer for {:?} is not enabled in this build", backend)),
+        }
+    }
+}
+
+impl Capturer<RgbImage> for LinuxCapturer {
+    fn capture_rect(&self, rect: Rect<i32>) -> Result<RgbImage> {
+        match self {
+            Self::X11(capturer) => capturer.capture_rect(rect),
+            #[cfg(feature = "capturer_libwayshot")]
+            Self::Libwayshot(capturer) => capturer.capture_rect(rect),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+
+    fn capture_color(&self, pos: Pos<i32>) -> Result<image::Rgb<u8>> {
+        match self {
+            Self::X11(capturer) => capturer.capture_color(pos),
+            #[cfg(feature = "capturer_libwayshot")]
+            Self::Libwayshot(capturer) => Capturer::<RgbImage>::capture_color(capturer, pos),
+            #[allow(unreachable_patterns)]
+            _ => unreachable!(),
+        }
+    }
+}
@@ -1,10 +1,20 @@
-pub use stream_capturer::StreamingCapturer;
+pub use stream_capturer::{StreamingCapturer, StreamingCapturerConfig};
 pub use capturer::Capturer;
+pub use capture_error::{CaptureError, retry_capture};
+pub use captured_frame::CapturedFrame;
 pub use generic_capturer::GenericCapturer;
+pub use replay_capturer::{ReplayCapturer, ReplayManifest, ReplayCaptureEntry};
+pub use recording_capturer::{RecordingCapturer, RecordingSession, RecordedCapture};
+pub use capturer_config::CapturerConfig;
 
 mod capturer;
+mod capture_error;
+mod captured_frame;
 mod generic_capturer;
 mod stream_capturer;
+mod replay_capturer;
+mod recording_capturer;
+mod capturer_config;
 
 // windows
 
@@ -23,8 +33,16 @@ pub use winapi_capturer::WinapiCapturer;
 pub use windows_capturer::WindowsCapturer;
 
 // linux
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", feature = "capturer_libwayshot"))]
 mod libwayshot_capturer;
-
 #[cfg(target_os = "linux")]
+mod x11_capturer;
+#[cfg(target_os = "linux")]
+mod linux_capturer;
+
+#[cfg(all(target_os = "linux", feature = "capturer_libwayshot"))]
 pub use libwayshot_capturer::LibwayshotCapturer;
+#[cfg(target_os = "linux")]
+pub use x11_capturer::X11Capturer;
+#[cfg(target_os = "linux")]
+pub use linux_capturer::{LinuxCapturer, LinuxSessionType};
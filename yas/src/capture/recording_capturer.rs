@@ -0,0 +1,151 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use image::{ImageBuffer, RgbImage};
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+
+use crate::capture::{Capturer, ReplayCaptureEntry, ReplayManifest};
+use crate::game_info::GameInfo;
+use crate::positioning::{Pos, Rect};
+
+/// The JSON sidecar written next to every recorded image
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RecordedCapture {
+    pub index: usize,
+    /// milliseconds since unix epoch
+    pub timestamp: u128,
+    /// the captured region, in screen coordinates
+    pub rect: Rect<i32>,
+    pub window_origin: Pos<i32>,
+    pub tag: String,
+}
+
+struct RecordingState {
+    next_index: usize,
+    /// `ReplayManifest::CAPTURE_LOG_FILENAME`, unbuffered so that every entry is on disk once recorded
+    capture_log: File,
+}
+
+/// A recorded session on disk, shared by all the capturers recording into the same directory.
+/// The manifest it writes can be replayed by `ReplayCapturer`.
+/// The captures are appended to a log as they are recorded, so that a session interrupted
+/// by a crash can still be replayed up to the last capture
+pub struct RecordingSession {
+    dir: PathBuf,
+    game_info: GameInfo,
+    state: Mutex<RecordingState>,
+}
+
+impl RecordingSession {
+    pub fn new<P: AsRef<Path>>(dir: P, game_info: &GameInfo) -> Result<Self> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        info!("录制截图到 {:?}", dir);
+
+        let manifest = ReplayManifest {
+            window: game_info.window,
+            ui: game_info.ui,
+            platform: game_info.platform,
+            is_cloud: game_info.is_cloud,
+            captures: Vec::new(),
+        };
+        fs::write(dir.join(ReplayManifest::FILENAME), serde_json::to_string_pretty(&manifest)?)?;
+
+        let capture_log = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(dir.join(ReplayManifest::CAPTURE_LOG_FILENAME))?;
+
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            game_info: game_info.clone(),
+            state: Mutex::new(RecordingState {
+                next_index: 0,
+                capture_log,
+            }),
+        })
+    }
+
+    pub fn record(&self, rect: Rect<i32>, image: &RgbImage, tag: &str) -> Result<()> {
+        let mut state = self.state.lock().map_err(|_| anyhow!("recording state poisoned"))?;
+
+        let index = state.next_index;
+        state.next_index += 1;
+
+        let file = PathBuf::from(format!("{:06}.png", index));
+        image.save(self.dir.join(&file))?;
+
+        let sidecar = RecordedCapture {
+            index,
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis(),
+            rect,
+            window_origin: self.game_info.window.origin(),
+            tag: String::from(tag),
+        };
+        fs::write(
+            self.dir.join(format!("{:06}.json", index)),
+            serde_json::to_string_pretty(&sidecar)?,
+        )?;
+
+        let entry = ReplayCaptureEntry {
+            file,
+            region: Some(rect),
+            tag: Some(String::from(tag)),
+        };
+        // one write per line, the image and the sidecar are already saved when it is logged
+        let line = format!("{}\n", serde_json::to_string(&entry)?);
+        state.capture_log.write_all(line.as_bytes())?;
+
+        Ok(())
+    }
+}
+
+/// Wraps a capturer and records every successful capture into a `RecordingSession`
+pub struct RecordingCapturer {
+    inner: Box<dyn Capturer<RgbImage> + Send>,
+    session: Arc<RecordingSession>,
+    tag: String,
+}
+
+impl RecordingCapturer {
+    pub fn new(inner: Box<dyn Capturer<RgbImage> + Send>, session: Arc<RecordingSession>, tag: &str) -> Self {
+        Self {
+            inner,
+            session,
+            tag: String::from(tag),
+        }
+    }
+
+    fn record(&self, rect: Rect<i32>, image: &RgbImage) {
+        // a failed recording should not break the scan
+        if let Err(e) = self.session.record(rect, image, &self.tag) {
+            error!("录制截图失败：{}", e);
+        }
+    }
+}
+
+impl Capturer<RgbImage> for RecordingCapturer {
+    fn capture_rect(&self, rect: Rect<i32>) -> Result<RgbImage> {
+        let image = self.inner.capture_rect(rect)?;
+        self.record(rect, &image);
+        Ok(image)
+    }
+
+    fn capture_color(&self, pos: Pos<i32>) -> Result<image::Rgb<u8>> {
+        let color = self.inner.capture_color(pos)?;
+        let rect = Rect {
+            left: pos.x,
+            top: pos.y,
+            width: 1,
+            height: 1,
+        };
+        self.record(rect, &ImageBuffer::from_pixel(1, 1, color));
+        Ok(color)
+    }
+}
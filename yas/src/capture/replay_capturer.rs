@@ -0,0 +1,180 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use image::{GenericImageView, RgbImage};
+use log::warn;
+use serde::{Deserialize, Serialize};
+
+use crate::capture::{CaptureError, Capturer};
+use crate::game_info::{GameInfo, Platform, ResolutionFamily, UI};
+use crate::positioning::Rect;
+
+/// Describes a recorded session, stored as `manifest.json` in the replay directory
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReplayManifest {
+    /// the game window when the session was recorded, in screen coordinates
+    pub window: Rect<i32>,
+    pub ui: UI,
+    pub platform: Platform,
+    #[serde(default)]
+    pub is_cloud: bool,
+    /// the i-th capture request is answered by the i-th entry,
+    /// followed by the entries in `CAPTURE_LOG_FILENAME` if it exists
+    pub captures: Vec<ReplayCaptureEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReplayCaptureEntry {
+    /// image file, relative to the replay directory
+    pub file: PathBuf,
+    /// the region the image covers, in screen coordinates.
+    /// If absent, the image is a full window screenshot located at the window origin
+    #[serde(default)]
+    pub region: Option<Rect<i32>>,
+    /// which capturer made this capture, untagged entries are served to every capturer
+    #[serde(default)]
+    pub tag: Option<String>,
+}
+
+impl ReplayManifest {
+    pub const FILENAME: &'static str = "manifest.json";
+    /// One `ReplayCaptureEntry` per line, appended by a recording session as it captures
+    pub const CAPTURE_LOG_FILENAME: &'static str = "captures.jsonl";
+
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let path = dir.as_ref().join(Self::FILENAME);
+        let s = std::fs::read_to_string(&path)
+            .map_err(|e| anyhow!("cannot read replay manifest {:?}: {}", path, e))?;
+        let mut manifest: ReplayManifest = serde_json::from_str(&s)?;
+
+        let log_path = dir.as_ref().join(Self::CAPTURE_LOG_FILENAME);
+        if log_path.exists() {
+            let log = std::fs::read_to_string(&log_path)
+                .map_err(|e| anyhow!("cannot read replay capture log {:?}: {}", log_path, e))?;
+            manifest.captures.extend(Self::parse_capture_log(&log)?);
+        }
+
+        Ok(manifest)
+    }
+
+    fn parse_capture_log(log: &str) -> Result<Vec<ReplayCaptureEntry>> {
+        let lines: Vec<&str> = log.lines().filter(|line| !line.trim().is_empty()).collect();
+        let mut captures = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            match serde_json::from_str(line) {
+                Ok(entry) => captures.push(entry),
+                // the recording can be killed in the middle of the last line
+                Err(e) if i + 1 == lines.len() => warn!("忽略不完整的录制记录：{}", e),
+                Err(e) => return Err(anyhow!("invalid replay capture log at line {}: {}", i + 1, e)),
+            }
+        }
+        Ok(captures)
+    }
+
+    pub fn game_info(&self) -> Result<GameInfo> {
+        let size = self.window.to_rect_usize().size();
+        let resolution_family = ResolutionFamily::detect(size, self.platform)?;
+
+        Ok(GameInfo {
+            window: self.window,
+            resolution_family,
+            is_cloud: self.is_cloud,
+            ui: self.ui,
+            platform: self.platform,
+        })
+    }
+}
+
+struct ReplayState {
+    cursor: usize,
+    // the last decoded image, consecutive captures usually come from the same file
+    cached_image: Option<(PathBuf, RgbImage)>,
+}
+
+/// Answers capture requests from a recorded directory instead of the screen.
+/// Every capture consumes one entry of the manifest, in order
+pub struct ReplayCapturer {
+    dir: PathBuf,
+    manifest: ReplayManifest,
+    state: Mutex<ReplayState>,
+}
+
+impl ReplayCapturer {
+    pub fn new<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let manifest = ReplayManifest::from_dir(dir.as_ref())?;
+        Ok(Self {
+            dir: dir.as_ref().to_path_buf(),
+            manifest,
+            state: Mutex::new(ReplayState {
+                cursor: 0,
+                cached_image: None,
+            }),
+        })
+    }
+
+    /// Only replay the entries recorded with `tag`, and the untagged ones
+    pub fn new_with_tag<P: AsRef<Path>>(dir: P, tag: &str) -> Result<Self> {
+        let mut result = Self::new(dir)?;
+        result.manifest.captures.retain(|entry| match entry.tag.as_ref() {
+            Some(t) => t == tag,
+            None => true,
+        });
+        Ok(result)
+    }
+
+    pub fn manifest(&self) -> &ReplayManifest {
+        &self.manifest
+    }
+
+    /// How many captures are not replayed yet
+    pub fn remaining(&self) -> usize {
+        let state = self.state.lock().unwrap();
+        self.manifest.captures.len() - state.cursor
+    }
+}
+
+impl Capturer<RgbImage> for ReplayCapturer {
+    fn capture_rect(&self, rect: Rect<i32>) -> Result<RgbImage> {
+        let mut state = self.state.lock().map_err(|_| anyhow!("replay state poisoned"))?;
+
+        let index = state.cursor;
+        let entry = self.manifest.captures
+            .get(index)
+            .ok_or(CaptureError::ReplayExhausted { count: index })?;
+
+        let path = self.dir.join(&entry.file);
+        let is_cached = matches!(&state.cached_image, Some((p, _)) if *p == path);
+        if !is_cached {
+            let image = image::open(&path)
+                .map_err(|e| CaptureError::ReplayImage(format!("{:?}: {}", path, e)))?
+                .to_rgb8();
+            state.cached_image = Some((path.clone(), image));
+        }
+        let image = &state.cached_image.as_ref().unwrap().1;
+
+        let region = entry.region.unwrap_or(Rect {
+            left: self.manifest.window.left,
+            top: self.manifest.window.top,
+            width: image.width() as i32,
+            height: image.height() as i32,
+        });
+
+        let left = rect.left - region.left;
+        let top = rect.top - region.top;
+        if rect.width <= 0 || rect.height <= 0 {
+            return Err(CaptureError::InvalidRect(rect).into());
+        }
+        if left < 0 || top < 0
+            || left + rect.width > image.width() as i32
+            || top + rect.height > image.height() as i32
+        {
+            return Err(CaptureError::OutOfBounds { rect, bounds: region }.into());
+        }
+
+        let captured = image.view(left as u32, top as u32, rect.width as u32, rect.height as u32).to_image();
+        // a failed capture is replayed again by the next call
+        state.cursor += 1;
+        Ok(captured)
+    }
+}
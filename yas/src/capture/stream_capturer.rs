@@ -1,61 +1,128 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::sync::{Arc, atomic};
 use std::sync::atomic::AtomicBool;
-use std::sync::mpsc::{Receiver, Sender};
+use std::sync::mpsc::{self, Receiver};
 use std::thread::JoinHandle;
 use std::thread;
-use image::{GenericImage, RgbImage};
-use crate::capture::{Capturer, GenericCapturer};
+use std::time::{Duration, Instant};
+use image::RgbImage;
+use log::info;
+use crate::capture::Capturer;
 use crate::positioning::Rect;
 use anyhow::Result;
 
+#[derive(Clone, clap::Args)]
+pub struct StreamingCapturerConfig {
+    /// Capture at most this many frames per second, 0 means unlimited
+    #[arg(id = "stream-fps", long = "stream-fps", help = "连续截图的最大帧率，0 表示不限制", value_name = "FPS", default_value_t = 30.0)]
+    pub fps: f64,
+
+    /// How many frames can wait for the consumer, the capturer blocks when the buffer is full
+    #[arg(id = "stream-buffer", long = "stream-buffer", help = "连续截图的缓冲帧数，缓冲区满时暂停截图", value_name = "FRAMES", default_value_t = 8)]
+    pub buffer_size: usize,
+
+    /// Send every frame, even if it is identical to the previous one
+    #[arg(id = "stream-keep-duplicates", long = "stream-keep-duplicates", help = "不跳过与上一帧相同的截图")]
+    pub keep_duplicates: bool,
+}
+
+impl Default for StreamingCapturerConfig {
+    fn default() -> Self {
+        Self {
+            fps: 30.0,
+            buffer_size: 8,
+            keep_duplicates: false,
+        }
+    }
+}
+
 pub struct StreamingCapturer {
     region: Rect<i32>,
     capturer: Box<dyn Capturer<RgbImage> + Send>,
+    config: StreamingCapturerConfig,
 
     is_cancelled: Arc<AtomicBool>
 }
 
-impl StreamingCapturer where {
-    pub fn new(region: Rect<i32>) -> Self {
+fn hash_image(image: &RgbImage) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    image.dimensions().hash(&mut hasher);
+    image.as_raw().hash(&mut hasher);
+    hasher.finish()
+}
+
+impl StreamingCapturer {
+    pub fn new<C>(region: Rect<i32>, capturer: C, config: StreamingCapturerConfig) -> Self
+    where
+        C: Capturer<RgbImage> + Send + 'static
+    {
         Self {
             region,
-            capturer: Box::new(GenericCapturer::new().unwrap()),
+            capturer: Box::new(capturer),
+            config,
             is_cancelled: Arc::new(AtomicBool::new(false)),
         }
     }
 
-    pub fn start_transform<F, S>(self, tx: Sender<S>, transform: F) -> (JoinHandle<Result<()>>, impl Fn())
+    /// Start capturing in a new thread, transformed frames are sent to the returned receiver.
+    /// The channel is bounded, so a slow consumer throttles the capturer
+    pub fn start_transform<F, S>(self, transform: F) -> (Receiver<S>, JoinHandle<Result<()>>, impl Fn())
     where
         F: Fn(RgbImage) -> S + Send + Sync + 'static,
         S: Send + Sync + 'static
     {
         let is_cancelled = self.is_cancelled.clone();
+        let (tx, rx) = mpsc::sync_channel::<S>(self.config.buffer_size);
+
+        let frame_interval = if self.config.fps > 0.0 {
+            Some(Duration::from_secs_f64(1.0 / self.config.fps))
+        } else {
+            None
+        };
 
         let handle = thread::spawn(move || -> Result<()> {
-            let mut _it = 0;
+            let mut captured_count = 0;
+            let mut sent_count = 0;
+            let mut last_hash: Option<u64> = None;
+
             loop {
                 if self.is_cancelled.load(atomic::Ordering::Relaxed) {
                     break;
                 }
 
-                // println!("capture image {}", _it);
+                let start = Instant::now();
 
                 let image = self.capturer.capture_rect(self.region);
                 if let Ok(im) = image {
-                    tx.send(transform(im))?
+                    captured_count += 1;
+
+                    let hash = hash_image(&im);
+                    if self.config.keep_duplicates || last_hash != Some(hash) {
+                        last_hash = Some(hash);
+                        tx.send(transform(im))?;
+                        sent_count += 1;
+                    }
                 }
 
-                _it += 1;
+                if let Some(interval) = frame_interval {
+                    let elapsed = start.elapsed();
+                    if elapsed < interval {
+                        thread::sleep(interval - elapsed);
+                    }
+                }
             }
 
+            info!("连续截图 {} 帧，发送 {} 帧", captured_count, sent_count);
+
             Ok(())
         });
 
         let cancel = move || {
-            println!("cancel capture");
+            info!("cancel capture");
             is_cancelled.store(true, atomic::Ordering::Relaxed);
         };
 
-        (handle, cancel)
+        (rx, handle, cancel)
     }
 }
@@ -2,11 +2,11 @@ use std::ffi::c_void;
 use std::mem::size_of;
 use std::ptr::null_mut;
 
-use anyhow::{anyhow, Result};
+use anyhow::Result;
 use image::{ImageBuffer, RgbImage};
 use windows_sys::Win32::Graphics::Gdi::*;
 
-use crate::capture::Capturer;
+use crate::capture::{CaptureError, Capturer};
 use crate::positioning::{Pos, Rect};
 
 // BGRA
@@ -15,12 +15,12 @@ unsafe fn unsafe_capture(rect: Rect<i32>) -> Result<Vec<u8>> {
 
     let dc_mem: HDC = CreateCompatibleDC(dc_window);
     if dc_mem.is_null() {
-        return Err(anyhow!("CreateCompatibleDC failed"));
+        return Err(CaptureError::Backend(String::from("CreateCompatibleDC failed")).into());
     }
 
     let hbm: HBITMAP = CreateCompatibleBitmap(dc_window, rect.width, rect.height);
     if hbm.is_null() {
-        return Err(anyhow!("CreateCompatibleBitmap failed"));
+        return Err(CaptureError::Backend(String::from("CreateCompatibleBitmap failed")).into());
     }
 
     SelectObject(dc_mem, hbm as *mut c_void);
@@ -37,7 +37,7 @@ unsafe fn unsafe_capture(rect: Rect<i32>) -> Result<Vec<u8>> {
         SRCCOPY
     );
     if result == 0 {
-        return Err(anyhow!("BitBlt failed"));
+        return Err(CaptureError::Backend(String::from("BitBlt failed")).into());
     }
 
     let mut bitmap: BITMAP = BITMAP {
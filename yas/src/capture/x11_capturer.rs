@@ -0,0 +1,150 @@
+use std::num::TryFromIntError;
+
+use anyhow::{anyhow, Result};
+use image::{ImageBuffer, RgbImage};
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{ConnectionExt, ImageFormat, ImageOrder, Window};
+use x11rb::rust_connection::RustConnection;
+use crate::capture::{CaptureError, Capturer};
+use crate::positioning::{Pos, Rect};
+
+/// Captures the root window of an X11 display with `GetImage`,
+/// which also works for games running under Wine/Proton on a plain X11 session
+pub struct X11Capturer {
+    conn: RustConnection,
+    root: Window,
+    screen_rect: Rect<i32>,
+
+    // shifts of each channel inside a 32 bits pixel, derived from the root visual
+    red_shift: u32,
+    green_shift: u32,
+    blue_shift: u32,
+    bits_per_pixel: u8,
+    is_msb_first: bool,
+}
+
+impl X11Capturer {
+    pub fn new() -> Result<Self> {
+        let (conn, screen_num) = x11rb::connect(None)?;
+        let setup = conn.setup();
+        let screen = &setup.roots[screen_num];
+        let root = screen.root;
+        let screen_rect = Rect {
+            left: 0,
+            top: 0,
+            width: screen.width_in_pixels as i32,
+            height: screen.height_in_pixels as i32,
+        };
+
+        let visual = screen.allowed_depths
+            .iter()
+            .flat_map(|depth| depth.visuals.iter())
+            .find(|visual| visual.visual_id == screen.root_visual)
+            .ok_or(anyhow!("cannot find root visual of X11 screen {}", screen_num))?;
+
+        let bits_per_pixel = setup.pixmap_formats
+            .iter()
+            .find(|format| format.depth == screen.root_depth)
+            .map(|format| format.bits_per_pixel)
+            .ok_or(anyhow!("cannot find pixmap format for depth {}", screen.root_depth))?;
+        if bits_per_pixel != 32 {
+            return Err(anyhow!("unsupported X11 pixel format: {} bits per pixel", bits_per_pixel));
+        }
+
+        let red_shift = visual.red_mask.trailing_zeros();
+        let green_shift = visual.green_mask.trailing_zeros();
+        let blue_shift = visual.blue_mask.trailing_zeros();
+        let is_msb_first = setup.image_byte_order == ImageOrder::MSB_FIRST;
+
+        Ok(Self {
+            conn,
+            root,
+            screen_rect,
+            red_shift,
+            green_shift,
+            blue_shift,
+            bits_per_pixel,
+            is_msb_first,
+        })
+    }
+
+    fn get_image(&self, rect: Rect<i32>) -> Result<Vec<u8>> {
+        if rect.width <= 0 || rect.height <= 0 {
+            return Err(CaptureError::InvalidRect(rect).into());
+        }
+        // GetImage fails with BadMatch if the region is not entirely on the screen
+        let bounds = self.screen_rect;
+        if rect.left < bounds.left || rect.top < bounds.top
+            || rect.left + rect.width > bounds.left + bounds.width
+            || rect.top + rect.height > bounds.top + bounds.height
+        {
+            return Err(CaptureError::OutOfBounds { rect, bounds }.into());
+        }
+
+        // the request carries 16 bits coordinates
+        let invalid = |_: TryFromIntError| CaptureError::InvalidRect(rect);
+        let x = i16::try_from(rect.left).map_err(invalid)?;
+        let y = i16::try_from(rect.top).map_err(invalid)?;
+        let width = u16::try_from(rect.width).map_err(invalid)?;
+        let height = u16::try_from(rect.height).map_err(invalid)?;
+
+        let reply = self.conn.get_image(ImageFormat::Z_PIXMAP, self.root, x, y, width, height, !0)
+            .map_err(CaptureError::backend)?
+            .reply()
+            .map_err(CaptureError::backend)?;
+
+        Ok(reply.data)
+    }
+
+    fn decode_pixel(&self, raw: &[u8]) -> image::Rgb<u8> {
+        let bytes = [raw[0], raw[1], raw[2], raw[3]];
+        let value = if self.is_msb_first {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        };
+
+        image::Rgb([
+            (value >> self.red_shift) as u8,
+            (value >> self.green_shift) as u8,
+            (value >> self.blue_shift) as u8,
+        ])
+    }
+}
+
+impl Capturer<RgbImage> for X11Capturer {
+    fn capture_rect(&self, rect: Rect<i32>) -> Result<RgbImage> {
+        let raw = self.get_image(rect)?;
+
+        let width = rect.width as u32;
+        let height = rect.height as u32;
+        let bytes_per_pixel = self.bits_per_pixel as u32 / 8;
+        // Z_PIXMAP scanlines are padded to 32 bits, which is always satisfied with 32 bits per pixel
+        let stride = width * bytes_per_pixel;
+
+        if raw.len() < (stride * height) as usize {
+            return Err(CaptureError::Backend(format!("X11 GetImage returned {} bytes, expected {}", raw.len(), stride * height)).into());
+        }
+
+        let img = ImageBuffer::from_fn(width, height, |x, y| {
+            let offset = (y * stride + x * bytes_per_pixel) as usize;
+            self.decode_pixel(&raw[offset..offset + 4])
+        });
+
+        Ok(img)
+    }
+
+    fn capture_color(&self, pos: Pos<i32>) -> Result<image::Rgb<u8>> {
+        let raw = self.get_image(Rect {
+            left: pos.x,
+            top: pos.y,
+            width: 1,
+            height: 1,
+        })?;
+        if raw.len() < 4 {
+            return Err(CaptureError::Backend(format!("X11 GetImage returned {} bytes, expected 4", raw.len())).into());
+        }
+
+        Ok(self.decode_pixel(&raw[0..4]))
+    }
+}
@@ -1,4 +1,7 @@
-#[derive(Debug, PartialEq, Default, Clone, Copy)]
+use image::Rgb;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Default, Clone, Copy, Serialize, Deserialize)]
 pub struct Color(pub u8, pub u8, pub u8);
 
 impl Color {
@@ -14,4 +17,30 @@ impl Color {
     pub fn new(r: u8, g: u8, b: u8) -> Color {
         Color(r, g, b)
     }
-}
\ No newline at end of file
+}
+
+impl From<Rgb<u8>> for Color {
+    fn from(rgb: Rgb<u8>) -> Self {
+        Color(rgb.0[0], rgb.0[1], rgb.0[2])
+    }
+}
+
+impl From<Color> for Rgb<u8> {
+    fn from(color: Color) -> Self {
+        Rgb([color.0, color.1, color.2])
+    }
+}
+
+/// Named reference colors in order, a sampled color is classified as the nearest of them
+#[derive(Debug, PartialEq, Default, Clone, Serialize, Deserialize)]
+pub struct ColorPalette(pub Vec<(String, Color)>);
+
+impl ColorPalette {
+    /// The index and the name of the color nearest to `color`, None if the palette is empty
+    pub fn nearest(&self, color: &Color) -> Option<(usize, &str)> {
+        self.0.iter()
+            .enumerate()
+            .min_by_key(|(_, (_, c))| c.distance(color))
+            .map(|(index, (name, _))| (index, name.as_str()))
+    }
+}
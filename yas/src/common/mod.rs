@@ -1,3 +1,4 @@
 pub mod cancel;
 pub mod color;
 pub mod image_ext;
+pub mod worker_pool;
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver};
+
+use log::error;
+
+/// Runs the items of a stream through several workers in parallel, each on its own thread.
+/// The results are yielded in the order of the items, no matter which worker finishes first
+///
+/// Dropping the pool stops the workers after their current item, they take no more items.
+/// A worker already waiting for an item drops the item it gets without processing it,
+/// then the item stream is dropped, so the sending side of a channel notices the scan has ended
+pub struct OrderedWorkerPool<O> {
+    results: Receiver<(usize, O)>,
+    // results which arrived before an earlier item is done
+    pending: BTreeMap<usize, O>,
+    next_index: usize,
+    stopped: Arc<AtomicBool>,
+}
+
+impl<O: Send + 'static> OrderedWorkerPool<O> {
+    /// Spawn a thread for each worker, every item is processed by `process` with one of the workers
+    pub fn spawn<I, It, W, F>(workers: Vec<W>, items: It, process: F) -> Self
+    where
+        I: Send + 'static,
+        It: Iterator<Item = I> + Send + 'static,
+        W: Send + 'static,
+        F: Fn(&W, I) -> O + Send + Sync + 'static,
+    {
+        let items = Arc::new(Mutex::new(items.enumerate()));
+        let process = Arc::new(process);
+        let (tx, rx) = mpsc::channel();
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        for worker in workers {
+            let items = items.clone();
+            let process = process.clone();
+            let tx = tx.clone();
+            let stopped = stopped.clone();
+
+            std::thread::spawn(move || loop {
+                // the lock is held only to take the next item
+                let next = match items.lock() {
+                    Ok(mut items) if !stopped.load(Ordering::Acquire) => items.next(),
+                    _ => None,
+                };
+                let (index, item) = match next {
+                    // the pool can be dropped while waiting for the item
+                    Some(v) if !stopped.load(Ordering::Acquire) => v,
+                    _ => break,
+                };
+
+                let result = process(&worker, item);
+                if tx.send((index, result)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        Self {
+            results: rx,
+            pending: BTreeMap::new(),
+            next_index: 0,
+            stopped,
+        }
+    }
+}
+
+impl<O> Drop for OrderedWorkerPool<O> {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Release);
+    }
+}
+
+impl<O> Iterator for OrderedWorkerPool<O> {
+    type Item = O;
+
+    fn next(&mut self) -> Option<O> {
+        loop {
+            if let Some(result) = self.pending.remove(&self.next_index) {
+                self.next_index += 1;
+                return Some(result);
+            }
+
+            match self.results.recv() {
+                Ok((index, result)) => {
+                    self.pending.insert(index, result);
+                },
+                Err(_) => {
+                    // all workers have ended, an item is missing only if a worker panicked,
+                    // the later results cannot be matched with their items then
+                    if !self.pending.is_empty() {
+                        error!("识别线程出现错误，第 {} 个物品之后的识别结果被丢弃", self.next_index);
+                    }
+                    return None;
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[test]
+    fn results_are_in_item_order() {
+        let items = (0..100_u64).rev();
+        let pool = OrderedWorkerPool::spawn(vec![(); 4], items, |_, item| {
+            std::thread::sleep(Duration::from_micros(item * 10));
+            item
+        });
+        assert_eq!(pool.collect::<Vec<_>>(), (0..100).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn dropping_the_pool_stops_taking_items() {
+        let (tx, rx) = mpsc::channel();
+        let processed = Arc::new(AtomicUsize::new(0));
+        let counter = processed.clone();
+        let mut pool = OrderedWorkerPool::spawn(vec![(); 4], rx.into_iter(), move |_, item: usize| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            item
+        });
+
+        tx.send(0).unwrap();
+        assert_eq!(pool.next(), Some(0));
+        drop(pool);
+
+        // a worker waiting for an item takes one more, then the stream is dropped
+        let start = Instant::now();
+        let mut sent = 0;
+        while tx.send(sent + 1).is_ok() {
+            sent += 1;
+            assert!(start.elapsed() < Duration::from_secs(5), "the workers did not stop");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert!(sent >= 1);
+        assert_eq!(processed.load(Ordering::SeqCst), 1);
+    }
+}
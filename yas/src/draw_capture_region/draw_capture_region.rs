@@ -5,28 +5,35 @@ pub trait DrawCaptureRegion {
     fn draw_capture_region(&self, image: &mut image::RgbImage);
 }
 
+/// Put a pixel, the pixels outside of the image are skipped
+fn put_pixel_clipped(image: &mut image::RgbImage, x: i64, y: i64, color: image::Rgb<u8>) {
+    if x >= 0 && y >= 0 && x < image.width() as i64 && y < image.height() as i64 {
+        image.put_pixel(x as u32, y as u32, color);
+    }
+}
+
 // todo other types
 impl DrawCaptureRegion for Pos<f64> {
     fn draw_capture_region(&self, image: &mut image::RgbImage) {
         let blue = image::Rgb([0, 0, 255]);
 
-        let x = self.x as u32;
-        let y = self.y as u32;
+        let x = self.x as i64;
+        let y = self.y as i64;
 
         for i in x - 1..=x + 1 {
             for j in y - 1..=y + 1 {
-                image.put_pixel(i, j, blue);
+                put_pixel_clipped(image, i, j, blue);
             }
         }
 
         for i in x - 5..=x + 5 {
-            image.put_pixel(i, y + 5, blue);
-            image.put_pixel(i, y - 5, blue);
+            put_pixel_clipped(image, i, y + 5, blue);
+            put_pixel_clipped(image, i, y - 5, blue);
         }
 
         for j in y - 5..=y + 5 {
-            image.put_pixel(x + 5, j, blue);
-            image.put_pixel(x - 5, j, blue);
+            put_pixel_clipped(image, x + 5, j, blue);
+            put_pixel_clipped(image, x - 5, j, blue);
         }
     }
 }
@@ -35,21 +42,21 @@ impl DrawCaptureRegion for Rect<f64> {
     fn draw_capture_region(&self, image: &mut image::RgbImage) {
         let red = image::Rgb([255, 0, 0]);
 
-        let left = self.left as u32;
-        let top = self.top as u32;
-        let width = self.width as u32;
-        let height = self.height as u32;
+        let left = self.left as i64;
+        let top = self.top as i64;
+        let width = self.width as i64;
+        let height = self.height as i64;
         let bottom = top + height;
         let right = left + width;
 
         for x in left..right {
-            image.put_pixel(x, top, red);
-            image.put_pixel(x, bottom, red);
+            put_pixel_clipped(image, x, top, red);
+            put_pixel_clipped(image, x, bottom, red);
         }
 
         for y in top..bottom {
-            image.put_pixel(left, y, red);
-            image.put_pixel(right, y, red);
+            put_pixel_clipped(image, left, y, red);
+            put_pixel_clipped(image, right, y, red);
         }
     }
-}
\ No newline at end of file
+}
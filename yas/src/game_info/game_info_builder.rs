@@ -1,9 +1,19 @@
 use super::game_info::GameInfo;
+use crate::game_info::{Platform, ResolutionFamily, UI};
+use crate::positioning::Rect;
 use anyhow::Result;
 
 pub struct GameInfoBuilder {
     pub local_window_names: Vec<String>,
     pub cloud_window_names: Vec<String>,
+    /// `WM_CLASS` names of the game window, e.g. the executable name under Wine, only used on Linux
+    pub window_classes: Vec<String>,
+
+    /// The game window relative to the screen, given by the user instead of found from the OS
+    pub window_rect: Option<Rect<i32>>,
+    pub ui: Option<UI>,
+    pub platform: Option<Platform>,
+    pub is_cloud: bool,
 }
 
 impl GameInfoBuilder {
@@ -11,6 +21,11 @@ impl GameInfoBuilder {
         GameInfoBuilder {
             local_window_names: Vec::new(),
             cloud_window_names: Vec::new(),
+            window_classes: Vec::new(),
+            window_rect: None,
+            ui: None,
+            platform: None,
+            is_cloud: false,
         }
     }
 
@@ -24,7 +39,51 @@ impl GameInfoBuilder {
         self
     }
 
+    pub fn add_window_class(&mut self, class: &str) -> &mut Self {
+        self.window_classes.push(String::from(class));
+        self
+    }
+
+    /// Skip finding the game window, `build` uses this rect, and the ui, platform and cloud set here
+    pub fn set_window_rect(&mut self, rect: Rect<i32>) -> &mut Self {
+        self.window_rect = Some(rect);
+        self
+    }
+
+    pub fn set_ui(&mut self, ui: UI) -> &mut Self {
+        self.ui = Some(ui);
+        self
+    }
+
+    pub fn set_platform(&mut self, platform: Platform) -> &mut Self {
+        self.platform = Some(platform);
+        self
+    }
+
+    pub fn set_cloud(&mut self, is_cloud: bool) -> &mut Self {
+        self.is_cloud = is_cloud;
+        self
+    }
+
+    fn build_from_window_rect(&self, rect: Rect<i32>) -> Result<GameInfo> {
+        let size = rect.to_rect_usize().size();
+        let platform = self.platform.unwrap_or(Platform::current());
+        let rf = ResolutionFamily::detect(size, platform)?;
+
+        Ok(GameInfo {
+            window: rect,
+            resolution_family: rf,
+            is_cloud: self.is_cloud,
+            ui: self.ui.unwrap_or(UI::Desktop),
+            platform,
+        })
+    }
+
     pub fn build(&self) -> Result<GameInfo> {
+        if let Some(rect) = self.window_rect {
+            return self.build_from_window_rect(rect);
+        }
+
         #[cfg(windows)]
         {
             let mut window_names = Vec::new();
@@ -39,6 +98,13 @@ impl GameInfoBuilder {
         }
         
         #[cfg(target_os = "linux")]
-        crate::game_info::os::get_game_info()
+        {
+            let window_names: Vec<&str> = self.local_window_names.iter()
+                .chain(self.cloud_window_names.iter())
+                .map(|name| name.as_str())
+                .collect();
+            let window_classes: Vec<&str> = self.window_classes.iter().map(|class| class.as_str()).collect();
+            crate::game_info::os::get_game_info(&window_names, &window_classes)
+        }
     }
 }
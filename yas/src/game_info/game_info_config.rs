@@ -0,0 +1,48 @@
+use crate::game_info::{GameInfoBuilder, Platform, UI};
+use crate::positioning::Rect;
+
+/// Parse "x,y,w,h" into a rect with a positive size
+fn parse_window_rect(s: &str) -> Result<Rect<i32>, String> {
+    let values = s.split(',')
+        .map(|x| x.trim().parse::<i32>())
+        .collect::<Result<Vec<i32>, _>>()
+        .map_err(|e| format!("{}", e))?;
+
+    match values[..] {
+        [left, top, width, height] if width > 0 && height > 0 => Ok(Rect::new(left, top, width, height)),
+        [_, _, _, _] => Err(String::from("宽和高应为正数")),
+        _ => Err(String::from("应为 x,y,宽,高")),
+    }
+}
+
+#[derive(Clone, clap::Args, Default)]
+pub struct GameInfoConfig {
+    /// The game window relative to the screen, which skips finding the window.
+    /// For headless and VNC sessions, nested compositors, or when the window cannot be found
+    #[arg(id = "window-rect", long = "window-rect", help = "手动指定游戏窗口相对屏幕的区域，跳过查找窗口", value_name = "X,Y,W,H", value_parser = parse_window_rect)]
+    pub window_rect: Option<Rect<i32>>,
+
+    #[arg(id = "window-ui", long = "ui", help = "手动指定窗口时，游戏的界面类型", value_enum, requires = "window-rect")]
+    pub ui: Option<UI>,
+
+    #[arg(id = "window-platform", long = "platform", help = "手动指定窗口时，游戏的平台，默认为当前系统", value_enum, requires = "window-rect")]
+    pub platform: Option<Platform>,
+
+    #[arg(id = "window-cloud", long = "cloud", help = "手动指定窗口时，游戏是否为云游戏", requires = "window-rect")]
+    pub is_cloud: bool,
+}
+
+impl GameInfoConfig {
+    /// Make the builder skip finding the window, if a window rect is given
+    pub fn apply_to(&self, builder: &mut GameInfoBuilder) {
+        let Some(rect) = self.window_rect else { return };
+
+        builder.set_window_rect(rect).set_cloud(self.is_cloud);
+        if let Some(ui) = self.ui {
+            builder.set_ui(ui);
+        }
+        if let Some(platform) = self.platform {
+            builder.set_platform(platform);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use thiserror::Error;
+
+use crate::game_info::ResolutionFamily;
+use crate::positioning::Size;
+
+/// Errors when locating the game window
+#[derive(Error, Debug)]
+pub enum GameInfoError {
+    /// The game is not running, or its window is closed
+    #[error("未找到游戏窗口，请确认{0:?}已经开启")]
+    WindowNotFound(Vec<String>),
+
+    #[error("索引{0}超出范围")]
+    InvalidSelection(usize),
+
+    #[error("Resolution not supported: {}x{}", .0.width, .0.height)]
+    UnsupportedResolution(Size<usize>),
+
+    /// The aspect ratio of the window is too far from that of every resolution family
+    #[error("Resolution not supported: {}x{}, the nearest family {:?} is {:.2}% off", .size.width, .size.height, .nearest, .residual * 100.0)]
+    UnknownResolutionFamily {
+        size: Size<usize>,
+        nearest: ResolutionFamily,
+        residual: f64,
+    },
+
+    /// The OS refused to tell the window geometry
+    #[error("cannot query game window: {0}")]
+    Query(String),
+}
@@ -1,10 +1,14 @@
 mod game_info;
 mod os;
 mod game_info_builder;
+mod game_info_config;
 mod ui;
 mod resolution_family;
+mod game_info_error;
 
 pub use game_info_builder::GameInfoBuilder;
+pub use game_info_config::GameInfoConfig;
 pub use ui::{UI, Platform};
 pub use resolution_family::ResolutionFamily;
 pub use game_info::GameInfo;
+pub use game_info_error::GameInfoError;
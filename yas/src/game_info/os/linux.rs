@@ -1,42 +1,168 @@
-use anyhow::{Result, anyhow};
+use std::io::stdin;
 
-use crate::game_info::{GameInfo, Platform, UI, ResolutionFamily};
+use anyhow::Result;
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{Atom, AtomEnum, ConnectionExt, MapState, Window};
+use x11rb::rust_connection::RustConnection;
+
+use crate::game_info::{GameInfo, GameInfoError, Platform, UI, ResolutionFamily};
 use crate::positioning::Rect;
 
-pub fn get_game_info() -> Result<GameInfo> {
-    let window_id = String::from_utf8(
-            std::process::Command::new("sh")
-                .arg("-c")
-                .arg(r#" xwininfo|grep "Window id"|cut -d " " -f 4 "#)
-                .output()
-                .unwrap()
-                .stdout,
-        )?;
-    let window_id = window_id.trim_end_matches("\n");
-
-    let position_size = String::from_utf8(
-            std::process::Command::new("sh")
-                .arg("-c")
-                .arg(&format!(r#" xwininfo -id {window_id}|cut -f 2 -d :|tr -cd "0-9\n"|grep -v "^$"|sed -n "1,2p;5,6p" "#))
-                .output()
-                .unwrap()
-                .stdout,
-        )?;
-
-    let mut info = position_size.split("\n");
-
-    let left = info.next().unwrap().parse().unwrap();
-    let top = info.next().unwrap().parse().unwrap();
-    let width = info.next().unwrap().parse().unwrap();
-    let height = info.next().unwrap().parse().unwrap();
-
-    let rect = Rect::new(left, top, width, height);
-    let rf = ResolutionFamily::new(rect.size()).ok_or(anyhow!("unknown resolution family"))?;
+fn is_window_cloud(title: &str) -> bool {
+    title.starts_with("云")
+}
+
+fn query_error<E: std::fmt::Display>(e: E) -> GameInfoError {
+    GameInfoError::Query(format!("X11: {}", e))
+}
+
+struct X11Windows {
+    conn: RustConnection,
+    root: Window,
+    net_client_list: Atom,
+    net_wm_name: Atom,
+    utf8_string: Atom,
+}
+
+impl X11Windows {
+    fn new() -> Result<Self> {
+        let (conn, screen_num) = x11rb::connect(None).map_err(query_error)?;
+        let root = conn.setup().roots[screen_num].root;
+
+        let intern = |name: &[u8]| -> Result<Atom> {
+            Ok(conn.intern_atom(false, name).map_err(query_error)?.reply().map_err(query_error)?.atom)
+        };
+        let net_client_list = intern(b"_NET_CLIENT_LIST")?;
+        let net_wm_name = intern(b"_NET_WM_NAME")?;
+        let utf8_string = intern(b"UTF8_STRING")?;
+
+        Ok(Self { conn, root, net_client_list, net_wm_name, utf8_string })
+    }
+
+    fn get_property(&self, window: Window, property: Atom, ty: Atom) -> Result<Vec<u8>> {
+        let reply = self.conn.get_property(false, window, property, ty, 0, u32::MAX / 4)
+            .map_err(query_error)?
+            .reply()
+            .map_err(query_error)?;
+        Ok(reply.value)
+    }
+
+    /// The top level windows, from `_NET_CLIENT_LIST` of an EWMH window manager,
+    /// or the viewable children of the root window without one
+    fn client_windows(&self) -> Result<Vec<Window>> {
+        let reply = self.conn.get_property(false, self.root, self.net_client_list, AtomEnum::WINDOW, 0, u32::MAX / 4)
+            .map_err(query_error)?
+            .reply()
+            .map_err(query_error)?;
+        if let Some(windows) = reply.value32() {
+            let windows: Vec<Window> = windows.collect();
+            if !windows.is_empty() {
+                return Ok(windows);
+            }
+        }
+
+        let children = self.conn.query_tree(self.root)
+            .map_err(query_error)?
+            .reply()
+            .map_err(query_error)?
+            .children;
+        let mut result = Vec::new();
+        for window in children {
+            let attributes = self.conn.get_window_attributes(window).map_err(query_error)?.reply();
+            if matches!(attributes, Ok(a) if a.map_state == MapState::VIEWABLE) {
+                result.push(window);
+            }
+        }
+        Ok(result)
+    }
+
+    /// `_NET_WM_NAME`, or `WM_NAME` if the window does not set it
+    fn title(&self, window: Window) -> Result<String> {
+        let name = self.get_property(window, self.net_wm_name, self.utf8_string)?;
+        let name = if name.is_empty() {
+            self.get_property(window, AtomEnum::WM_NAME.into(), AtomEnum::ANY.into())?
+        } else {
+            name
+        };
+        Ok(String::from_utf8_lossy(&name).trim().to_string())
+    }
+
+    /// The instance and the class names in `WM_CLASS`
+    fn class(&self, window: Window) -> Result<Vec<String>> {
+        let class = self.get_property(window, AtomEnum::WM_CLASS.into(), AtomEnum::STRING.into())?;
+        Ok(class.split(|&b| b == 0)
+            .filter(|s| !s.is_empty())
+            .map(|s| String::from_utf8_lossy(s).to_string())
+            .collect())
+    }
+
+    /// The client area of the window relative to the root window.
+    /// A reparenting window manager draws the decorations on a frame window around it, so they are excluded
+    fn client_rect(&self, window: Window) -> Result<Rect<i32>> {
+        let geometry = self.conn.get_geometry(window)
+            .map_err(query_error)?
+            .reply()
+            .map_err(query_error)?;
+        let origin = self.conn.translate_coordinates(window, self.root, 0, 0)
+            .map_err(query_error)?
+            .reply()
+            .map_err(query_error)?;
+        Ok(Rect::new(origin.dst_x as i32, origin.dst_y as i32, geometry.width as i32, geometry.height as i32))
+    }
+}
+
+fn get_window(windows: &X11Windows, window_names: &[&str], window_classes: &[&str]) -> Result<(Window, bool)> {
+    let mut viable_windows = Vec::new();
+    for window in windows.client_windows()? {
+        // windows can be destroyed while they are listed
+        let title = match windows.title(window) {
+            Ok(title) => title,
+            Err(_) => continue,
+        };
+        let class = windows.class(window).unwrap_or_default();
+
+        let title_matches = window_names.iter().any(|name| title == *name);
+        let class_matches = class.iter().any(|c| window_classes.iter().any(|name| c.eq_ignore_ascii_case(name)));
+        if title_matches || class_matches {
+            viable_windows.push((window, title));
+        }
+    }
+
+    if viable_windows.len() == 1 {
+        return Ok((viable_windows[0].0, is_window_cloud(&viable_windows[0].1)));
+    } else if viable_windows.is_empty() {
+        let names = window_names.iter().map(|name| String::from(*name)).collect();
+        return Err(GameInfoError::WindowNotFound(names).into());
+    }
+
+    println!("找到多个符合名称的窗口，请手动选择窗口：");
+    for (i, (_window, title)) in viable_windows.iter().enumerate() {
+        println!("{}: {}", i, title);
+    }
+    let mut index = String::new();
+    stdin().read_line(&mut index)?;
+
+    let idx = index.trim().parse::<usize>()?;
+    if idx < viable_windows.len() {
+        let is_cloud = is_window_cloud(&viable_windows[idx].1);
+        Ok((viable_windows[idx].0, is_cloud))
+    } else {
+        Err(GameInfoError::InvalidSelection(idx).into())
+    }
+}
+
+/// Find the game window by its title or `WM_CLASS` on an X11 display, which includes XWayland
+pub fn get_game_info(window_names: &[&str], window_classes: &[&str]) -> Result<GameInfo> {
+    let windows = X11Windows::new()?;
+    let (window, is_cloud) = get_window(&windows, window_names, window_classes)?;
+
+    let rect = windows.client_rect(window)?;
+    let rf = ResolutionFamily::detect(rect.to_rect_usize().size(), Platform::Linux)?;
 
     Ok(GameInfo {
-        window: rect.to_rect_i32(),
+        window: rect,
         resolution_family: rf,
-        is_cloud: false,
+        is_cloud,
         ui: UI::Desktop,
         platform: Platform::Linux,
     })
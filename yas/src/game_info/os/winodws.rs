@@ -1,7 +1,7 @@
 use std::io::stdin;
-use crate::game_info::{GameInfo, ResolutionFamily, UI, Platform};
+use crate::game_info::{GameInfo, GameInfoError, ResolutionFamily, UI, Platform};
 use crate::utils;
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use windows_sys::Win32::Foundation::HWND;
 use windows_sys::Win32::UI::WindowsAndMessaging::*;
 
@@ -38,7 +38,8 @@ fn get_window(window_names: &[&str]) -> Result<(HWND, bool)> {
     if viable_handles.len() == 1 {
         return Ok((viable_handles[0].0, is_window_cloud(&viable_handles[0].1)));
     } else if viable_handles.len() == 0 {
-        return Err(anyhow!("未找到游戏窗口，请确认{:?}已经开启", window_names));
+        let names = window_names.iter().map(|name| String::from(*name)).collect();
+        return Err(GameInfoError::WindowNotFound(names).into());
     }
 
     println!("找到多个符合名称的窗口，请手动选择窗口：");
@@ -53,7 +54,7 @@ fn get_window(window_names: &[&str]) -> Result<(HWND, bool)> {
         let is_cloud = is_window_cloud(&viable_handles[idx].1);
         Ok((viable_handles[idx].0, is_cloud))
     } else {
-        Err(anyhow!("索引{}超出范围", idx))
+        Err(GameInfoError::InvalidSelection(idx).into())
     }
 }
 
@@ -73,14 +74,12 @@ pub fn get_game_info(window_names: &[&str]) -> Result<GameInfo> {
     utils::sleep(1000);
 
     let rect = utils::get_client_rect(hwnd)?;
-    let resolution_family = ResolutionFamily::new(rect.to_rect_usize().size());
-    if resolution_family.is_none() {
-        return Err(anyhow!("Resolution not supported: {}x{}", rect.width, rect.height));
-    }
+    let size = rect.to_rect_usize().size();
+    let resolution_family = ResolutionFamily::detect(size, Platform::Windows)?;
 
     Ok(GameInfo {
         window: rect,
-        resolution_family: resolution_family.unwrap(),
+        resolution_family,
         is_cloud,
         ui: UI::Desktop,
         platform: Platform::Windows
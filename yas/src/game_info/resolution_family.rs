@@ -1,11 +1,19 @@
+use log::warn;
 use serde::{Deserialize, Serialize};
+use crate::game_info::{GameInfoError, Platform};
 use crate::positioning::Size;
 
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub enum ResolutionFamily {
     // PC
+    /// e.g. 3440x1440
     Windows43x18,
+    /// e.g. 2100x900, 2520x1080
     Windows7x3,
+    /// e.g. 2560x1080
+    Windows64x27,
+    /// e.g. 3840x1080, 5120x1440
+    Windows32x9,
     Windows16x9,
     Windows8x5,
     Windows4x3,
@@ -14,26 +22,153 @@ pub enum ResolutionFamily {
 }
 
 impl ResolutionFamily {
-    pub fn new(size: Size<usize>) -> Option<Self> {
-        // todo get OS at run time
-
-        let height = size.height as u32;
-        let width = size.width as u32;
-
-        if height * 43 == width * 18 {
-            Some(ResolutionFamily::Windows43x18)
-        } else if height * 16 == width * 9 {
-            Some(ResolutionFamily::Windows16x9)
-        } else if height * 8 == width * 5 {
-            Some(ResolutionFamily::Windows8x5)
-        } else if height * 4 == width * 3 {
-            Some(ResolutionFamily::Windows4x3)
-        } else if height * 7 == width * 3 {
-            Some(ResolutionFamily::Windows7x3)
-        } else if (height as i32 * 8 - width as i32 * 5).abs() < 20 {
-            Some(ResolutionFamily::MacOS8x5)
-        } else {
-            None
+    /// The largest residual accepted by `new`, about 1% of the aspect ratio
+    pub const TOLERANCE: f64 = 0.01;
+
+    /// The families found by their aspect ratios, `MacOS8x5` shares the ratio of `Windows8x5`
+    const BY_ASPECT_RATIO: [ResolutionFamily; 7] = [
+        ResolutionFamily::Windows43x18,
+        ResolutionFamily::Windows7x3,
+        ResolutionFamily::Windows64x27,
+        ResolutionFamily::Windows32x9,
+        ResolutionFamily::Windows16x9,
+        ResolutionFamily::Windows8x5,
+        ResolutionFamily::Windows4x3,
+    ];
+
+    /// Width and height of the aspect ratio
+    fn ratio(&self) -> (u32, u32) {
+        match *self {
+            ResolutionFamily::Windows43x18 => (43, 18),
+            ResolutionFamily::Windows7x3 => (7, 3),
+            ResolutionFamily::Windows64x27 => (64, 27),
+            ResolutionFamily::Windows32x9 => (32, 9),
+            ResolutionFamily::Windows16x9 => (16, 9),
+            ResolutionFamily::Windows8x5 => (8, 5),
+            ResolutionFamily::Windows4x3 => (4, 3),
+            ResolutionFamily::MacOS8x5 => (8, 5),
+        }
+    }
+
+    pub fn aspect_ratio(&self) -> f64 {
+        let (width, height) = self.ratio();
+        width as f64 / height as f64
+    }
+
+    /// The family with the nearest aspect ratio, and the residual, which is the log of the ratio
+    /// between the two aspect ratios, about their relative difference. None for an empty size
+    pub fn nearest(size: Size<usize>, platform: Platform) -> Option<(Self, f64)> {
+        if size.width == 0 || size.height == 0 {
+            return None;
+        }
+
+        let aspect_ratio = size.width as f64 / size.height as f64;
+        let (family, residual) = Self::BY_ASPECT_RATIO.iter()
+            .map(|family| (*family, (aspect_ratio / family.aspect_ratio()).ln().abs()))
+            .min_by(|a, b| a.1.total_cmp(&b.1))?;
+
+        // on macOS, an 8:5 window with the size slightly off is a window with its title bar
+        if platform == Platform::MacOS && family == ResolutionFamily::Windows8x5 && size.height * 8 != size.width * 5 {
+            return Some((ResolutionFamily::MacOS8x5, residual));
+        }
+        Some((family, residual))
+    }
+
+    /// The nearest family, if its residual is within `TOLERANCE`
+    pub fn new(size: Size<usize>, platform: Platform) -> Option<Self> {
+        match Self::nearest(size, platform) {
+            Some((family, residual)) if residual <= Self::TOLERANCE => Some(family),
+            _ => None,
+        }
+    }
+
+    /// Same as `new`, but warns when the size is not exactly of the family,
+    /// and the error tells the nearest family otherwise
+    pub fn detect(size: Size<usize>, platform: Platform) -> Result<Self, GameInfoError> {
+        let (family, residual) = Self::nearest(size, platform).ok_or(GameInfoError::UnsupportedResolution(size))?;
+        if residual > Self::TOLERANCE {
+            return Err(GameInfoError::UnknownResolutionFamily { size, nearest: family, residual });
+        }
+
+        let (width, height) = family.ratio();
+        if size.width * height as usize != size.height * width as usize {
+            warn!("窗口尺寸 {}x{} 不是 {}:{}，按 {:?} 处理，相差 {:.2}%", size.width, size.height, width, height, family, residual * 100.0);
+        }
+        Ok(family)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn family(width: usize, height: usize) -> Option<ResolutionFamily> {
+        ResolutionFamily::new(Size::new(width, height), Platform::Windows)
+    }
+
+    #[test]
+    fn exact_sizes() {
+        assert_eq!(family(1920, 1080), Some(ResolutionFamily::Windows16x9));
+        assert_eq!(family(1440, 900), Some(ResolutionFamily::Windows8x5));
+        assert_eq!(family(1280, 960), Some(ResolutionFamily::Windows4x3));
+        assert_eq!(family(3840, 1080), Some(ResolutionFamily::Windows32x9));
+        assert_eq!(family(5120, 1440), Some(ResolutionFamily::Windows32x9));
+    }
+
+    #[test]
+    fn off_by_one_sizes() {
+        assert_eq!(family(1921, 1080), Some(ResolutionFamily::Windows16x9));
+        assert_eq!(family(1920, 1079), Some(ResolutionFamily::Windows16x9));
+        assert_eq!(family(1440, 901), Some(ResolutionFamily::Windows8x5));
+        assert_eq!(family(1680, 1051), Some(ResolutionFamily::Windows8x5));
+    }
+
+    #[test]
+    fn off_8x5_is_macos_only_on_macos() {
+        let size = Size::new(1440, 901);
+        assert_eq!(ResolutionFamily::new(size, Platform::MacOS), Some(ResolutionFamily::MacOS8x5));
+        assert_eq!(ResolutionFamily::new(size, Platform::Linux), Some(ResolutionFamily::Windows8x5));
+        // an exact 8:5 window has no title bar
+        assert_eq!(ResolutionFamily::new(Size::new(1440, 900), Platform::MacOS), Some(ResolutionFamily::Windows8x5));
+    }
+
+    #[test]
+    fn close_wide_families_are_told_apart() {
+        // 64:27, 43:18 and 7:3 are less than 1% apart
+        assert_eq!(family(2560, 1080), Some(ResolutionFamily::Windows64x27));
+        assert_eq!(family(3440, 1440), Some(ResolutionFamily::Windows43x18));
+        assert_eq!(family(2100, 900), Some(ResolutionFamily::Windows7x3));
+        assert_eq!(family(2520, 1080), Some(ResolutionFamily::Windows7x3));
+        // 2540 / 1080 is a little closer to 64:27 than to 7:3
+        assert_eq!(family(2540, 1080), Some(ResolutionFamily::Windows64x27));
+    }
+
+    #[test]
+    fn empty_sizes() {
+        assert_eq!(ResolutionFamily::nearest(Size::new(0, 1080), Platform::Windows), None);
+        assert_eq!(ResolutionFamily::nearest(Size::new(1920, 0), Platform::Windows), None);
+        assert!(matches!(
+            ResolutionFamily::detect(Size::new(0, 0), Platform::Windows),
+            Err(GameInfoError::UnsupportedResolution(_))
+        ));
+    }
+
+    #[test]
+    fn residual() {
+        let (family, residual) = ResolutionFamily::nearest(Size::new(1920, 1080), Platform::Windows).unwrap();
+        assert_eq!(family, ResolutionFamily::Windows16x9);
+        assert_eq!(residual, 0.0);
+
+        let (_, residual) = ResolutionFamily::nearest(Size::new(1921, 1080), Platform::Windows).unwrap();
+        assert!((residual - (1921.0_f64 / 1920.0).ln()).abs() < 1e-12);
+
+        // a square window is 4:3 at best, about 29% off
+        match ResolutionFamily::detect(Size::new(1000, 1000), Platform::Windows) {
+            Err(GameInfoError::UnknownResolutionFamily { nearest, residual, .. }) => {
+                assert_eq!(nearest, ResolutionFamily::Windows4x3);
+                assert!((residual - (4.0_f64 / 3.0).ln()).abs() < 1e-12);
+            },
+            other => panic!("a square window is detected as {:?}", other),
         }
     }
 }
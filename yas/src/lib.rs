@@ -1,11 +1,10 @@
 #![feature(decl_macro)]
 #![allow(unused_imports)]
 
-#[cfg(all(feature = "ort", feature = "tract_onnx"))]
-compile_error!("feature \"ort\" and \"tract_onnx\" cannot be enabled at the same time");
-
 extern crate log;
 extern crate lazy_static;
+// the code of `yas_derive` refers to this crate as `yas`
+extern crate self as yas;
 
 pub mod common;
 pub mod export;
@@ -18,3 +17,4 @@ pub mod system_control;
 pub mod ocr;
 pub mod positioning;
 pub mod profiler;
+pub mod text;
@@ -0,0 +1,11 @@
+use anyhow::Result;
+
+use crate::ocr::backend::{OcrBackend, Tensor};
+
+/// A loaded ONNX model, each inference backend implements this
+pub trait InferenceSession: Send {
+    /// Run the model on its only input, returns the first output
+    fn run(&self, input: Tensor) -> Result<Tensor>;
+
+    fn backend(&self) -> OcrBackend;
+}
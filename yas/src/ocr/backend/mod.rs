@@ -0,0 +1,15 @@
+mod tensor;
+mod inference_session;
+mod ocr_backend;
+#[cfg(feature = "ort")]
+mod ort_session;
+#[cfg(feature = "tract_onnx")]
+mod tract_session;
+
+pub use tensor::Tensor;
+pub use inference_session::InferenceSession;
+pub use ocr_backend::OcrBackend;
+#[cfg(feature = "ort")]
+pub use ort_session::OrtSession;
+#[cfg(feature = "tract_onnx")]
+pub use tract_session::TractSession;
@@ -0,0 +1,76 @@
+use anyhow::Result;
+use log::warn;
+
+use crate::ocr::OcrError;
+use crate::ocr::backend::InferenceSession;
+#[cfg(feature = "ort")]
+use crate::ocr::backend::OrtSession;
+#[cfg(feature = "tract_onnx")]
+use crate::ocr::backend::TractSession;
+
+/// The library which runs the ONNX models
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OcrBackend {
+    /// onnxruntime if it can be loaded, tract otherwise
+    #[default]
+    Auto,
+    /// onnxruntime, needs the `ort` feature
+    Ort,
+    /// tract, in pure Rust, needs the `tract_onnx` feature
+    Tract,
+}
+
+impl OcrBackend {
+    /// Whether the backend is compiled in
+    pub fn is_available(self) -> bool {
+        match self {
+            OcrBackend::Auto => OcrBackend::Ort.is_available() || OcrBackend::Tract.is_available(),
+            OcrBackend::Ort => cfg!(feature = "ort"),
+            OcrBackend::Tract => cfg!(feature = "tract_onnx"),
+        }
+    }
+
+    /// Load a model, `input_shape` has `None` for the dimensions which vary between runs
+    pub fn load_session(self, model: &[u8], input_shape: &[Option<usize>]) -> Result<Box<dyn InferenceSession>> {
+        match self {
+            OcrBackend::Auto => {
+                if !OcrBackend::Tract.is_available() {
+                    return OcrBackend::Ort.load_session(model, input_shape);
+                }
+                if !OcrBackend::Ort.is_available() {
+                    return OcrBackend::Tract.load_session(model, input_shape);
+                }
+
+                match OcrBackend::Ort.load_session(model, input_shape) {
+                    Ok(session) => Ok(session),
+                    Err(e) => {
+                        warn!("无法使用 onnxruntime 加载模型，改用 tract：{}", e);
+                        OcrBackend::Tract.load_session(model, input_shape)
+                    },
+                }
+            },
+            OcrBackend::Ort => Self::load_ort(model),
+            OcrBackend::Tract => Self::load_tract(model, input_shape),
+        }
+    }
+
+    #[cfg(feature = "ort")]
+    fn load_ort(model: &[u8]) -> Result<Box<dyn InferenceSession>> {
+        Ok(Box::new(OrtSession::new(model)?))
+    }
+
+    #[cfg(not(feature = "ort"))]
+    fn load_ort(_model: &[u8]) -> Result<Box<dyn InferenceSession>> {
+        Err(OcrError::ModelLoad(String::from("the ort backend is not compiled in")).into())
+    }
+
+    #[cfg(feature = "tract_onnx")]
+    fn load_tract(model: &[u8], input_shape: &[Option<usize>]) -> Result<Box<dyn InferenceSession>> {
+        Ok(Box::new(TractSession::new(model, input_shape)?))
+    }
+
+    #[cfg(not(feature = "tract_onnx"))]
+    fn load_tract(_model: &[u8], _input_shape: &[Option<usize>]) -> Result<Box<dyn InferenceSession>> {
+        Err(OcrError::ModelLoad(String::from("the tract backend is not compiled in")).into())
+    }
+}
@@ -0,0 +1,101 @@
+use std::ffi::CStr;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+use ort::{GraphOptimizationLevel, Session};
+
+use crate::ocr::OcrError;
+use crate::ocr::backend::{InferenceSession, OcrBackend, Tensor};
+
+#[cfg(target_os = "windows")]
+const DYLIB_NAME: &str = "onnxruntime.dll";
+#[cfg(target_os = "macos")]
+const DYLIB_NAME: &str = "libonnxruntime.dylib";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const DYLIB_NAME: &str = "libonnxruntime.so";
+
+/// `ORT_DYLIB_PATH`, or the library next to the executable, or the library on the search path
+fn dylib_path() -> PathBuf {
+    let path = match std::env::var("ORT_DYLIB_PATH") {
+        Ok(path) if !path.is_empty() => PathBuf::from(path),
+        _ => PathBuf::from(DYLIB_NAME),
+    };
+    if path.is_absolute() {
+        return path;
+    }
+
+    let beside_exe = std::env::current_exe().ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(&path)));
+    match beside_exe {
+        Some(p) if p.exists() => p,
+        _ => path,
+    }
+}
+
+/// Check that the library loads and is recent enough, ort panics on both when it first loads it
+fn check_dylib(path: &PathBuf) -> Result<(), String> {
+    let lib = unsafe { libloading::Library::new(path) }
+        .map_err(|e| format!("cannot load onnxruntime from {:?}: {}", path, e))?;
+    let get_api_base: libloading::Symbol<unsafe extern "C" fn() -> *const ort::sys::OrtApiBase> = unsafe { lib.get(b"OrtGetApiBase") }
+        .map_err(|e| format!("{:?} is not onnxruntime: {}", path, e))?;
+
+    let base = unsafe { get_api_base() };
+    let get_version_string = unsafe { base.as_ref() }
+        .and_then(|base| base.GetVersionString)
+        .ok_or(format!("{:?} is not onnxruntime", path))?;
+    let version = unsafe { CStr::from_ptr(get_version_string()) }.to_string_lossy().to_string();
+
+    let minor_version = version.split('.').nth(1).and_then(|v| v.parse::<u32>().ok()).unwrap_or(0);
+    if minor_version < ort::MINOR_VERSION {
+        return Err(format!("onnxruntime {} at {:?} is too old, 1.{} or later is required", version, path, ort::MINOR_VERSION));
+    }
+    Ok(())
+}
+
+/// Runs models with onnxruntime
+pub struct OrtSession {
+    session: Session,
+}
+
+impl OrtSession {
+    /// Load onnxruntime once for the process, an error tells the library is missing or unusable
+    pub fn init() -> Result<(), OcrError> {
+        static INIT: OnceLock<Result<(), String>> = OnceLock::new();
+
+        INIT.get_or_init(|| {
+            let path = dylib_path();
+            check_dylib(&path)?;
+            ort::init_from(path.to_string_lossy())
+                .with_name("yas")
+                .commit()
+                .map_err(|e| format!("cannot initialize onnxruntime: {}", e))
+        }).clone().map_err(OcrError::ModelLoad)
+    }
+
+    pub fn new(model: &[u8]) -> Result<Self> {
+        Self::init()?;
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_intra_threads(4)?
+            .commit_from_memory(model)?;
+        Ok(Self {
+            session,
+        })
+    }
+}
+
+impl InferenceSession for OrtSession {
+    fn run(&self, input: Tensor) -> Result<Tensor> {
+        let shape = input.shape().to_vec();
+        let result = self.session.run(ort::inputs![(shape, input.into_data())]?)?;
+
+        let (shape, data) = result[0].try_extract_raw_tensor::<f32>()?;
+        let shape = shape.into_iter().map(|d| d as usize).collect();
+        Ok(Tensor::new(shape, data.to_vec())?)
+    }
+
+    fn backend(&self) -> OcrBackend {
+        OcrBackend::Ort
+    }
+}
@@ -0,0 +1,66 @@
+use crate::ocr::OcrError;
+
+/// A dense `f32` tensor in row-major order, the input and output of an `InferenceSession`
+#[derive(Clone, Debug)]
+pub struct Tensor {
+    shape: Vec<usize>,
+    data: Vec<f32>,
+}
+
+impl Tensor {
+    pub fn new(shape: Vec<usize>, data: Vec<f32>) -> Result<Self, OcrError> {
+        let len = shape.iter().product::<usize>();
+        if len != data.len() {
+            return Err(OcrError::Inference(format!("tensor of shape {:?} needs {} values, got {}", shape, len, data.len())));
+        }
+
+        Ok(Self {
+            shape,
+            data,
+        })
+    }
+
+    /// `f(index)` is the value at `index`
+    pub fn from_shape_fn<F>(shape: Vec<usize>, mut f: F) -> Self where F: FnMut(&[usize]) -> f32 {
+        let len = shape.iter().product::<usize>();
+        let mut data = Vec::with_capacity(len);
+        let mut index = vec![0; shape.len()];
+        for _ in 0..len {
+            data.push(f(&index));
+
+            // increase the index, the last dimension changes the fastest
+            for d in (0..shape.len()).rev() {
+                index[d] += 1;
+                if index[d] < shape[d] {
+                    break;
+                }
+                index[d] = 0;
+            }
+        }
+
+        Self {
+            shape,
+            data,
+        }
+    }
+
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    pub fn data(&self) -> &[f32] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<f32> {
+        self.data
+    }
+
+    pub fn get(&self, index: &[usize]) -> f32 {
+        let mut offset = 0;
+        for (&i, &dim) in index.iter().zip(self.shape.iter()) {
+            offset = offset * dim + i;
+        }
+        self.data[offset]
+    }
+}
@@ -0,0 +1,77 @@
+use anyhow::Result;
+use tract_onnx::prelude::{
+    tvec, DatumExt, DatumType, Framework, Graph, InferenceFact, InferenceModelExt, RunnableModel, TDim, ToDim, TypedFact, TypedOp, TVec,
+};
+use tract_onnx::tract_hir::infer::{DimFact, GenericFactoid, InferenceOp, ShapeFactoid};
+
+use crate::ocr::backend::{InferenceSession, OcrBackend, Tensor};
+
+type TypedModelType = RunnableModel<TypedFact, Box<dyn TypedOp>, Graph<TypedFact, Box<dyn TypedOp>>>;
+type InferenceModelType = RunnableModel<InferenceFact, Box<dyn InferenceOp>, Graph<InferenceFact, Box<dyn InferenceOp>>>;
+
+enum TractModel {
+    Typed(TypedModelType),
+    Inference(InferenceModelType),
+}
+
+/// Runs models with tract, in pure Rust
+pub struct TractSession {
+    model: TractModel,
+}
+
+impl TractSession {
+    /// `input_shape` has `None` for the dimensions which vary between runs.
+    /// If only the batch size varies, the model is optimized with a symbolic batch size
+    pub fn new(model: &[u8], input_shape: &[Option<usize>]) -> Result<Self> {
+        let mut reader = model;
+        let onnx = tract_onnx::onnx().model_for_read(&mut reader)?;
+
+        let is_batch_only = input_shape.iter().skip(1).all(|d| d.is_some());
+        let model = if is_batch_only {
+            let batch = onnx.symbol_table.sym("N");
+            let shape = std::iter::once(batch.to_dim())
+                .chain(input_shape.iter().skip(1).flatten().map(|d| d.to_dim()))
+                .collect::<TVec<TDim>>();
+            let model = onnx
+                .with_input_fact(0, f32::fact(shape).into())?
+                .into_optimized()?
+                .into_runnable()?;
+            TractModel::Typed(model)
+        } else {
+            let dims = input_shape.iter()
+                .map(|d| match d {
+                    Some(v) => GenericFactoid::Only(v.to_dim()),
+                    None => DimFact::default(),
+                })
+                .collect::<TVec<DimFact>>();
+            let fact = InferenceFact::new()
+                .with_datum_type(DatumType::F32)
+                .with_shape(ShapeFactoid::closed(dims));
+            let model = onnx
+                .with_input_fact(0, fact)?
+                .into_runnable()?;
+            TractModel::Inference(model)
+        };
+
+        Ok(Self {
+            model,
+        })
+    }
+}
+
+impl InferenceSession for TractSession {
+    fn run(&self, input: Tensor) -> Result<Tensor> {
+        let tensor = tract_onnx::prelude::Tensor::from_shape(input.shape(), input.data())?;
+        let result = match &self.model {
+            TractModel::Typed(model) => model.run(tvec!(tensor.into()))?,
+            TractModel::Inference(model) => model.run(tvec!(tensor.into()))?,
+        };
+
+        let arr = result[0].to_array_view::<f32>()?;
+        Ok(Tensor::new(arr.shape().to_vec(), arr.iter().cloned().collect())?)
+    }
+
+    fn backend(&self) -> OcrBackend {
+        OcrBackend::Tract
+    }
+}
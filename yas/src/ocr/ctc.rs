@@ -0,0 +1,662 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use regex::Regex;
+use regex_automata::dfa::{dense, Automaton};
+use regex_automata::util::primitives::StateID;
+use regex_automata::util::start;
+
+/// A character (a word of the model vocabulary) and its probability
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecognizedChar {
+    pub text: String,
+    pub confidence: f32,
+}
+
+/// Text recognized by an OCR model, with the confidence of each character
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecognizedText {
+    pub text: String,
+    pub chars: Vec<RecognizedChar>,
+    /// mean probability of the characters, 1.0 if nothing is recognized
+    pub confidence: f32,
+}
+
+impl RecognizedText {
+    /// Fields below this confidence are worth a second look
+    pub const LOW_CONFIDENCE: f32 = 0.8;
+
+    pub fn empty() -> Self {
+        Self {
+            text: String::new(),
+            chars: Vec::new(),
+            confidence: 1.0,
+        }
+    }
+
+    /// For models which cannot estimate the confidence
+    pub fn without_confidence(text: String) -> Self {
+        let chars = text.chars()
+            .map(|c| RecognizedChar {
+                text: c.to_string(),
+                confidence: 1.0,
+            })
+            .collect();
+        Self {
+            text,
+            chars,
+            confidence: 1.0,
+        }
+    }
+
+    pub fn from_chars(chars: Vec<RecognizedChar>) -> Self {
+        if chars.is_empty() {
+            return Self::empty();
+        }
+
+        let text = chars.iter().map(|c| c.text.as_str()).collect();
+        let confidence = chars.iter().map(|c| c.confidence).sum::<f32>() / chars.len() as f32;
+        Self {
+            text,
+            chars,
+            confidence,
+        }
+    }
+
+    pub fn is_low_confidence(&self) -> bool {
+        self.confidence < Self::LOW_CONFIDENCE
+    }
+}
+
+/// Restricts what the decoder may output for a field
+#[derive(Clone, Debug)]
+pub enum DecodeConstraint {
+    /// The text must be one of the words
+    Lexicon(Vec<String>),
+    /// The text must match the regex, anchor it with `^` and `$` to match the whole text.
+    /// The DFA of the same pattern tells during decoding which prefixes can still match
+    Regex {
+        regex: Regex,
+        dfa: Box<dense::DFA<Vec<u32>>>,
+    },
+}
+
+impl DecodeConstraint {
+    pub fn lexicon<I, S>(words: I) -> Self where I: IntoIterator<Item = S>, S: Into<String> {
+        DecodeConstraint::Lexicon(words.into_iter().map(|w| w.into()).collect())
+    }
+
+    pub fn regex(pattern: &str) -> anyhow::Result<Self> {
+        Ok(DecodeConstraint::Regex {
+            regex: Regex::new(pattern)?,
+            dfa: Box::new(dense::DFA::new(pattern)?),
+        })
+    }
+
+    pub fn is_satisfied_by(&self, text: &str) -> bool {
+        match self {
+            DecodeConstraint::Lexicon(words) => words.iter().any(|w| w == text),
+            DecodeConstraint::Regex { regex, .. } => regex.is_match(text),
+        }
+    }
+
+    /// For models which cannot constrain the decoding, a text violating the constraint gets 0 confidence.
+    /// An empty text is never a violation, the field may be absent
+    pub(crate) fn check(&self, mut text: RecognizedText) -> RecognizedText {
+        if !text.text.is_empty() && !self.is_satisfied_by(&text.text) {
+            text.confidence = 0.0;
+        }
+        text
+    }
+}
+
+/// The words a CTC model outputs, `None` is the blank
+pub(crate) struct CtcVocabulary {
+    words: Vec<Option<String>>,
+    blank: usize,
+    word_to_class: HashMap<String, usize>,
+    // in chars
+    max_word_len: usize,
+}
+
+impl CtcVocabulary {
+    pub fn new(words: Vec<Option<String>>) -> Self {
+        let blank = words.iter().position(|w| w.is_none()).unwrap_or(0);
+        let mut word_to_class = HashMap::new();
+        for (class, word) in words.iter().enumerate() {
+            if let Some(word) = word {
+                word_to_class.entry(word.clone()).or_insert(class);
+            }
+        }
+        let max_word_len = word_to_class.keys().map(|w| w.chars().count()).max().unwrap_or(1);
+
+        Self {
+            words,
+            blank,
+            word_to_class,
+            max_word_len,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// The word of a class, classes out of the vocabulary are treated as the blank
+    pub fn word(&self, class: usize) -> Option<&str> {
+        self.words.get(class).and_then(|w| w.as_deref())
+    }
+
+    /// Split a text into classes, longest words first. None if a part of the text is not in the vocabulary
+    fn tokenize(&self, text: &str) -> Option<Vec<usize>> {
+        let chars = text.chars().collect::<Vec<char>>();
+        let mut labels = Vec::new();
+        let mut i = 0;
+        while i < chars.len() {
+            let max_len = self.max_word_len.min(chars.len() - i);
+            let (len, class) = (1..=max_len).rev()
+                .find_map(|len| {
+                    let word = chars[i..i + len].iter().collect::<String>();
+                    self.word_to_class.get(&word).map(|&class| (len, class))
+                })?;
+            labels.push(class);
+            i += len;
+        }
+        Some(labels)
+    }
+}
+
+/// Follows a label sequence as the beam search extends it,
+/// so that the prefixes which cannot lead to an accepted text are dropped early
+trait PrefixAutomaton {
+    type State: Copy + Eq + Hash;
+
+    fn start(&self) -> Self::State;
+
+    /// None if no accepted text starts with the prefix extended by `label`
+    fn next(&self, state: Self::State, label: usize) -> Option<Self::State>;
+
+    fn is_accepting(&self, state: Self::State) -> bool;
+}
+
+/// Runs the bytes of the words through a regex DFA, with the semantics of `Regex::is_match`
+struct RegexAutomaton<'a> {
+    dfa: &'a dense::DFA<Vec<u32>>,
+    vocabulary: &'a CtcVocabulary,
+}
+
+/// The DFA state, and whether the text already contains a match
+type RegexState = (StateID, bool);
+
+impl<'a> PrefixAutomaton for RegexAutomaton<'a> {
+    type State = Option<RegexState>;
+
+    /// None if the DFA cannot start, then nothing is accepted
+    fn start(&self) -> Option<RegexState> {
+        let config = start::Config::new().anchored(regex_automata::Anchored::No);
+        self.dfa.start_state(&config).ok().map(|id| (id, false))
+    }
+
+    fn next(&self, state: Option<RegexState>, label: usize) -> Option<Option<RegexState>> {
+        let (mut id, mut matched) = state?;
+        for &byte in self.vocabulary.word(label).unwrap_or("").as_bytes() {
+            id = self.dfa.next_state(id, byte);
+            // matches are reported one byte late
+            matched = matched || self.dfa.is_match_state(id);
+            if self.dfa.is_dead_state(id) || self.dfa.is_quit_state(id) {
+                // a text which contains a match keeps matching whatever follows
+                return if matched { Some(Some((id, true))) } else { None };
+            }
+        }
+        Some(Some((id, matched)))
+    }
+
+    fn is_accepting(&self, state: Option<RegexState>) -> bool {
+        match state {
+            Some((_, true)) => true,
+            Some((id, false)) => self.dfa.is_match_state(self.dfa.next_eoi_state(id)),
+            None => false,
+        }
+    }
+}
+
+/// The tokenized words of a lexicon as a trie of labels
+struct LexiconTrie {
+    /// the children and whether a word ends here, the root is the first node
+    nodes: Vec<(HashMap<usize, usize>, bool)>,
+}
+
+impl LexiconTrie {
+    fn new(words: &[String], vocabulary: &CtcVocabulary) -> Self {
+        let mut nodes = vec![(HashMap::new(), false)];
+        for labels in words.iter().filter_map(|word| vocabulary.tokenize(word)) {
+            let mut node = 0;
+            for label in labels {
+                node = match nodes[node].0.get(&label) {
+                    Some(&child) => child,
+                    None => {
+                        nodes.push((HashMap::new(), false));
+                        let child = nodes.len() - 1;
+                        nodes[node].0.insert(label, child);
+                        child
+                    },
+                };
+            }
+            nodes[node].1 = true;
+        }
+        Self { nodes }
+    }
+}
+
+impl PrefixAutomaton for LexiconTrie {
+    type State = usize;
+
+    fn start(&self) -> usize {
+        0
+    }
+
+    fn next(&self, state: usize, label: usize) -> Option<usize> {
+        self.nodes[state].0.get(&label).copied()
+    }
+
+    fn is_accepting(&self, state: usize) -> bool {
+        self.nodes[state].1
+    }
+}
+
+/// A label sequence kept by the beam search
+struct Beam<S> {
+    /// log probability of the paths ending with a blank
+    p_blank: f64,
+    /// log probability of the paths ending with the last label
+    p_label: f64,
+    state: S,
+}
+
+impl<S> Beam<S> {
+    fn new(state: S) -> Self {
+        Self {
+            p_blank: f64::NEG_INFINITY,
+            p_label: f64::NEG_INFINITY,
+            state,
+        }
+    }
+
+    fn p_total(&self) -> f64 {
+        log_add(self.p_blank, self.p_label)
+    }
+}
+
+fn log_add(a: f64, b: f64) -> f64 {
+    if a == f64::NEG_INFINITY {
+        return b;
+    }
+    if b == f64::NEG_INFINITY {
+        return a;
+    }
+    let max = a.max(b);
+    max + ((a - max).exp() + (b - max).exp()).ln()
+}
+
+/// The probability of each word at each time step of a CTC output
+pub(crate) struct CtcProbabilities {
+    steps: usize,
+    classes: usize,
+    data: Vec<f32>,
+}
+
+impl CtcProbabilities {
+    /// How many label sequences the beam search keeps at each time step
+    const BEAM_WIDTH: usize = 10;
+    /// Words below this probability are not considered by the beam search
+    const MIN_CANDIDATE_PROBABILITY: f32 = 1e-3;
+
+    /// `get_score(i, j)` is the output of word `j` at time step `i`.
+    /// Models may output probabilities, logits or log probabilities,
+    /// rows which are not already a distribution are normalized with softmax
+    pub fn from_scores<F>(steps: usize, classes: usize, get_score: F) -> Self where F: Fn(usize, usize) -> f32 {
+        let mut data = Vec::with_capacity(steps * classes);
+        for i in 0..steps {
+            let row = (0..classes).map(|j| get_score(i, j)).collect::<Vec<f32>>();
+
+            let sum = row.iter().sum::<f32>();
+            let is_distribution = row.iter().all(|&v| v >= 0.0) && (sum - 1.0).abs() < 1e-3;
+            if is_distribution {
+                data.extend(row);
+            } else {
+                let max = row.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+                let exp = row.iter().map(|v| (v - max).exp()).collect::<Vec<f32>>();
+                let exp_sum = exp.iter().sum::<f32>();
+                data.extend(exp.iter().map(|v| v / exp_sum));
+            }
+        }
+
+        Self {
+            steps,
+            classes,
+            data,
+        }
+    }
+
+    pub fn get(&self, step: usize, class: usize) -> f32 {
+        self.data[step * self.classes + class]
+    }
+
+    fn log_get(&self, step: usize, class: usize) -> f64 {
+        (self.get(step, class) as f64).ln()
+    }
+
+    /// Greedy decoding, takes the most probable word at each time step
+    pub fn greedy_decode(&self, vocabulary: &CtcVocabulary) -> RecognizedText {
+        let mut chars: Vec<RecognizedChar> = Vec::new();
+        let mut last_class = None;
+        for i in 0..self.steps {
+            let mut max_class = 0;
+            let mut max_value = -1.0_f32;
+            for j in 0..self.classes {
+                let value = self.get(i, j);
+                if value > max_value {
+                    max_value = value;
+                    max_class = j;
+                }
+            }
+
+            if last_class == Some(max_class) {
+                // the same character spans several time steps, keep its best probability
+                if vocabulary.word(max_class).is_some() {
+                    let last = chars.last_mut().unwrap();
+                    last.confidence = last.confidence.max(max_value);
+                }
+            } else if let Some(word) = vocabulary.word(max_class) {
+                chars.push(RecognizedChar {
+                    text: String::from(word),
+                    confidence: max_value,
+                });
+            }
+
+            last_class = Some(max_class);
+        }
+
+        RecognizedText::from_chars(chars)
+    }
+
+    /// Decode the most probable text satisfying the constraint.
+    /// If nothing satisfies it, the greedy result is returned with 0 confidence
+    pub fn decode(&self, vocabulary: &CtcVocabulary, constraint: Option<&DecodeConstraint>) -> RecognizedText {
+        let greedy = self.greedy_decode(vocabulary);
+        let constraint = match constraint {
+            Some(c) => c,
+            None => return greedy,
+        };
+        if greedy.text.is_empty() || constraint.is_satisfied_by(&greedy.text) {
+            return greedy;
+        }
+
+        // the candidates are sorted by probability, and all of them satisfy the constraint
+        let candidates = match constraint {
+            DecodeConstraint::Lexicon(words) => self.beam_search(vocabulary, &LexiconTrie::new(words, vocabulary)),
+            DecodeConstraint::Regex { dfa, .. } => self.beam_search(vocabulary, &RegexAutomaton { dfa, vocabulary }),
+        };
+        candidates.into_iter()
+            .find_map(|labels| self.align(vocabulary, &labels))
+            .map(|(_, text)| text)
+            .unwrap_or_else(|| constraint.check(greedy))
+    }
+
+    /// Prefix beam search, returns the most probable label sequences accepted by the automaton, best first.
+    /// The prefixes the automaton rejects are dropped as soon as they appear, so they take no room in the beam
+    fn beam_search<A: PrefixAutomaton>(&self, vocabulary: &CtcVocabulary, automaton: &A) -> Vec<Vec<usize>> {
+        let blank = vocabulary.blank;
+        let mut beams = vec![(Vec::new(), Beam {
+            p_blank: 0.0,
+            p_label: f64::NEG_INFINITY,
+            state: automaton.start(),
+        })];
+
+        for i in 0..self.steps {
+            let candidates = (0..self.classes)
+                .filter(|&j| j != blank && self.get(i, j) >= Self::MIN_CANDIDATE_PROBABILITY)
+                .collect::<Vec<usize>>();
+
+            let mut next: HashMap<Vec<usize>, Beam<A::State>> = HashMap::new();
+            for (labels, beam) in beams.iter() {
+                let p_total = beam.p_total();
+
+                let entry = next.entry(labels.clone()).or_insert(Beam::new(beam.state));
+                entry.p_blank = log_add(entry.p_blank, p_total + self.log_get(i, blank));
+                if let Some(&last) = labels.last() {
+                    // the last label spans this time step too
+                    entry.p_label = log_add(entry.p_label, beam.p_label + self.log_get(i, last));
+                }
+
+                for &j in candidates.iter() {
+                    let state = match automaton.next(beam.state, j) {
+                        Some(s) => s,
+                        None => continue,
+                    };
+                    let mut extended = labels.clone();
+                    extended.push(j);
+                    // a repeated label must be separated by a blank
+                    let p_prefix = if labels.last() == Some(&j) { beam.p_blank } else { p_total };
+
+                    let entry = next.entry(extended).or_insert(Beam::new(state));
+                    entry.p_label = log_add(entry.p_label, p_prefix + self.log_get(i, j));
+                }
+            }
+
+            let mut next = next.into_iter().collect::<Vec<_>>();
+            next.sort_by(|(_, b1), (_, b2)| b2.p_total().total_cmp(&b1.p_total()));
+            next.truncate(Self::BEAM_WIDTH);
+            beams = next;
+        }
+
+        beams.into_iter()
+            .filter(|(_, beam)| automaton.is_accepting(beam.state))
+            .map(|(labels, _)| labels)
+            .collect()
+    }
+
+    /// Viterbi alignment of a label sequence, returns the log probability of the best path
+    /// and the text with the best probability of each label along the path.
+    /// None if the sequence cannot fit in the time steps
+    fn align(&self, vocabulary: &CtcVocabulary, labels: &[usize]) -> Option<(f64, RecognizedText)> {
+        let blank = vocabulary.blank;
+        // blanks interleaved with the labels
+        let mut states = vec![blank];
+        for &label in labels {
+            states.push(label);
+            states.push(blank);
+        }
+        let state_count = states.len();
+        if self.steps == 0 {
+            return None;
+        }
+
+        let mut score = vec![f64::NEG_INFINITY; state_count];
+        let mut back = vec![vec![0_usize; state_count]; self.steps];
+        score[0] = self.log_get(0, blank);
+        if state_count > 1 {
+            score[1] = self.log_get(0, states[1]);
+        }
+
+        for (i, back_step) in back.iter_mut().enumerate().skip(1) {
+            let mut next = vec![f64::NEG_INFINITY; state_count];
+            for s in 0..state_count {
+                let mut best = (score[s], s);
+                if s >= 1 && score[s - 1] > best.0 {
+                    best = (score[s - 1], s - 1);
+                }
+                if s >= 2 && states[s] != blank && states[s] != states[s - 2] && score[s - 2] > best.0 {
+                    best = (score[s - 2], s - 2);
+                }
+                if best.0 > f64::NEG_INFINITY {
+                    next[s] = best.0 + self.log_get(i, states[s]);
+                    back_step[s] = best.1;
+                }
+            }
+            score = next;
+        }
+
+        let mut state = state_count - 1;
+        if state_count > 1 && score[state_count - 2] > score[state] {
+            state = state_count - 2;
+        }
+        let log_probability = score[state];
+        if log_probability == f64::NEG_INFINITY {
+            return None;
+        }
+
+        let mut confidences = vec![0.0_f32; labels.len()];
+        for i in (0..self.steps).rev() {
+            if state % 2 == 1 {
+                let index = state / 2;
+                confidences[index] = confidences[index].max(self.get(i, states[state]));
+            }
+            state = back[i][state];
+        }
+
+        let chars = labels.iter().zip(confidences)
+            .filter_map(|(&label, confidence)| vocabulary.word(label).map(|word| RecognizedChar {
+                text: String::from(word),
+                confidence,
+            }))
+            .collect();
+        Some((log_probability, RecognizedText::from_chars(chars)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Unconstrained;
+
+    impl PrefixAutomaton for Unconstrained {
+        type State = ();
+
+        fn start(&self) {}
+
+        fn next(&self, _state: (), _label: usize) -> Option<()> {
+            Some(())
+        }
+
+        fn is_accepting(&self, _state: ()) -> bool {
+            true
+        }
+    }
+
+    /// The blank is the first word
+    fn vocabulary(words: &[&str]) -> CtcVocabulary {
+        let mut all = vec![None];
+        all.extend(words.iter().map(|w| Some(String::from(*w))));
+        CtcVocabulary::new(all)
+    }
+
+    fn probabilities(rows: &[&[f32]]) -> CtcProbabilities {
+        CtcProbabilities::from_scores(rows.len(), rows[0].len(), |i, j| rows[i][j])
+    }
+
+    #[test]
+    fn beam_search_sums_the_paths_of_a_label_sequence() {
+        let vocabulary = vocabulary(&["a"]);
+        // the greedy path is two blanks, but "a" has 0.64 over all its paths against 0.36
+        let p = probabilities(&[&[0.6, 0.4], &[0.6, 0.4]]);
+
+        assert_eq!(p.greedy_decode(&vocabulary).text, "");
+        let beams = p.beam_search(&vocabulary, &Unconstrained);
+        assert_eq!(beams[0], vec![1]);
+        assert_eq!(beams[1], Vec::<usize>::new());
+    }
+
+    #[test]
+    fn align_finds_the_best_path() {
+        let vocabulary = vocabulary(&["a", "b"]);
+        let p = probabilities(&[
+            &[0.1, 0.8, 0.1],
+            &[0.7, 0.2, 0.1],
+            &[0.2, 0.1, 0.7],
+        ]);
+
+        let (log_probability, text) = p.align(&vocabulary, &[1, 2]).unwrap();
+        assert!((log_probability - (0.8_f64 * 0.7 * 0.7).ln()).abs() < 1e-6);
+        assert_eq!(text.text, "ab");
+        assert_eq!(text.chars[0].confidence, 0.8);
+        assert_eq!(text.chars[1].confidence, 0.7);
+    }
+
+    #[test]
+    fn align_rejects_a_sequence_longer_than_the_steps() {
+        let vocabulary = vocabulary(&["a"]);
+        let p = probabilities(&[&[0.5, 0.5], &[0.5, 0.5]]);
+
+        // a repeated label needs a blank in between
+        assert!(p.align(&vocabulary, &[1, 1]).is_none());
+        assert!(p.align(&vocabulary, &[1]).is_some());
+    }
+
+    #[test]
+    fn regex_prunes_prefixes_during_the_search() {
+        let letters = ["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l"];
+        let mut words = vec!["+", "7"];
+        words.extend(letters);
+        let vocabulary = vocabulary(&words);
+
+        let mut first = vec![0.0; words.len() + 1];
+        first[0] = 0.1;
+        first[1] = 0.9;
+        // the digit is less probable than every letter, unconstrained it falls out of the beam
+        let mut second = vec![0.07; words.len() + 1];
+        second[0] = 0.15;
+        second[1] = 0.0;
+        second[2] = 0.01;
+        let p = probabilities(&[&first, &second]);
+
+        let unconstrained = p.beam_search(&vocabulary, &Unconstrained);
+        assert!(!unconstrained.contains(&vec![1, 2]));
+
+        let constraint = DecodeConstraint::regex(r"^\+\d+$").unwrap();
+        let text = p.decode(&vocabulary, Some(&constraint));
+        assert_eq!(text.text, "+7");
+        assert_eq!(text.chars[1].confidence, 0.01);
+    }
+
+    #[test]
+    fn unanchored_regex_accepts_text_around_the_match() {
+        let vocabulary = vocabulary(&["x", "1"]);
+        let p = probabilities(&[
+            &[0.0, 1.0, 0.0],
+            &[0.0, 0.6, 0.4],
+            &[0.0, 1.0, 0.0],
+        ]);
+
+        assert_eq!(p.greedy_decode(&vocabulary).text, "x");
+        let constraint = DecodeConstraint::regex(r"\d").unwrap();
+        assert_eq!(p.decode(&vocabulary, Some(&constraint)).text, "x1x");
+    }
+
+    #[test]
+    fn lexicon_only_follows_the_words() {
+        let vocabulary = vocabulary(&["攻", "击", "力", "防", "御"]);
+        let p = probabilities(&[
+            &[0.0, 0.8, 0.0, 0.0, 0.2, 0.0],
+            &[0.0, 0.0, 0.4, 0.0, 0.0, 0.6],
+            &[0.1, 0.0, 0.0, 0.9, 0.0, 0.0],
+        ]);
+        let constraint = DecodeConstraint::lexicon(["攻击力", "防御力"]);
+
+        assert_eq!(p.greedy_decode(&vocabulary).text, "攻御力");
+        let text = p.decode(&vocabulary, Some(&constraint));
+        assert_eq!(text.text, "攻击力");
+        assert_eq!(text.chars[1].confidence, 0.4);
+    }
+
+    #[test]
+    fn unsatisfiable_constraint_keeps_the_greedy_text_with_zero_confidence() {
+        let vocabulary = vocabulary(&["a"]);
+        let p = probabilities(&[&[0.1, 0.9]]);
+
+        let text = p.decode(&vocabulary, Some(&DecodeConstraint::lexicon(["b"])));
+        assert_eq!(text.text, "a");
+        assert_eq!(text.confidence, 0.0);
+    }
+}
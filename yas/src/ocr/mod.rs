@@ -1,10 +1,25 @@
 mod traits;
-mod yas_model;
+mod ocr_error;
+mod ctc;
+mod text_box;
+pub mod yas_model;
 mod paddle_paddle_model;
+mod model_manifest;
+mod ocr_model_config;
+mod backend;
+mod panel_fields;
 
 pub use yas_model::yas_ocr_model::YasOCRModel;
 pub use yas_model::yas_ocr_model::yas_ocr_model;
-pub use traits::ImageToText;
+pub use traits::{ImageTextDetection, ImageToText};
+pub use ocr_error::OcrError;
+pub use ctc::{DecodeConstraint, RecognizedChar, RecognizedText};
+pub use text_box::TextBox;
 pub use paddle_paddle_model::PPOCRModel;
 pub use paddle_paddle_model::PPOCRChV4RecInfer;
+pub use paddle_paddle_model::PPOCRDetModel;
 pub use paddle_paddle_model::ppocr_model;
+pub use model_manifest::{ModelFiles, ModelManifest, ModelManifestEntry};
+pub use ocr_model_config::OcrModelConfig;
+pub use backend::{InferenceSession, OcrBackend, Tensor};
+pub use panel_fields::{crop_image, recognize_field_array, recognize_fields, LEVEL_PATTERN, STAT_VALUE_PATTERN};
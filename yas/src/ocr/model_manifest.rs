@@ -0,0 +1,91 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::ocr::OcrError;
+
+/// Describes the OCR models of a directory, stored as `manifest.json` in the model directory
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ModelManifest {
+    pub models: Vec<ModelManifestEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ModelManifestEntry {
+    /// the name the scanners ask for, e.g. `genshin_artifact`
+    pub name: String,
+    pub version: String,
+    /// ONNX file, relative to the model directory
+    pub model: PathBuf,
+    /// vocabulary file, relative to the model directory, absent for detection models
+    #[serde(default)]
+    pub vocab: Option<PathBuf>,
+    /// the input shape of a single image, without the batch dimension, `null` for the dimensions which vary
+    pub input_shape: Vec<Option<usize>>,
+    /// hex SHA-256 of the ONNX file
+    pub sha256: String,
+}
+
+/// An OCR model read from a model directory, checked against its manifest entry
+pub struct ModelFiles {
+    pub entry: ModelManifestEntry,
+    pub model: Vec<u8>,
+    pub vocab: Option<String>,
+}
+
+impl ModelManifest {
+    pub const FILENAME: &'static str = "manifest.json";
+
+    pub fn from_dir<P: AsRef<Path>>(dir: P) -> Result<Self, OcrError> {
+        let path = dir.as_ref().join(Self::FILENAME);
+        let s = std::fs::read_to_string(&path)
+            .map_err(|e| OcrError::ModelLoad(format!("cannot read model manifest {:?}: {}", path, e)))?;
+        let manifest = serde_json::from_str(&s)
+            .map_err(|e| OcrError::ModelLoad(format!("invalid model manifest {:?}: {}", path, e)))?;
+        Ok(manifest)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ModelManifestEntry> {
+        self.models.iter().find(|m| m.name == name)
+    }
+}
+
+impl ModelManifestEntry {
+    /// Read the model and its vocabulary, the model must match the checksum and `input_shape`
+    pub fn read_files<P: AsRef<Path>>(&self, dir: P, input_shape: &[Option<usize>]) -> Result<ModelFiles, OcrError> {
+        if self.input_shape != input_shape {
+            return Err(OcrError::ModelLoad(format!(
+                "model {} has input shape {:?}, expect {:?}", self.name, self.input_shape, input_shape
+            )));
+        }
+
+        let model_path = dir.as_ref().join(&self.model);
+        let model = std::fs::read(&model_path)
+            .map_err(|e| OcrError::ModelLoad(format!("{:?}: {}", model_path, e)))?;
+
+        let checksum = format!("{:x}", Sha256::digest(&model));
+        if !checksum.eq_ignore_ascii_case(&self.sha256) {
+            return Err(OcrError::ModelLoad(format!(
+                "checksum mismatch of {:?}, expect {}, got {}", model_path, self.sha256, checksum
+            )));
+        }
+
+        let vocab = match self.vocab.as_ref() {
+            Some(vocab) => {
+                let vocab_path = dir.as_ref().join(vocab);
+                let content = std::fs::read_to_string(&vocab_path)
+                    .map_err(|e| OcrError::Vocabulary(format!("{:?}: {}", vocab_path, e)))?;
+                Some(content)
+            },
+            None => None,
+        };
+
+        Ok(ModelFiles {
+            entry: self.clone(),
+            model,
+            vocab,
+        })
+    }
+}
@@ -0,0 +1,28 @@
+use std::fmt::Display;
+
+use thiserror::Error;
+
+/// Errors of the OCR models, the `anyhow::Error` returned by an `ImageToText` can be downcast to this type
+#[derive(Error, Debug)]
+pub enum OcrError {
+    /// The model file is missing or cannot be loaded by the inference backend
+    #[error("cannot load OCR model: {0}")]
+    ModelLoad(String),
+
+    /// The index-to-word file of a model is malformed
+    #[error("invalid OCR vocabulary: {0}")]
+    Vocabulary(String),
+
+    #[error("OCR inference failed: {0}")]
+    Inference(String),
+}
+
+impl OcrError {
+    pub fn model_load<E: Display>(e: E) -> Self {
+        OcrError::ModelLoad(e.to_string())
+    }
+
+    pub fn inference<E: Display>(e: E) -> Self {
+        OcrError::Inference(e.to_string())
+    }
+}
@@ -0,0 +1,113 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use log::{info, warn};
+
+use crate::ocr::{ModelFiles, ModelManifest, OcrBackend, OcrError, PPOCRDetModel, PPOCRModel, YasOCRModel};
+use crate::ocr::paddle_paddle_model::parse_index_to_word;
+
+#[derive(Clone, clap::Args, Default)]
+pub struct OcrModelConfig {
+    /// Load the OCR models from a directory with a `manifest.json`, models missing there are the embedded ones
+    #[arg(id = "model-dir", long = "model-dir", help = "从目录加载 OCR 模型（目录中需有 manifest.json），目录中没有的模型使用内置模型", value_name = "DIR")]
+    pub model_dir: Option<PathBuf>,
+
+    /// The library which runs the OCR models
+    #[arg(id = "ocr-backend", long = "ocr-backend", help = "OCR 推理后端，auto 优先使用 onnxruntime，无法加载时使用 tract", value_name = "BACKEND", value_enum, default_value_t = OcrBackend::Auto)]
+    pub backend: OcrBackend,
+
+    /// How many model instances recognize items in parallel, decided by the CPU count if not given
+    #[arg(id = "ocr-threads", long = "ocr-threads", help = "并行识别的 OCR 线程数，默认根据 CPU 核心数决定", value_name = "THREADS")]
+    pub threads: Option<usize>,
+}
+
+impl OcrModelConfig {
+    /// Each thread loads its own model, so the default is kept small
+    const MAX_DEFAULT_THREADS: usize = 4;
+
+    pub fn thread_count(&self) -> usize {
+        match self.threads {
+            Some(threads) => threads.max(1),
+            None => std::thread::available_parallelism()
+                .map(|n| n.get().min(Self::MAX_DEFAULT_THREADS))
+                .unwrap_or(1),
+        }
+    }
+
+    /// None if there is no model directory, or it has no model `name`
+    fn read_model_files(&self, name: &str, input_shape: &[Option<usize>]) -> Result<Option<ModelFiles>, OcrError> {
+        let dir = match self.model_dir.as_ref() {
+            Some(dir) => dir,
+            None => return Ok(None),
+        };
+
+        let manifest = ModelManifest::from_dir(dir)?;
+        match manifest.get(name) {
+            Some(entry) => entry.read_files(dir, input_shape).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// None if the model should fall back to the embedded one
+    fn read_model_files_or_embedded(&self, name: &str, input_shape: &[Option<usize>]) -> Option<ModelFiles> {
+        match self.read_model_files(name, input_shape) {
+            Ok(Some(files)) => Some(files),
+            Ok(None) => {
+                if self.model_dir.is_some() {
+                    info!("模型目录中没有模型 {}，使用内置模型", name);
+                }
+                None
+            },
+            Err(e) => {
+                warn!("无法从模型目录加载模型 {}，使用内置模型：{}", name, e);
+                None
+            },
+        }
+    }
+
+    /// Build the Yas model `name` from the model directory, `embedded` builds the model compiled into the binary
+    /// with the given backend
+    pub fn build_yas_model<F>(&self, name: &str, embedded: F) -> Result<YasOCRModel> where F: FnOnce(OcrBackend) -> Result<YasOCRModel> {
+        if let Some(files) = self.read_model_files_or_embedded(name, &YasOCRModel::INPUT_SHAPE.map(Some)) {
+            let model = files.vocab.as_deref()
+                .ok_or_else(|| OcrError::Vocabulary(format!("model {} has no vocabulary", name)).into())
+                .and_then(|vocab| YasOCRModel::new_with_backend(&files.model, vocab, self.backend));
+            match model {
+                Ok(model) => {
+                    info!("从模型目录加载模型 {}，版本 {}", name, files.entry.version);
+                    return Ok(model);
+                },
+                Err(e) => warn!("无法从模型目录加载模型 {}，使用内置模型：{}", name, e),
+            }
+        }
+
+        embedded(self.backend)
+    }
+
+    /// Like `build_yas_model`, for PaddleOCR recognition models
+    pub fn build_ppocr_model<F>(&self, name: &str, embedded: F) -> Result<PPOCRModel> where F: FnOnce(OcrBackend) -> Result<PPOCRModel> {
+        if let Some(files) = self.read_model_files_or_embedded(name, &PPOCRModel::INPUT_SHAPE) {
+            let model = files.vocab.as_deref()
+                .ok_or_else(|| OcrError::Vocabulary(format!("model {} has no vocabulary", name)).into())
+                .and_then(|vocab| PPOCRModel::new_with_backend(&files.model, parse_index_to_word(vocab, true), self.backend));
+            match model {
+                Ok(model) => {
+                    info!("从模型目录加载模型 {}，版本 {}", name, files.entry.version);
+                    return Ok(model);
+                },
+                Err(e) => warn!("无法从模型目录加载模型 {}，使用内置模型：{}", name, e),
+            }
+        }
+
+        embedded(self.backend)
+    }
+
+    /// Build the PaddleOCR text detection model `name`, which is not embedded and must be in the model directory
+    pub fn build_ppocr_det_model(&self, name: &str) -> Result<PPOCRDetModel> {
+        let files = self.read_model_files(name, &PPOCRDetModel::INPUT_SHAPE)?
+            .ok_or_else(|| OcrError::ModelLoad(format!("model {} is not in the model directory", name)))?;
+        let model = PPOCRDetModel::new_with_backend(&files.model, self.backend)?;
+        info!("从模型目录加载模型 {}，版本 {}", name, files.entry.version);
+        Ok(model)
+    }
+}
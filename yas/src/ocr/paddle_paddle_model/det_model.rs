@@ -0,0 +1,245 @@
+use std::cell::RefCell;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use image::RgbImage;
+use image::imageops::{FilterType, resize};
+
+use crate::ocr::{ImageTextDetection, OcrError, TextBox};
+use crate::ocr::backend::{InferenceSession, OcrBackend, Tensor};
+use crate::positioning::Rect;
+
+/// PP-OCR DBNet text detector, finds the boxes of text lines in an image
+pub struct PPOCRDetModel {
+    session: Box<dyn InferenceSession>,
+
+    inference_count: RefCell<usize>,
+    inference_time: RefCell<Duration>,
+}
+
+impl PPOCRDetModel {
+    /// Name of the model in a model directory manifest
+    pub const MODEL_NAME: &'static str = "ppocr_ch_v4_det";
+    /// The input shape of a single image, without the batch dimension, the size of the image varies
+    pub const INPUT_SHAPE: [Option<usize>; 3] = [Some(3), None, None];
+
+    /// Images are scaled down so that the longer side is at most this
+    const LIMIT_SIDE_LEN: u32 = 960;
+    /// Pixels above this probability are text
+    const THRESHOLD: f32 = 0.3;
+    /// Boxes below this mean probability are dropped
+    const BOX_THRESHOLD: f32 = 0.6;
+    /// DBNet predicts shrunk text regions, they are expanded by this ratio
+    const UNCLIP_RATIO: f64 = 1.5;
+    const MIN_SIZE: f64 = 3.0;
+
+    // ImageNet normalization, in the BGR order the model is trained with
+    const MEAN: [f32; 3] = [0.485, 0.456, 0.406];
+    const STD: [f32; 3] = [0.229, 0.224, 0.225];
+
+    pub fn new(onnx: &[u8]) -> Result<Self> {
+        Self::new_with_backend(onnx, OcrBackend::default())
+    }
+
+    pub fn new_with_backend(onnx: &[u8], backend: OcrBackend) -> Result<Self> {
+        let session = backend.load_session(onnx, &[None, Some(3), None, None]).map_err(OcrError::model_load)?;
+
+        Ok(Self {
+            session,
+            inference_count: RefCell::new(0),
+            inference_time: RefCell::new(Duration::new(0, 0)),
+        })
+    }
+
+    pub fn get_average_inference_time(&self) -> Option<Duration> {
+        let count = *self.inference_count.borrow();
+        if count == 0 {
+            None
+        } else {
+            Some(self.inference_time.borrow().div_f64(count as f64))
+        }
+    }
+
+    /// Resize both sides to multiples of 32, as the network downsamples 32 times
+    fn resize_image(image: &RgbImage) -> RgbImage {
+        let (width, height) = image.dimensions();
+        let max_side = width.max(height);
+        let ratio = if max_side > Self::LIMIT_SIDE_LEN {
+            Self::LIMIT_SIDE_LEN as f64 / max_side as f64
+        } else {
+            1.0
+        };
+
+        let round_to_32 = |v: u32| (((v as f64 * ratio / 32.0).round() as u32) * 32).max(32);
+        resize(image, round_to_32(width), round_to_32(height), FilterType::Triangle)
+    }
+
+    fn normalize_image(image: &RgbImage) -> Tensor {
+        let height = image.height() as usize;
+        let width = image.width() as usize;
+        Tensor::from_shape_fn(vec![1, 3, height, width], |index| {
+            let c = index[1];
+            let pix = image.get_pixel(index[3] as u32, index[2] as u32)[2 - c];
+            (pix as f32 / 255.0 - Self::MEAN[c]) / Self::STD[c]
+        })
+    }
+
+    /// Turn the probability map of shape (1, 1, height, width) into boxes, in the coordinates of the map
+    fn boxes_from_map(map: &Tensor) -> Vec<TextBox> {
+        let height = map.shape()[2];
+        let width = map.shape()[3];
+        let probability = |x: usize, y: usize| map.get(&[0, 0, y, x]);
+
+        let mut visited = vec![false; width * height];
+        let mut boxes = Vec::new();
+        for y0 in 0..height {
+            for x0 in 0..width {
+                if visited[y0 * width + x0] || probability(x0, y0) <= Self::THRESHOLD {
+                    continue;
+                }
+
+                // the bounding box of an 8-connected text region
+                let (mut left, mut top, mut right, mut bottom) = (x0, y0, x0, y0);
+                let mut stack = vec![(x0, y0)];
+                visited[y0 * width + x0] = true;
+                while let Some((x, y)) = stack.pop() {
+                    left = left.min(x);
+                    right = right.max(x);
+                    top = top.min(y);
+                    bottom = bottom.max(y);
+
+                    for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+                        for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                            if !visited[ny * width + nx] && probability(nx, ny) > Self::THRESHOLD {
+                                visited[ny * width + nx] = true;
+                                stack.push((nx, ny));
+                            }
+                        }
+                    }
+                }
+
+                let box_width = (right - left + 1) as f64;
+                let box_height = (bottom - top + 1) as f64;
+                if box_width.min(box_height) < Self::MIN_SIZE {
+                    continue;
+                }
+
+                let mut sum = 0.0;
+                for y in top..=bottom {
+                    for x in left..=right {
+                        sum += probability(x, y);
+                    }
+                }
+                let score = sum / (box_width * box_height) as f32;
+                if score < Self::BOX_THRESHOLD {
+                    continue;
+                }
+
+                let distance = box_width * box_height * Self::UNCLIP_RATIO / (2.0 * (box_width + box_height));
+                boxes.push(TextBox {
+                    rect: Rect {
+                        left: left as f64 - distance,
+                        top: top as f64 - distance,
+                        width: box_width + 2.0 * distance,
+                        height: box_height + 2.0 * distance,
+                    },
+                    score,
+                });
+            }
+        }
+
+        boxes
+    }
+}
+
+impl ImageTextDetection<RgbImage> for PPOCRDetModel {
+    fn detect_text(&self, image: &RgbImage) -> Result<Vec<TextBox>> {
+        let start_time = SystemTime::now();
+
+        let resized_image = Self::resize_image(image);
+        let map = self.session.run(Self::normalize_image(&resized_image)).map_err(OcrError::inference)?;
+
+        let scale_x = image.width() as f64 / map.shape()[3] as f64;
+        let scale_y = image.height() as f64 / map.shape()[2] as f64;
+        let mut boxes = Self::boxes_from_map(&map)
+            .into_iter()
+            .map(|b| {
+                let left = (b.rect.left * scale_x).max(0.0);
+                let top = (b.rect.top * scale_y).max(0.0);
+                let right = ((b.rect.left + b.rect.width) * scale_x).min(image.width() as f64);
+                let bottom = ((b.rect.top + b.rect.height) * scale_y).min(image.height() as f64);
+                TextBox {
+                    rect: Rect::new(left, top, right - left, bottom - top),
+                    score: b.score,
+                }
+            })
+            .filter(|b| b.rect.width > 0.0 && b.rect.height > 0.0)
+            .collect::<Vec<_>>();
+        boxes.sort_by(|a, b| a.rect.top.total_cmp(&b.rect.top).then(a.rect.left.total_cmp(&b.rect.left)));
+
+        *self.inference_time.borrow_mut() += start_time.elapsed()?;
+        *self.inference_count.borrow_mut() += 1;
+
+        Ok(boxes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A probability map of `background` with the given rects filled
+    fn map(width: usize, height: usize, background: f32, regions: &[(Rect<usize>, f32)]) -> Tensor {
+        Tensor::from_shape_fn(vec![1, 1, height, width], |index| {
+            let (y, x) = (index[2], index[3]);
+            regions.iter()
+                .find(|(r, _)| (r.left..r.left + r.width).contains(&x) && (r.top..r.top + r.height).contains(&y))
+                .map_or(background, |&(_, p)| p)
+        })
+    }
+
+    #[test]
+    fn each_connected_region_is_a_box() {
+        let boxes = PPOCRDetModel::boxes_from_map(&map(40, 20, 0.0, &[
+            (Rect::new(2, 2, 10, 5), 0.9),
+            (Rect::new(20, 3, 8, 6), 0.9),
+        ]));
+        assert_eq!(boxes.len(), 2);
+        // expanded on each side by area * ratio / perimeter = 50 * 1.5 / 30
+        assert_eq!(boxes[0].rect, Rect::new(-0.5, -0.5, 15.0, 10.0));
+        assert!((boxes[0].score - 0.9).abs() < 1e-6);
+        assert!(boxes[1].rect.left < 20.0 && boxes[1].rect.left + boxes[1].rect.width > 28.0);
+    }
+
+    #[test]
+    fn diagonal_neighbours_are_connected() {
+        // the pixels at the threshold are not text, but count into the mean of the box
+        let boxes = PPOCRDetModel::boxes_from_map(&map(12, 12, PPOCRDetModel::THRESHOLD, &[
+            (Rect::new(0, 0, 4, 4), 1.0),
+            (Rect::new(4, 4, 4, 4), 1.0),
+        ]));
+        assert_eq!(boxes.len(), 1);
+        assert_eq!(boxes[0].rect, Rect::new(-3.0, -3.0, 14.0, 14.0));
+        assert!((boxes[0].score - 0.65).abs() < 1e-6);
+    }
+
+    #[test]
+    fn thin_regions_are_dropped() {
+        let boxes = PPOCRDetModel::boxes_from_map(&map(30, 20, 0.0, &[
+            (Rect::new(1, 1, 20, 2), 0.9),
+            (Rect::new(1, 10, 3, 3), 0.9),
+        ]));
+        assert_eq!(boxes.len(), 1);
+        assert_eq!(boxes[0].rect.top, 10.0 - 9.0 * 1.5 / 12.0);
+    }
+
+    #[test]
+    fn uncertain_regions_are_dropped() {
+        let boxes = PPOCRDetModel::boxes_from_map(&map(30, 20, 0.0, &[
+            (Rect::new(1, 1, 10, 5), 0.5),
+            (Rect::new(1, 10, 10, 5), PPOCRDetModel::BOX_THRESHOLD),
+        ]));
+        assert_eq!(boxes.len(), 1);
+        assert_eq!(boxes[0].rect.top, 10.0 - 2.5);
+    }
+}
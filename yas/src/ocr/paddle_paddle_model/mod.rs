@@ -1,6 +1,9 @@
 mod model;
+mod det_model;
 mod preprocess;
 
 pub use model::PPOCRModel;
 pub use model::ppocr_model;
 pub use model::PPOCRChV4RecInfer;
+pub use det_model::PPOCRDetModel;
+pub(crate) use model::parse_index_to_word;
@@ -2,40 +2,30 @@ use std::cell::RefCell;
 use std::path::Path;
 use std::time::{Duration, SystemTime};
 use anyhow::Result;
-use image::{EncodableLayout, RgbImage};
-#[cfg(feature = "tract_onnx")]
-use tract_onnx::tract_hir::shapefactoid;
-use crate::ocr::ImageToText;
-use crate::ocr::paddle_paddle_model::preprocess::resize_img;
+use image::RgbImage;
+use crate::ocr::{DecodeConstraint, ImageToText, OcrError, OcrModelConfig, RecognizedText};
+use crate::ocr::backend::{InferenceSession, OcrBackend};
+use crate::ocr::ctc::{CtcProbabilities, CtcVocabulary};
+use crate::ocr::paddle_paddle_model::preprocess::{normalize_image, resize_img};
 use crate::positioning::Shape3D;
-use crate::utils::read_file_to_string;
-#[cfg(feature = "ort")]
-use ort::GraphOptimizationLevel;
-#[cfg(feature = "tract_onnx")]
-use tract_onnx::prelude::*;
-#[cfg(feature = "tract_onnx")]
-use tract_onnx::tract_hir::infer::InferenceOp;
-
-#[cfg(feature = "tract_onnx")]
-use super::preprocess::normalize_image_to_tensor;
-#[cfg(feature = "ort")]
-use super::preprocess::normalize_image_to_ndarray;
-
-#[cfg(feature = "tract_onnx")]
-type ModelType = RunnableModel<InferenceFact, Box<dyn InferenceOp>, Graph<InferenceFact, Box<dyn InferenceOp>>>;
 
 pub struct PPOCRModel {
-    index_to_word: Vec<String>,
-    #[cfg(feature = "tract_onnx")]
-    model: ModelType,
-    #[cfg(feature = "ort")]
-    model: ort::Session,
+    vocabulary: CtcVocabulary,
+    session: Box<dyn InferenceSession>,
 
     inference_count: RefCell<usize>,
     inference_time: RefCell<Duration>,
 }
 
-fn parse_index_to_word(s: &str, use_whitespace: bool) -> Vec<String> {
+/// Class 0 is the blank, class `j` is `index_to_word[j - 1]`
+fn to_vocabulary(index_to_word: Vec<String>) -> CtcVocabulary {
+    let words = std::iter::once(None)
+        .chain(index_to_word.into_iter().map(Some))
+        .collect();
+    CtcVocabulary::new(words)
+}
+
+pub(crate) fn parse_index_to_word(s: &str, use_whitespace: bool) -> Vec<String> {
     let mut result = Vec::new();
     for line in s.lines() {
         result.push(String::from(line));
@@ -47,58 +37,36 @@ fn parse_index_to_word(s: &str, use_whitespace: bool) -> Vec<String> {
 }
 
 impl PPOCRModel {
-    pub fn new_from_file<P1, P2>(onnx_file: P1, words_file: P2) -> Result<PPOCRModel> where P1: AsRef<Path>, P2: AsRef<Path> {
-        let words_str = std::fs::read_to_string(words_file)?;
-        let index_to_word = parse_index_to_word(&words_str, true);
+    /// The input shape of a single image, without the batch dimension, the width varies
+    pub const INPUT_SHAPE: [Option<usize>; 3] = [Some(3), Some(48), None];
 
-        #[cfg(feature = "ort")]
-        let model = ort::Session::builder()?
-            .with_optimization_level(GraphOptimizationLevel::Level3)?
-            .with_intra_threads(4)?
-            .commit_from_file(onnx_file)?;
+    /// The width of the resized image varies
+    const SESSION_INPUT_SHAPE: [Option<usize>; 4] = [None, Some(3), None, None];
 
-        #[cfg(feature = "tract_onnx")]
-        let model = {
-            let fact = InferenceFact::new().with_datum_type(DatumType::F32)
-                .with_shape(shapefactoid!(_, 3, _, _));
+    pub fn new_from_file<P1, P2>(onnx_file: P1, words_file: P2) -> Result<PPOCRModel> where P1: AsRef<Path>, P2: AsRef<Path> {
+        Self::new_from_file_with_backend(onnx_file, words_file, OcrBackend::default())
+    }
 
-            tract_onnx::onnx()
-                .model_for_path(onnx_file)?
-                .with_input_fact(0, fact)?
-                // .into_optimized()?
-                .into_runnable()?
-        };
+    pub fn new_from_file_with_backend<P1, P2>(onnx_file: P1, words_file: P2, backend: OcrBackend) -> Result<PPOCRModel> where P1: AsRef<Path>, P2: AsRef<Path> {
+        let words_str = std::fs::read_to_string(words_file.as_ref())
+            .map_err(|e| OcrError::Vocabulary(format!("{:?}: {}", words_file.as_ref(), e)))?;
+        let index_to_word = parse_index_to_word(&words_str, true);
 
-        Ok(Self {
-            index_to_word,
-            model,
-            inference_count: RefCell::new(0),
-            inference_time: RefCell::new(Duration::new(0, 0)),
-        })
+        let onnx = std::fs::read(onnx_file.as_ref())
+            .map_err(|e| OcrError::ModelLoad(format!("{:?}: {}", onnx_file.as_ref(), e)))?;
+        Self::new_with_backend(&onnx, index_to_word, backend)
     }
 
     pub fn new(onnx: &[u8], index_to_word: Vec<String>) -> Result<Self> {
-        #[cfg(feature = "ort")]
-        let model = ort::Session::builder()?
-            .with_optimization_level(GraphOptimizationLevel::Level3)?
-            .with_intra_threads(4)?
-            .commit_from_memory(onnx)?;
-
-        #[cfg(feature = "tract_onnx")]
-        let model = {
-            let fact = InferenceFact::new().with_datum_type(DatumType::F32)
-                .with_shape(shapefactoid!(_, 3, _, _));
-
-            tract_onnx::onnx()
-                .model_for_read(&mut onnx.as_bytes())?
-                .with_input_fact(0, fact)?
-                // .into_optimized()?
-                .into_runnable()?
-        };
+        Self::new_with_backend(onnx, index_to_word, OcrBackend::default())
+    }
+
+    pub fn new_with_backend(onnx: &[u8], index_to_word: Vec<String>, backend: OcrBackend) -> Result<Self> {
+        let session = backend.load_session(onnx, &Self::SESSION_INPUT_SHAPE).map_err(OcrError::model_load)?;
 
         Ok(Self {
-            index_to_word,
-            model,
+            vocabulary: to_vocabulary(index_to_word),
+            session,
             inference_count: RefCell::new(0),
             inference_time: RefCell::new(Duration::new(0, 0)),
         })
@@ -113,72 +81,38 @@ impl PPOCRModel {
             Some(duration.div_f64(count as f64))
         }
     }
-}
 
-impl ImageToText<RgbImage> for PPOCRModel {
-    fn image_to_text(&self, image: &RgbImage, _is_preprocessed: bool) -> Result<String> {
+    fn recognize(&self, image: &RgbImage, constraint: Option<&DecodeConstraint>) -> Result<RecognizedText> {
         let start_time = SystemTime::now();
 
         let resized_image = resize_img(Shape3D::new(3, 48, 320), image);
 
-        #[cfg(feature = "ort")]
-        let tensor = normalize_image_to_ndarray(&resized_image);
-        #[cfg(feature = "tract_onnx")]
-        let tensor = normalize_image_to_tensor(&resized_image);
-
-        #[cfg(feature = "ort")]
-        let result = self.model.run(ort::inputs![tensor]?)?;
-        #[cfg(feature = "tract_onnx")]
-        let result = self.model.run(tvec!(tensor.into()))?;
-
-        #[cfg(feature = "ort")]
-        let arr = result[0].try_extract_tensor()?;
-        #[cfg(feature = "tract_onnx")]
-        let arr = result[0].to_array_view::<f32>()?;
-
-        let shape = arr.shape();
-        // println!("{:?}", shape);
-
-        let mut text_index = Vec::new();
-
-        for i in 0..shape[1] {
-            let mut max_index = 0;
-            let mut max_value = -f32::INFINITY;
-            for j in 0..shape[2] {
-                let value = arr[[0, i, j]];
-                // println!("{}", value);
-                if value > max_value {
-                    max_value = value;
-                    max_index = j;
-                }
-            }
-            text_index.push(max_index);
-        }
-
-        let mut indices = Vec::new();
-        if text_index[0] != 0 {
-            indices.push(text_index[0]);
-        }
-        for i in 1..text_index.len() {
-            if text_index[i] != text_index[i - 1] && text_index[i] != 0 {
-                indices.push(text_index[i]);
-            }
-        }
-
-        let mut s = String::new();
-        for &index in indices.iter() {
-            s.push_str(&self.index_to_word[index - 1]);
-        }
-
-        // println!("{:?}", text_index);
+        let output = self.session.run(normalize_image(&resized_image))?;
 
-        // let s = format!("{:?}", shape);
+        // the output is of shape (batch, time step, word), where word 0 is the blank
+        let shape = output.shape();
+        let probabilities = CtcProbabilities::from_scores(shape[1], shape[2], |i, j| output.get(&[0, i, j]));
+        let result = probabilities.decode(&self.vocabulary, constraint);
 
         let elapsed_time = start_time.elapsed()?;
         *self.inference_time.borrow_mut() += elapsed_time;
         *self.inference_count.borrow_mut() += 1;
 
-        Ok(s)
+        Ok(result)
+    }
+}
+
+impl ImageToText<RgbImage> for PPOCRModel {
+    fn image_to_text(&self, image: &RgbImage, is_preprocessed: bool) -> Result<String> {
+        Ok(self.image_to_text_with_confidence(image, is_preprocessed)?.text)
+    }
+
+    fn image_to_text_with_confidence(&self, image: &RgbImage, is_preprocessed: bool) -> Result<RecognizedText> {
+        self.image_to_text_constrained(image, is_preprocessed, None)
+    }
+
+    fn image_to_text_constrained(&self, image: &RgbImage, _is_preprocessed: bool, constraint: Option<&DecodeConstraint>) -> Result<RecognizedText> {
+        Ok(self.recognize(image, constraint).map_err(OcrError::inference)?)
     }
 
     fn get_average_inference_time(&self) -> Option<Duration> {
@@ -186,20 +120,25 @@ impl ImageToText<RgbImage> for PPOCRModel {
     }
 }
 
-pub macro ppocr_model($onnx:literal, $index_to_word:literal) {
-    {
-        let model_bytes = include_bytes!($onnx);
-        let index_to_word_str = include_str!($index_to_word);
+pub macro ppocr_model {
+    ($onnx:literal, $index_to_word:literal) => {
+        ppocr_model!($onnx, $index_to_word, OcrBackend::default())
+    },
+    ($onnx:literal, $index_to_word:literal, $backend:expr) => {
+        {
+            let model_bytes = include_bytes!($onnx);
+            let index_to_word_str = include_str!($index_to_word);
+
+            let mut index_to_word_vec: Vec<String> = Vec::new();
+            for line in index_to_word_str.lines() {
+                index_to_word_vec.push(String::from(line));
+            }
+            index_to_word_vec.push(String::from(" "));
 
-        let mut index_to_word_vec: Vec<String> = Vec::new();
-        for line in index_to_word_str.lines() {
-            index_to_word_vec.push(String::from(line));
+            PPOCRModel::new_with_backend(
+                model_bytes, index_to_word_vec, $backend,
+            )
         }
-        index_to_word_vec.push(String::from(" "));
-
-        PPOCRModel::new(
-            model_bytes, index_to_word_vec,
-        )
     }
 }
 
@@ -208,11 +147,25 @@ pub struct PPOCRChV4RecInfer {
 }
 
 impl PPOCRChV4RecInfer {
+    /// Name of the model in a model directory manifest
+    pub const MODEL_NAME: &'static str = "ppocr_ch_v4_rec";
+
     pub fn new() -> Result<Self> {
         Ok(Self {
-            model: ppocr_model!("./ch_PP-OCRv4_rec_infer.onnx", "./ppocr_keys_v1.txt")?
+            model: Self::embedded_model(OcrBackend::default())?
+        })
+    }
+
+    /// Prefer the model of the model directory, if there is one
+    pub fn from_config(config: &OcrModelConfig) -> Result<Self> {
+        Ok(Self {
+            model: config.build_ppocr_model(Self::MODEL_NAME, Self::embedded_model)?
         })
     }
+
+    fn embedded_model(backend: OcrBackend) -> Result<PPOCRModel> {
+        ppocr_model!("./ch_PP-OCRv4_rec_infer.onnx", "./ppocr_keys_v1.txt", backend)
+    }
 }
 
 impl ImageToText<RgbImage> for PPOCRChV4RecInfer {
@@ -220,6 +173,14 @@ impl ImageToText<RgbImage> for PPOCRChV4RecInfer {
         self.model.image_to_text(image, is_preprocessed)
     }
 
+    fn image_to_text_with_confidence(&self, image: &RgbImage, is_preprocessed: bool) -> Result<RecognizedText> {
+        self.model.image_to_text_with_confidence(image, is_preprocessed)
+    }
+
+    fn image_to_text_constrained(&self, image: &RgbImage, is_preprocessed: bool, constraint: Option<&DecodeConstraint>) -> Result<RecognizedText> {
+        self.model.image_to_text_constrained(image, is_preprocessed, constraint)
+    }
+
     fn get_average_inference_time(&self) -> Option<Duration> {
         self.model.get_average_inference_time()
     }
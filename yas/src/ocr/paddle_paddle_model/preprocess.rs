@@ -2,8 +2,7 @@ use image::{ImageBuffer, RgbImage};
 use crate::positioning::Shape3D;
 use anyhow::Result;
 use image::imageops::{FilterType, resize};
-#[cfg(feature = "tract_onnx")]
-use tract_onnx::prelude::{Tensor, tract_ndarray};
+use crate::ocr::backend::Tensor;
 
 /// Resize an image to the expected height, but the width can vary
 /// rec_image_shape: the expected shape to feed into the onnx model. CHW
@@ -20,33 +19,13 @@ pub fn resize_img(rec_image_shape: Shape3D<u32>, img: &RgbImage) -> RgbImage {
     resized_image
 }
 
-#[cfg(feature = "ort")]
-pub fn normalize_image_to_ndarray(img: &RgbImage) -> ndarray::Array4<f32> {
+/// Normalize the pixels to [-1, 1], in a tensor of shape (1, 3, height, width)
+pub fn normalize_image(img: &RgbImage) -> Tensor {
     let height = img.height() as usize;
     let width = img.width() as usize;
-    // let tensor: Tensor = tract_ndarray::Array4::from_shape_fn((1, 3, height, width), |(_, c, y, x)| {
-    //     let pix = img.get_pixel(x as u32, y as u32)[c];
-    //     let v = pix as f32 / 255.0_f32;
-    //     (v - 0.5) / 0.5
-    // }).into();
-    // tensor
-
-    let arr = ndarray::Array4::from_shape_fn((1, 3, height, width), |(_, c, y, x)| {
-        let pix = img.get_pixel(x as u32, y as u32)[c];
+    Tensor::from_shape_fn(vec![1, 3, height, width], |index| {
+        let pix = img.get_pixel(index[3] as u32, index[2] as u32)[index[1]];
         let v = pix as f32 / 255.0_f32;
         (v - 0.5) / 0.5
-    });
-    arr
+    })
 }
-
-#[cfg(feature = "tract_onnx")]
-pub fn normalize_image_to_tensor(img: &RgbImage) -> Tensor {
-    let height = img.height() as usize;
-    let width = img.width() as usize;
-    let tensor: Tensor = tract_ndarray::Array4::from_shape_fn((1, 3, height, width), |(_, c, y, x)| {
-        let pix = img.get_pixel(x as u32, y as u32)[c];
-        let v = pix as f32 / 255.0_f32;
-        (v - 0.5) / 0.5
-    }).into();
-    tensor
-}
\ No newline at end of file
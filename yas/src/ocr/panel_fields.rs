@@ -0,0 +1,73 @@
+use anyhow::{anyhow, Result};
+use image::{GenericImageView, RgbImage};
+use log::warn;
+
+use crate::ocr::{DecodeConstraint, ImageToText};
+use crate::positioning::{Pos, Rect};
+
+/// A stat value, such as "4,780" or "46.6%"
+pub const STAT_VALUE_PATTERN: &str = r"\d[\d,]*(?:\.\d+)?%?";
+/// The level of an item, such as "+20"
+pub const LEVEL_PATTERN: &str = r"^\+\d+$";
+
+impl DecodeConstraint {
+    /// A region holding a stat value only
+    pub fn stat_value() -> Result<Self> {
+        Self::regex(&format!("^{}$", STAT_VALUE_PATTERN))
+    }
+
+    /// A region holding the level of an item
+    pub fn level() -> Result<Self> {
+        Self::regex(LEVEL_PATTERN)
+    }
+}
+
+/// Crop a region of an image captured at `origin`, both the rect and the origin are relative to the game window
+pub fn crop_image(image: &RgbImage, origin: Pos<f64>, rect: Rect<f64>) -> RgbImage {
+    let relative_rect = rect.translate(Pos { x: -origin.x, y: -origin.y });
+
+    image.view(
+        relative_rect.left as u32, relative_rect.top as u32, relative_rect.width as u32, relative_rect.height as u32,
+    ).to_image()
+}
+
+/// Recognize many regions of an image captured at `origin` in a single batched inference,
+/// each region is decoded under its own constraint. Low confidence results are reported
+pub fn recognize_fields(
+    model: &dyn ImageToText<RgbImage>,
+    image: &RgbImage,
+    origin: Pos<f64>,
+    fields: &[(Rect<f64>, Option<&DecodeConstraint>)],
+) -> Result<Vec<String>> {
+    let images = fields.iter()
+        .map(|&(rect, _)| crop_image(image, origin, rect))
+        .collect::<Vec<_>>();
+    let constraints = fields.iter()
+        .map(|&(_, constraint)| constraint)
+        .collect::<Vec<_>>();
+    let results = model.image_to_text_batch_constrained(&images, false, &constraints)?;
+    if results.len() != fields.len() {
+        return Err(anyhow!("expect {} texts, got {}", fields.len(), results.len()));
+    }
+
+    let mut texts = Vec::with_capacity(fields.len());
+    for result in results {
+        if result.is_low_confidence() {
+            warn!("识别置信度较低（{:.2}）：{}", result.confidence, result.text);
+        }
+        texts.push(result.text);
+    }
+
+    Ok(texts)
+}
+
+/// Same as `recognize_fields`, for a fixed number of fields
+pub fn recognize_field_array<const N: usize>(
+    model: &dyn ImageToText<RgbImage>,
+    image: &RgbImage,
+    origin: Pos<f64>,
+    fields: [(Rect<f64>, Option<&DecodeConstraint>); N],
+) -> Result<[String; N]> {
+    let texts = recognize_fields(model, image, origin, &fields)?;
+    texts.try_into().map_err(|v: Vec<String>| anyhow!("expect {} texts, got {}", N, v.len()))
+}
@@ -0,0 +1,96 @@
+use crate::positioning::Rect;
+
+/// A region of text found by a detection model
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextBox {
+    /// in the coordinates of the detected image
+    pub rect: Rect<f64>,
+    /// mean text probability in the box
+    pub score: f32,
+}
+
+impl TextBox {
+    fn center_y(&self) -> f64 {
+        self.rect.top + self.rect.height / 2.0
+    }
+
+    fn union(&self, other: &TextBox) -> TextBox {
+        let left = self.rect.left.min(other.rect.left);
+        let top = self.rect.top.min(other.rect.top);
+        let right = (self.rect.left + self.rect.width).max(other.rect.left + other.rect.width);
+        let bottom = (self.rect.top + self.rect.height).max(other.rect.top + other.rect.height);
+        TextBox {
+            rect: Rect::new(left, top, right - left, bottom - top),
+            score: self.score.min(other.score),
+        }
+    }
+
+    /// Merge the boxes of the same row, such as the name and the value of a stat.
+    /// The lines are sorted from top to bottom
+    pub fn merge_into_lines(mut boxes: Vec<TextBox>) -> Vec<TextBox> {
+        boxes.sort_by(|a, b| a.center_y().total_cmp(&b.center_y()));
+
+        let mut lines: Vec<TextBox> = Vec::new();
+        for b in boxes {
+            match lines.last_mut() {
+                // the centers of a row are closer than half of the height
+                Some(line) if (b.center_y() - line.center_y()).abs() < line.rect.height.min(b.rect.height) / 2.0 => {
+                    *line = line.union(&b);
+                },
+                _ => lines.push(b),
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_box(left: f64, top: f64, width: f64, height: f64, score: f32) -> TextBox {
+        TextBox { rect: Rect::new(left, top, width, height), score }
+    }
+
+    #[test]
+    fn boxes_of_a_row_are_merged() {
+        let lines = TextBox::merge_into_lines(vec![
+            text_box(200.0, 102.0, 40.0, 20.0, 0.8),
+            text_box(10.0, 100.0, 50.0, 20.0, 0.9),
+        ]);
+        assert_eq!(lines, vec![text_box(10.0, 100.0, 230.0, 22.0, 0.8)]);
+    }
+
+    #[test]
+    fn lines_are_sorted_from_top_to_bottom() {
+        let lines = TextBox::merge_into_lines(vec![
+            text_box(0.0, 200.0, 50.0, 20.0, 0.9),
+            text_box(0.0, 100.0, 50.0, 20.0, 0.8),
+            text_box(80.0, 150.0, 50.0, 20.0, 0.7),
+        ]);
+        let tops = lines.iter().map(|l| l.rect.top).collect::<Vec<_>>();
+        assert_eq!(tops, vec![100.0, 150.0, 200.0]);
+    }
+
+    #[test]
+    fn rows_closer_than_half_a_line_are_merged() {
+        // the centers are 10 and 19, half of the height is 10
+        let lines = TextBox::merge_into_lines(vec![
+            text_box(0.0, 0.0, 10.0, 20.0, 0.9),
+            text_box(0.0, 9.0, 10.0, 20.0, 0.9),
+        ]);
+        assert_eq!(lines.len(), 1);
+
+        // the centers are 10 and 22
+        let lines = TextBox::merge_into_lines(vec![
+            text_box(0.0, 0.0, 10.0, 20.0, 0.9),
+            text_box(0.0, 12.0, 10.0, 20.0, 0.9),
+        ]);
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn no_boxes_no_lines() {
+        assert_eq!(TextBox::merge_into_lines(Vec::new()), Vec::new());
+    }
+}
@@ -2,12 +2,54 @@ use std::time::Duration;
 
 use anyhow::Result;
 
+use crate::ocr::{DecodeConstraint, RecognizedText, TextBox};
+
 pub trait ImageToText<ImageType> {
     fn image_to_text(&self, image: &ImageType, is_preprocessed: bool) -> Result<String>;
 
+    /// Recognize many images at once, models that support batched inference run them in a single pass
+    fn image_to_text_batch(&self, images: &[ImageType], is_preprocessed: bool) -> Result<Vec<String>> {
+        images.iter()
+            .map(|image| self.image_to_text(image, is_preprocessed))
+            .collect()
+    }
+
+    /// Like `image_to_text`, with the confidence of each character.
+    /// Models which cannot estimate the confidence report 1.0
+    fn image_to_text_with_confidence(&self, image: &ImageType, is_preprocessed: bool) -> Result<RecognizedText> {
+        Ok(RecognizedText::without_confidence(self.image_to_text(image, is_preprocessed)?))
+    }
+
+    fn image_to_text_batch_with_confidence(&self, images: &[ImageType], is_preprocessed: bool) -> Result<Vec<RecognizedText>> {
+        images.iter()
+            .map(|image| self.image_to_text_with_confidence(image, is_preprocessed))
+            .collect()
+    }
+
+    /// Like `image_to_text_with_confidence`, the text is decoded to satisfy the constraint when possible.
+    /// Models which cannot constrain the decoding report 0 confidence for a text violating it
+    fn image_to_text_constrained(&self, image: &ImageType, is_preprocessed: bool, constraint: Option<&DecodeConstraint>) -> Result<RecognizedText> {
+        let result = self.image_to_text_with_confidence(image, is_preprocessed)?;
+        Ok(match constraint {
+            Some(c) => c.check(result),
+            None => result,
+        })
+    }
+
+    /// `constraints[i]` applies to `images[i]`
+    fn image_to_text_batch_constrained(&self, images: &[ImageType], is_preprocessed: bool, constraints: &[Option<&DecodeConstraint>]) -> Result<Vec<RecognizedText>> {
+        assert_eq!(images.len(), constraints.len());
+
+        images.iter().zip(constraints.iter())
+            .map(|(image, &constraint)| self.image_to_text_constrained(image, is_preprocessed, constraint))
+            .collect()
+    }
+
     fn get_average_inference_time(&self) -> Option<Duration>;
 }
 
-// pub trait ImageTextDetection<ImageType> {
-//
-// }
+/// Find where the text is, so that it can be cropped and recognized
+pub trait ImageTextDetection<ImageType> {
+    /// The boxes are sorted from top to bottom, then from left to right
+    fn detect_text(&self, image: &ImageType) -> Result<Vec<TextBox>>;
+}
@@ -1,180 +1,297 @@
-use image::{ImageBuffer, Luma, RgbImage, GenericImageView};
-use image::imageops;
+use std::ops::Range;
 
-/// convert rgb image to f32 gray image
-pub fn to_gray(raw: &RgbImage) -> ImageBuffer<Luma<f32>, Vec<f32>> {
-    let mut new_gray: ImageBuffer<Luma<f32>, Vec<f32>> = ImageBuffer::new(raw.width(), raw.height());
-    for x in 0..raw.width() {
-        for y in 0..raw.height() {
-            let rgb = raw.get_pixel(x, y);
-
-            let r = rgb[0] as f32 / 255.0;
-            let g = rgb[1] as f32 / 255.0;
-            let b = rgb[2] as f32 / 255.0;
-
-            let gray = r * 0.2989 + g * 0.5870 + b * 0.1140;
-            let grayp = new_gray.get_pixel_mut(x, y);
-            grayp[0] = gray;
+use image::{ImageBuffer, Luma, RgbImage};
+use once_cell::sync::Lazy;
+
+type GrayF32Image = ImageBuffer<Luma<f32>, Vec<f32>>;
+
+const OUTPUT_WIDTH: u32 = 384;
+const OUTPUT_HEIGHT: u32 = 32;
+
+/// Preprocesses images for the Yas model, the intermediate buffers are kept between images
+/// so that scanning many items does not allocate them again and again
+#[derive(Default)]
+pub struct Preprocessor {
+    gray: Vec<f32>,
+    cropped: Vec<f32>,
+    // the cropped image with its columns resampled, and then its rows
+    resampled: Vec<f32>,
+    transposed: Vec<f32>,
+    row_weights: ResampleWeights,
+    col_weights: ResampleWeights,
+}
+
+impl Preprocessor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Preprocess an rgb image, see `pre_process`
+    pub fn preprocess_rgb(&mut self, raw: &RgbImage) -> (GrayF32Image, bool) {
+        gray_into(raw, &mut self.gray);
+        self.preprocess_buffer(raw.width(), raw.height())
+    }
+
+    /// Preprocess an f32 gray image, see `pre_process`
+    pub fn preprocess_gray(&mut self, im: &GrayF32Image) -> (GrayF32Image, bool) {
+        self.gray.clear();
+        self.gray.extend_from_slice(im.as_raw());
+        self.preprocess_buffer(im.width(), im.height())
+    }
+
+    /// Preprocess the image in `self.gray`
+    fn preprocess_buffer(&mut self, width: u32, height: u32) -> (GrayF32Image, bool) {
+        if !normalize(&mut self.gray, width, height, true) {
+            let im = ImageBuffer::from_raw(width, height, self.gray.clone()).unwrap();
+            return (im, false);
         }
+
+        let (width, height) = crop_into(&self.gray, width, height, &mut self.cropped);
+        normalize(&mut self.cropped, width, height, false);
+
+        (self.resize_pad_and_threshold(width, height), true)
     }
-    new_gray
 }
 
-/// normalize an f32 gray image
-/// which makes the bright pixel brighter, the dark pixels darker
-fn normalize(im: &mut ImageBuffer<Luma<f32>, Vec<f32>>, auto_inverse: bool) -> bool {
-    let width = im.width();
-    let height = im.height();
+/// convert rgb image to f32 gray image
+pub fn to_gray(raw: &RgbImage) -> GrayF32Image {
+    let mut data = Vec::new();
+    gray_into(raw, &mut data);
+    ImageBuffer::from_raw(raw.width(), raw.height(), data).unwrap()
+}
+
+/// The weighted channels of every subpixel value, `WEIGHTED_CHANNELS[c][v]` is `v / 255 * weight of c`
+static WEIGHTED_CHANNELS: Lazy<[[f32; 256]; 3]> = Lazy::new(|| {
+    let weights = [0.2989_f32, 0.5870, 0.1140];
+    weights.map(|weight| std::array::from_fn(|v| v as f32 / 255.0 * weight))
+});
+
+fn gray_into(raw: &RgbImage, out: &mut Vec<f32>) {
+    let [r, g, b] = &*WEIGHTED_CHANNELS;
+    out.clear();
+    out.extend(raw.as_raw().chunks_exact(3).map(|rgb| {
+        r[rgb[0] as usize] + g[rgb[1] as usize] + b[rgb[2] as usize]
+    }));
+}
 
+/// normalize an f32 gray image, stored row by row
+/// which makes the bright pixel brighter, the dark pixels darker
+fn normalize(data: &mut [f32], width: u32, height: u32, auto_inverse: bool) -> bool {
     if width == 0 || height == 0 {
         println!("wrong width or height");
         return false;
     }
 
-    let mut max: f32 = 0.0;
-    let mut min: f32 = 256.0;
-
-    for i in 0..width {
-        for j in 0..height {
-            let p = im.get_pixel(i, j)[0];
-            if p > max {
-                max = p;
-            }
-            if p < min {
-                min = p;
-            }
-        }
-    }
-
+    let (min, max) = min_max(data);
     if max == min {
         return false;
     }
 
+    let width = width as usize;
+    let last_row = (height as usize - 1) * width;
     let flag_pixel = if width >= 2 {
-        im.get_pixel(width - 2, height - 1)[0]
+        data[last_row + width - 2]
     } else {
-        im.get_pixel(width - 1, height - 1)[0]
+        data[last_row + width - 1]
     };
-    let flag_pixel = (flag_pixel - min) / (max - min);
-
-    for i in 0..width {
-        for j in 0..height {
-            let p = im.get_pixel_mut(i, j);
-            let pv = p[0];
-            let mut new_pv = (pv - min) / (max - min);
-            if auto_inverse && flag_pixel > 0.5 {
-                new_pv = 1.0 - new_pv;
-            }
-            p[0] = new_pv;
-        }
+    let range = max - min;
+    let flag_pixel = (flag_pixel - min) / range;
+
+    // separate loops without branches, so that they are vectorized
+    if auto_inverse && flag_pixel > 0.5 {
+        data.iter_mut().for_each(|p| *p = 1.0 - (*p - min) / range);
+    } else {
+        data.iter_mut().for_each(|p| *p = (*p - min) / range);
     }
 
     true
 }
 
-/// crop an f32 gray image to only where there is text
-fn crop(im: &ImageBuffer<Luma<f32>, Vec<f32>>) -> ImageBuffer<Luma<f32>, Vec<f32>> {
-    let width = im.width();
-    let height = im.height();
+/// The minimum and maximum pixel, starting from 256 and 0
+fn min_max(data: &[f32]) -> (f32, f32) {
+    // independent lanes let the comparisons run in parallel
+    const LANES: usize = 8;
+    let mut min = [256.0_f32; LANES];
+    let mut max = [0.0_f32; LANES];
 
-    let mut min_col = width - 1;
-    let mut max_col = 0;
-    let mut min_row = height - 1;
-    let mut max_row = 0_u32;
-
-    for i in 0..width {
-        for j in 0..height {
-            let p = im.get_pixel(i, j)[0];
-            if p > 0.7 {
-                if i < min_col {
-                    min_col = i;
-                }
-                if i > max_col {
-                    max_col = i;
-                }
-                break;
-            }
+    let chunks = data.chunks_exact(LANES);
+    let remainder = chunks.remainder();
+    for chunk in chunks {
+        for i in 0..LANES {
+            min[i] = if chunk[i] < min[i] { chunk[i] } else { min[i] };
+            max[i] = if chunk[i] > max[i] { chunk[i] } else { max[i] };
         }
     }
+    for (i, &p) in remainder.iter().enumerate() {
+        min[i] = if p < min[i] { p } else { min[i] };
+        max[i] = if p > max[i] { p } else { max[i] };
+    }
 
-    for j in 0..height {
-        for i in 0..width {
-            let p = im.get_pixel(i, j)[0];
-            if p > 0.7 {
-                if j < min_row {
-                    min_row = j;
-                }
-                if j > max_row {
-                    max_row = j;
-                }
-                break;
-            }
-        }
+    let min = min.into_iter().fold(256.0, |a, b| if b < a { b } else { a });
+    let max = max.into_iter().fold(0.0, |a, b| if b > a { b } else { a });
+    (min, max)
+}
+
+/// crop an f32 gray image to only where there is text, the result is written to `out`
+/// and its size is returned
+fn crop_into(data: &[f32], width: u32, height: u32, out: &mut Vec<f32>) -> (u32, u32) {
+    let row_len = width as usize;
+
+    let mut min_col = width as usize - 1;
+    let mut max_col = 0;
+    let mut min_row = height as usize - 1;
+    let mut max_row = 0;
+
+    for (j, row) in data.chunks_exact(row_len).enumerate() {
+        let first = match row.iter().position(|&p| p > 0.7) {
+            Some(v) => v,
+            None => continue,
+        };
+        // a bright pixel exists, so the search from the end finds one too
+        let last = row.iter().rposition(|&p| p > 0.7).unwrap();
+
+        min_col = min_col.min(first);
+        max_col = max_col.max(last);
+        min_row = min_row.min(j);
+        max_row = max_row.max(j);
     }
 
+    out.clear();
     if min_col > max_col || min_row > max_row {
-        return im.clone();
+        out.extend_from_slice(data);
+        return (width, height);
     }
 
-    let new_height = max_row - min_row + 1;
-    let new_width = max_col - min_col + 1;
-
-    // let _ans: Vec<f32> = vec![0.0; (new_width * new_height) as usize];
-    let cropped_im = im.view(min_col, min_row, new_width, new_height).to_image();
+    for row in data.chunks_exact(row_len).skip(min_row).take(max_row - min_row + 1) {
+        out.extend_from_slice(&row[min_col..=max_col]);
+    }
+    ((max_col - min_col + 1) as u32, (max_row - min_row + 1) as u32)
+}
 
-    cropped_im
+/// The taps of a triangle filter resampling a line of pixels, computed the same way as
+/// `imageops::resize` with `FilterType::Triangle` so that the output is the same bit for bit
+#[derive(Default)]
+struct ResampleWeights {
+    // the first source pixel and the range in `weights` of each output pixel
+    taps: Vec<(usize, Range<usize>)>,
+    weights: Vec<f32>,
 }
 
-/// resize an f32 gray image to 384 * 32, if not wide enough, then pad with background
-fn resize_and_pad(im: &ImageBuffer<Luma<f32>, Vec<f32>>) -> ImageBuffer<Luma<f32>, Vec<f32>> {
-    let w = im.width();
-    let h = im.height();
+impl ResampleWeights {
+    fn compute(&mut self, src_len: u32, dst_len: u32) {
+        self.taps.clear();
+        self.weights.clear();
 
-    let new_width = if w as f64 / (h as f64) > 384.0 / 32.0 {
-        384
-    } else {
-        std::cmp::min((32.0 / h as f64 * w as f64) as u32, 384)
-    };
+        let ratio = src_len as f32 / dst_len as f32;
+        let sratio = if ratio < 1.0 { 1.0 } else { ratio };
+        // the support of the triangle kernel is 1
+        let src_support = sratio;
+
+        for out in 0..dst_len {
+            let input = (out as f32 + 0.5) * ratio;
+
+            let left = ((input - src_support).floor() as i64).clamp(0, src_len as i64 - 1);
+            let right = ((input + src_support).ceil() as i64).clamp(left + 1, src_len as i64);
 
-    let new_height = std::cmp::min((384.0 / w as f64 * h as f64) as u32, 32);
+            // the kernel treats the centre of a pixel as 0
+            let input = input - 0.5;
+
+            let start = self.weights.len();
+            let mut sum = 0.0;
+            for i in left..right {
+                let x = ((i as f32 - input) / sratio).abs();
+                let w = if x < 1.0 { 1.0 - x } else { 0.0 };
+                self.weights.push(w);
+                sum += w;
+            }
+            self.weights[start..].iter_mut().for_each(|w| *w /= sum);
 
-    let img = imageops::resize(
-        im,
-        new_width,
-        new_height,
-        image::imageops::FilterType::Triangle,
-    );
+            self.taps.push((left as usize, start..self.weights.len()));
+        }
+    }
 
-    let data: Vec<f32> = vec![0.0; 32 * 384];
-    let mut padded_im = ImageBuffer::from_vec(384, 32, data).unwrap();
-    imageops::overlay(&mut padded_im, &img, 0, 0);
-    padded_im
+    fn iter(&self) -> impl Iterator<Item = (usize, &[f32])> {
+        self.taps.iter().map(|(left, range)| (*left, &self.weights[range.clone()]))
+    }
 }
 
-/// transform an f32 gray image to a preprocessed image
-/// if the image has only one color, then return false, but this is not an error
-pub fn pre_process(im: ImageBuffer<Luma<f32>, Vec<f32>>) -> (ImageBuffer<Luma<f32>, Vec<f32>>, bool) {
-    let mut im = im;
-    if !normalize(&mut im, true) {
-        return (im, false);
+/// Each line of `dst` is the weighted sum of the lines of `src`, which have `line_len` pixels.
+/// The sums are added up in the order of the taps
+fn resample_lines(src: &[f32], line_len: usize, weights: &ResampleWeights, dst: &mut Vec<f32>) {
+    dst.clear();
+    dst.resize(line_len * weights.taps.len(), 0.0);
+    for (dst_line, (first, line_weights)) in dst.chunks_exact_mut(line_len).zip(weights.iter()) {
+        for (i, &weight) in line_weights.iter().enumerate() {
+            let src_line = &src[(first + i) * line_len..(first + i + 1) * line_len];
+            for (d, &s) in dst_line.iter_mut().zip(src_line) {
+                *d += s * weight;
+            }
+        }
+    }
+}
+
+/// Transpose an image with rows of `width` pixels
+fn transpose(src: &[f32], width: usize, dst: &mut Vec<f32>) {
+    let height = src.len() / width;
+    dst.clear();
+    dst.resize(src.len(), 0.0);
+    for (y, row) in src.chunks_exact(width).enumerate() {
+        for (x, &p) in row.iter().enumerate() {
+            dst[x * height + y] = p;
+        }
     }
-    let mut im = crop(&im);
+}
+
+impl Preprocessor {
+    /// resize the image in `self.cropped` to fit 384 * 32, if not wide enough, then pad with background.
+    /// Then the pixels are turned to 0 or 1
+    fn resize_pad_and_threshold(&mut self, w: u32, h: u32) -> GrayF32Image {
+        let new_width = if w as f64 / (h as f64) > OUTPUT_WIDTH as f64 / OUTPUT_HEIGHT as f64 {
+            OUTPUT_WIDTH
+        } else {
+            std::cmp::min((OUTPUT_HEIGHT as f64 / h as f64 * w as f64) as u32, OUTPUT_WIDTH)
+        };
 
-    normalize(&mut im, false);
+        let new_height = std::cmp::min((OUTPUT_WIDTH as f64 / w as f64 * h as f64) as u32, OUTPUT_HEIGHT);
 
-    let mut im = resize_and_pad(&im);
+        let threshold = |p: f32| if p < 0.53 { 0.0 } else { 1.0 };
+        let mut padded = vec![0.0; (OUTPUT_WIDTH * OUTPUT_HEIGHT) as usize];
 
-    for i in 0..im.width() {
-        for j in 0..im.height() {
-            let p = im.get_pixel_mut(i, j);
-            let pv = p[0];
-            if pv < 0.53 {
-                p[0] = 0.0;
-            } else {
-                p[0] = 1.0;
+        if (new_width, new_height) == (w, h) {
+            // resizing to the same size is a copy
+            for (dst, src) in padded.chunks_exact_mut(OUTPUT_WIDTH as usize).zip(self.cropped.chunks_exact(w as usize)) {
+                for (d, &s) in dst.iter_mut().zip(src) {
+                    *d = threshold(s);
+                }
             }
+            return ImageBuffer::from_raw(OUTPUT_WIDTH, OUTPUT_HEIGHT, padded).unwrap();
         }
+
+        if new_width == 0 || new_height == 0 {
+            return ImageBuffer::from_raw(OUTPUT_WIDTH, OUTPUT_HEIGHT, padded).unwrap();
+        }
+
+        // sample the columns, then the rows, as `imageops::resize` does.
+        // The rows are sampled as the columns of the transposed image, over contiguous memory
+        self.row_weights.compute(h, new_height);
+        resample_lines(&self.cropped, w as usize, &self.row_weights, &mut self.resampled);
+        transpose(&self.resampled, w as usize, &mut self.transposed);
+        self.col_weights.compute(w, new_width);
+        resample_lines(&self.transposed, new_height as usize, &self.col_weights, &mut self.resampled);
+
+        for (x, column) in self.resampled.chunks_exact(new_height as usize).enumerate() {
+            // resizing clamps the pixels to the range 0 to 1, which is left to the threshold
+            for (y, &t) in column.iter().enumerate() {
+                padded[y * OUTPUT_WIDTH as usize + x] = threshold(t);
+            }
+        }
+
+        ImageBuffer::from_raw(OUTPUT_WIDTH, OUTPUT_HEIGHT, padded).unwrap()
     }
+}
 
-    (im, true)
+/// transform an f32 gray image to a preprocessed image
+/// if the image has only one color, then return false, but this is not an error
+pub fn pre_process(im: GrayF32Image) -> (GrayF32Image, bool) {
+    Preprocessor::new().preprocess_gray(&im)
 }
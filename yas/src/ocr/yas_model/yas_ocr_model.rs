@@ -1,29 +1,32 @@
-use std::{cell::RefCell, time::Duration};
+use std::{cell::{Cell, RefCell}, time::Duration};
 use std::time::SystemTime;
-use image::{EncodableLayout, GrayImage, ImageBuffer, Luma, RgbImage};
-// use tract_onnx::prelude::*;
+use image::{GrayImage, ImageBuffer, Luma, RgbImage};
+use crate::ocr::{DecodeConstraint, OcrError, RecognizedText};
+use crate::ocr::backend::{InferenceSession, OcrBackend, Tensor};
+use crate::ocr::ctc::{CtcProbabilities, CtcVocabulary};
 use crate::ocr::traits::ImageToText;
-use super::preprocess;
+use super::preprocess::Preprocessor;
 use anyhow::Result;
+use log::warn;
 use crate::common::image_ext::*;
-#[cfg(feature = "tract_onnx")]
-use tract_onnx::prelude::*;
-
-#[cfg(feature = "tract_onnx")]
-type ModelType = RunnableModel<TypedFact, Box<dyn TypedOp>, Graph<TypedFact, Box<dyn TypedOp>>>;
 
 pub struct YasOCRModel {
-    #[cfg(feature = "ort")]
-    model: ort::Session,
-    #[cfg(feature = "tract_onnx")]
-    model: ModelType,
-    index_to_word: Vec<String>,
+    session: Box<dyn InferenceSession>,
+    vocabulary: CtcVocabulary,
+    preprocessor: RefCell<Preprocessor>,
 
     inference_time: RefCell<Duration>,   // in seconds
     invoke_count: RefCell<usize>,
+    // set when the model is exported with a fixed batch size of 1
+    is_batch_unsupported: Cell<bool>,
 }
 
+type PreprocessedImage = ImageBuffer<Luma<f32>, Vec<f32>>;
+
 impl YasOCRModel {
+    /// The input shape of a single image, without the batch dimension
+    pub const INPUT_SHAPE: [usize; 3] = [1, 32, 384];
+
     pub fn get_average_inference_time(&self) -> Option<Duration> {
         let count = *self.invoke_count.borrow();
         let total_time = *self.inference_time.borrow();
@@ -35,108 +38,180 @@ impl YasOCRModel {
         }
     }
 
-    pub fn new(model: &[u8], content: &str) -> Result<YasOCRModel> {
-        #[cfg(feature = "ort")]
-        let model = ort::Session::builder()?
-            .with_optimization_level(ort::GraphOptimizationLevel::Level3)?
-            .with_intra_threads(4)?
-            .commit_from_memory(model)?;
-        #[cfg(feature = "tract_onnx")]
-        let model = tract_onnx::onnx()
-            .model_for_read(&mut model.as_bytes())?
-            .with_input_fact(0, f32::fact([1, 1, 32, 384]).into())?
-            .into_optimized()?
-            .into_runnable()?;
-
-        let json = serde_json::from_str::<serde_json::Value>(content)?;
-
-        let mut index_to_word = json
-            .as_object()
-            .unwrap()
-            .iter()
-            .map(|(k, v)| (k.parse::<usize>().unwrap(), v.as_str().unwrap().to_string()))
-            .collect::<Vec<(usize, String)>>();
+    fn parse_index_to_word(content: &str) -> Result<Vec<String>, OcrError> {
+        let json = serde_json::from_str::<serde_json::Value>(content)
+            .map_err(|e| OcrError::Vocabulary(e.to_string()))?;
+        let object = json.as_object()
+            .ok_or(OcrError::Vocabulary(String::from("expect a JSON object")))?;
+
+        let mut index_to_word = Vec::new();
+        for (k, v) in object.iter() {
+            let index = k.parse::<usize>()
+                .map_err(|_| OcrError::Vocabulary(format!("invalid index \"{}\"", k)))?;
+            let word = v.as_str()
+                .ok_or(OcrError::Vocabulary(format!("word of index {} is not a string", index)))?;
+            index_to_word.push((index, word.to_string()));
+        }
 
         index_to_word.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
 
-        let index_to_word = index_to_word.into_iter().map(|(_, v)| v).collect();
+        Ok(index_to_word.into_iter().map(|(_, v)| v).collect())
+    }
+
+    pub fn new(model: &[u8], content: &str) -> Result<YasOCRModel> {
+        Self::new_with_backend(model, content, OcrBackend::default())
+    }
+
+    pub fn new_with_backend(model: &[u8], content: &str, backend: OcrBackend) -> Result<YasOCRModel> {
+        // the batch size varies, so that a batch of any size can run through the same plan
+        let input_shape = [None, Some(Self::INPUT_SHAPE[0]), Some(Self::INPUT_SHAPE[1]), Some(Self::INPUT_SHAPE[2])];
+        let session = backend.load_session(model, &input_shape).map_err(OcrError::model_load)?;
+        let index_to_word = Self::parse_index_to_word(content)?;
+        let vocabulary = CtcVocabulary::new(
+            index_to_word.into_iter()
+                .map(|word| if word == "-" { None } else { Some(word) })
+                .collect()
+        );
 
         Ok(YasOCRModel {
-            model,
-            index_to_word,
+            session,
+            vocabulary,
+            preprocessor: RefCell::new(Preprocessor::new()),
             inference_time: RefCell::new(Duration::new(0, 0)),
             invoke_count: RefCell::new(0),
+            is_batch_unsupported: Cell::new(false),
         })
     }
 
-    pub fn inference_string(&self, img: &ImageBuffer<Luma<f32>, Vec<f32>>) -> Result<String> {
-        let now = SystemTime::now();
+    pub fn inference_string(&self, img: &PreprocessedImage) -> Result<String> {
+        Ok(self.inference(img)?.text)
+    }
 
-        #[cfg(feature = "ort")]
-        let tensor = ndarray::Array4::from_shape_fn((1, 1, 32, 384), |(_, _, y, x)| {
-            img.get_pixel(x as u32, y as u32)[0]
-        });
-        #[cfg(feature = "tract_onnx")]
-        let tensor: Tensor =
-            tract_ndarray::Array4::from_shape_fn((1, 1, 32, 384), |(_, _, y, x)| {
-                img.get_pixel(x as u32, y as u32)[0]
-            }).into();
-
-        #[cfg(feature = "ort")]
-        let result = self.model.run(ort::inputs![tensor]?)?;
-        #[cfg(feature = "tract_onnx")]
-        let result = self.model.run(tvec!(tensor.into()))?;
-
-        #[cfg(feature = "ort")]
-        let arr = result[0].try_extract_tensor()?;
-        #[cfg(feature = "tract_onnx")]
-        let arr = result[0].to_array_view::<f32>()?;
-
-        let shape = arr.shape();
-
-        let mut ans = String::new();
-        let mut last_word = String::new();
-        for i in 0..shape[0] {
-            let mut max_index = 0;
-            let mut max_value = -1.0_f32;
-            for j in 0..self.index_to_word.len() {
-                let value = arr[[i, 0, j]];
-                if value > max_value {
-                    max_value = value;
-                    max_index = j;
-                }
-            }
-            let word = &self.index_to_word[max_index];
-            if *word != last_word && word != "-" {
-                ans = ans + word;
+    pub fn inference(&self, img: &PreprocessedImage) -> Result<RecognizedText> {
+        let mut result = self.inference_batch(&[img])?;
+        Ok(result.remove(0))
+    }
+
+    pub fn inference_string_batch(&self, images: &[&PreprocessedImage]) -> Result<Vec<String>> {
+        let result = self.inference_batch(images)?;
+        Ok(result.into_iter().map(|r| r.text).collect())
+    }
+
+    /// Run all the preprocessed images as a single `Nx1x32x384` tensor
+    pub fn inference_batch(&self, images: &[&PreprocessedImage]) -> Result<Vec<RecognizedText>> {
+        self.inference_batch_constrained(images, &vec![None; images.len()])
+    }
+
+    /// Like `inference_batch`, `constraints[i]` restricts the text of `images[i]`
+    pub fn inference_batch_constrained(&self, images: &[&PreprocessedImage], constraints: &[Option<&DecodeConstraint>]) -> Result<Vec<RecognizedText>> {
+        assert_eq!(images.len(), constraints.len());
+        if images.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut batch_error = None;
+        if images.len() > 1 && !self.is_batch_unsupported.get() {
+            match self.run_inference(images, constraints) {
+                Ok(result) => return Ok(result),
+                Err(e) => batch_error = Some(e),
             }
+        }
+
+        let mut result = Vec::with_capacity(images.len());
+        for (&image, &constraint) in images.iter().zip(constraints.iter()) {
+            let mut text = self.run_inference(&[image], &[constraint]).map_err(OcrError::inference)?;
+            result.push(text.remove(0));
+        }
 
-            last_word.clone_from(word);
+        // the images run one by one but not as a batch, so the model does not take a batch.
+        // If a single run fails too, the error is returned above and the next batch is tried again
+        if let Some(e) = batch_error {
+            warn!("批量推理失败，之后改为逐个推理：{}", e);
+            self.is_batch_unsupported.set(true);
         }
+        Ok(result)
+    }
+
+    fn run_inference(&self, images: &[&PreprocessedImage], constraints: &[Option<&DecodeConstraint>]) -> Result<Vec<RecognizedText>> {
+        let now = SystemTime::now();
+        let batch_size = images.len();
+
+        let tensor = Tensor::from_shape_fn(vec![batch_size, 1, 32, 384], |index| {
+            images[index[0]].get_pixel(index[3] as u32, index[2] as u32)[0]
+        });
+        let output = self.session.run(tensor)?;
+
+        // the output is of shape (time step, batch, word)
+        let steps = output.shape()[0];
+        let classes = self.vocabulary.len();
+
+        let result = (0..batch_size)
+            .map(|n| {
+                let probabilities = CtcProbabilities::from_scores(steps, classes, |i, j| output.get(&[i, n, j]));
+                probabilities.decode(&self.vocabulary, constraints[n])
+            })
+            .collect();
 
         let time = now.elapsed()?;
-        
-        *self.invoke_count.borrow_mut() += 1;
+
+        *self.invoke_count.borrow_mut() += batch_size;
         *self.inference_time.borrow_mut() += time;
 
-        Ok(ans)
+        Ok(result)
+    }
+
+    /// Recognize the non-blank ones of the preprocessed images in a single batch
+    fn recognize_preprocessed(&self, images: Vec<(PreprocessedImage, bool)>, constraints: &[Option<&DecodeConstraint>]) -> Result<Vec<RecognizedText>> {
+        let (batch, batch_constraints): (Vec<_>, Vec<_>) = images.iter()
+            .zip(constraints.iter())
+            .filter(|((_, non_mono), _)| *non_mono)
+            .map(|((im, _), &constraint)| (im, constraint))
+            .unzip();
+        let mut texts = self.inference_batch_constrained(&batch, &batch_constraints)?.into_iter();
+
+        let result = images.iter()
+            .map(|(_, non_mono)| if *non_mono {
+                texts.next().unwrap()
+            } else {
+                RecognizedText::empty()
+            })
+            .collect();
+        Ok(result)
     }
 }
 
 impl ImageToText<RgbImage> for YasOCRModel {
     fn image_to_text(&self, image: &RgbImage, is_preprocessed: bool) -> Result<String> {
-        assert!(!is_preprocessed);
+        Ok(self.image_to_text_with_confidence(image, is_preprocessed)?.text)
+    }
 
-        let gray_image_float = preprocess::to_gray(image);
-        let (result, non_mono) = preprocess::pre_process(gray_image_float);
+    fn image_to_text_batch(&self, images: &[RgbImage], is_preprocessed: bool) -> Result<Vec<String>> {
+        let result = self.image_to_text_batch_with_confidence(images, is_preprocessed)?;
+        Ok(result.into_iter().map(|r| r.text).collect())
+    }
 
-        if !non_mono {
-            return Ok(String::new());
-        }
+    fn image_to_text_with_confidence(&self, image: &RgbImage, is_preprocessed: bool) -> Result<RecognizedText> {
+        let mut result = self.image_to_text_batch_with_confidence(std::slice::from_ref(image), is_preprocessed)?;
+        Ok(result.remove(0))
+    }
+
+    fn image_to_text_batch_with_confidence(&self, images: &[RgbImage], is_preprocessed: bool) -> Result<Vec<RecognizedText>> {
+        self.image_to_text_batch_constrained(images, is_preprocessed, &vec![None; images.len()])
+    }
+
+    fn image_to_text_constrained(&self, image: &RgbImage, is_preprocessed: bool, constraint: Option<&DecodeConstraint>) -> Result<RecognizedText> {
+        let mut result = self.image_to_text_batch_constrained(std::slice::from_ref(image), is_preprocessed, &[constraint])?;
+        Ok(result.remove(0))
+    }
 
-        let string_result = self.inference_string(&result)?;
+    fn image_to_text_batch_constrained(&self, images: &[RgbImage], is_preprocessed: bool, constraints: &[Option<&DecodeConstraint>]) -> Result<Vec<RecognizedText>> {
+        assert!(!is_preprocessed);
 
-        Ok(string_result)
+        let mut preprocessor = self.preprocessor.borrow_mut();
+        let preprocessed = images.iter()
+            .map(|im| preprocessor.preprocess_rgb(im))
+            .collect();
+        self.recognize_preprocessed(preprocessed, constraints)
     }
 
     fn get_average_inference_time(&self) -> Option<Duration> {
@@ -146,19 +221,37 @@ impl ImageToText<RgbImage> for YasOCRModel {
 
 impl ImageToText<ImageBuffer<Luma<f32>, Vec<f32>>> for YasOCRModel {
     fn image_to_text(&self, image: &ImageBuffer<Luma<f32>, Vec<f32>>, is_preprocessed: bool) -> Result<String> {
-        if is_preprocessed {
-            let string_result = self.inference_string(image)?;
-            Ok(string_result)
-        } else {
-            let im = image.clone();
-            let (preprocess_result, non_mono) = preprocess::pre_process(im);
+        Ok(self.image_to_text_with_confidence(image, is_preprocessed)?.text)
+    }
 
-            if !non_mono {
-                return Ok(String::new());
-            }
+    fn image_to_text_batch(&self, images: &[ImageBuffer<Luma<f32>, Vec<f32>>], is_preprocessed: bool) -> Result<Vec<String>> {
+        let result = self.image_to_text_batch_with_confidence(images, is_preprocessed)?;
+        Ok(result.into_iter().map(|r| r.text).collect())
+    }
+
+    fn image_to_text_with_confidence(&self, image: &ImageBuffer<Luma<f32>, Vec<f32>>, is_preprocessed: bool) -> Result<RecognizedText> {
+        let mut result = self.image_to_text_batch_with_confidence(std::slice::from_ref(image), is_preprocessed)?;
+        Ok(result.remove(0))
+    }
+
+    fn image_to_text_batch_with_confidence(&self, images: &[ImageBuffer<Luma<f32>, Vec<f32>>], is_preprocessed: bool) -> Result<Vec<RecognizedText>> {
+        self.image_to_text_batch_constrained(images, is_preprocessed, &vec![None; images.len()])
+    }
 
-            let string_result = self.inference_string(&preprocess_result)?;
-            Ok(string_result)
+    fn image_to_text_constrained(&self, image: &ImageBuffer<Luma<f32>, Vec<f32>>, is_preprocessed: bool, constraint: Option<&DecodeConstraint>) -> Result<RecognizedText> {
+        let mut result = self.image_to_text_batch_constrained(std::slice::from_ref(image), is_preprocessed, &[constraint])?;
+        Ok(result.remove(0))
+    }
+
+    fn image_to_text_batch_constrained(&self, images: &[ImageBuffer<Luma<f32>, Vec<f32>>], is_preprocessed: bool, constraints: &[Option<&DecodeConstraint>]) -> Result<Vec<RecognizedText>> {
+        if is_preprocessed {
+            self.inference_batch_constrained(&images.iter().collect::<Vec<_>>(), constraints)
+        } else {
+            let mut preprocessor = self.preprocessor.borrow_mut();
+            let preprocessed = images.iter()
+                .map(|im| preprocessor.preprocess_gray(im))
+                .collect();
+            self.recognize_preprocessed(preprocessed, constraints)
         }
     }
 
@@ -173,18 +266,113 @@ impl ImageToText<GrayImage> for YasOCRModel {
         self.image_to_text(&gray_f32_image, is_preprocessed)
     }
 
+    fn image_to_text_with_confidence(&self, im: &GrayImage, is_preprocessed: bool) -> Result<RecognizedText> {
+        let gray_f32_image: ImageBuffer<Luma<f32>, Vec<f32>> = im.to_f32_gray_image();
+        self.image_to_text_with_confidence(&gray_f32_image, is_preprocessed)
+    }
+
+    fn image_to_text_constrained(&self, im: &GrayImage, is_preprocessed: bool, constraint: Option<&DecodeConstraint>) -> Result<RecognizedText> {
+        let gray_f32_image: ImageBuffer<Luma<f32>, Vec<f32>> = im.to_f32_gray_image();
+        self.image_to_text_constrained(&gray_f32_image, is_preprocessed, constraint)
+    }
+
     fn get_average_inference_time(&self) -> Option<Duration> {
         self.get_average_inference_time()
     }
 }
 
-pub macro yas_ocr_model($model_name:literal, $index_to_word:literal) {
-    {
-        let model_bytes = include_bytes!($model_name);
-        let index_to_word = include_str!($index_to_word);
+pub macro yas_ocr_model {
+    ($model_name:literal, $index_to_word:literal) => {
+        yas_ocr_model!($model_name, $index_to_word, OcrBackend::default())
+    },
+    ($model_name:literal, $index_to_word:literal, $backend:expr) => {
+        {
+            let model_bytes = include_bytes!($model_name);
+            let index_to_word = include_str!($index_to_word);
+
+            YasOCRModel::new_with_backend(
+                model_bytes, index_to_word, $backend,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// Fails the first `batch_failures` batches and the first `single_failures` single runs
+    struct FlakySession {
+        batch_failures: usize,
+        single_failures: usize,
+        batch_runs: Arc<AtomicUsize>,
+        single_runs: Arc<AtomicUsize>,
+    }
+
+    impl InferenceSession for FlakySession {
+        fn run(&self, input: Tensor) -> Result<Tensor> {
+            let batch_size = input.shape()[0];
+            let (runs, failures) = if batch_size > 1 {
+                (&self.batch_runs, self.batch_failures)
+            } else {
+                (&self.single_runs, self.single_failures)
+            };
+            if runs.fetch_add(1, Ordering::SeqCst) < failures {
+                return Err(anyhow::anyhow!("inference failed"));
+            }
+            // one time step of blanks
+            Ok(Tensor::new(vec![1, batch_size, 2], [1.0, 0.0].repeat(batch_size))?)
+        }
+
+        fn backend(&self) -> OcrBackend {
+            OcrBackend::Tract
+        }
+    }
+
+    fn model(batch_failures: usize, single_failures: usize) -> (YasOCRModel, Arc<AtomicUsize>) {
+        let batch_runs = Arc::new(AtomicUsize::new(0));
+        let session = FlakySession {
+            batch_failures,
+            single_failures,
+            batch_runs: batch_runs.clone(),
+            single_runs: Arc::new(AtomicUsize::new(0)),
+        };
+        let model = YasOCRModel {
+            session: Box::new(session),
+            vocabulary: CtcVocabulary::new(vec![None, Some(String::from("a"))]),
+            preprocessor: RefCell::new(Preprocessor::new()),
+            inference_time: RefCell::new(Duration::new(0, 0)),
+            invoke_count: RefCell::new(0),
+            is_batch_unsupported: Cell::new(false),
+        };
+        (model, batch_runs)
+    }
+
+    #[test]
+    fn a_failing_inference_does_not_turn_batching_off() {
+        let (model, batch_runs) = model(1, 1);
+        let image = PreprocessedImage::new(384, 32);
+
+        assert!(model.inference_batch(&[&image, &image]).is_err());
+        assert!(!model.is_batch_unsupported.get());
+
+        assert_eq!(model.inference_batch(&[&image, &image]).unwrap().len(), 2);
+        assert_eq!(batch_runs.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn a_model_without_batches_runs_one_by_one() {
+        let (model, batch_runs) = model(usize::MAX, 0);
+        let image = PreprocessedImage::new(384, 32);
+
+        assert_eq!(model.inference_batch(&[&image, &image]).unwrap().len(), 2);
+        assert!(model.is_batch_unsupported.get());
 
-        YasOCRModel::new(
-            model_bytes, index_to_word,
-        )
+        // no batch is tried again
+        assert_eq!(model.inference_batch(&[&image, &image]).unwrap().len(), 2);
+        assert_eq!(batch_runs.load(Ordering::SeqCst), 1);
     }
 }
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+
+use edit_distance::edit_distance;
+
+/// The closest known word to a recognized text
+#[derive(Debug, Clone, Copy)]
+pub struct FuzzyMatch<'a, T> {
+    pub word: &'a str,
+    pub value: &'a T,
+    /// The edit distance between the word and the text, counted in chars
+    pub distance: usize,
+    /// Whether a word with a different value is as close to the text
+    pub ambiguous: bool,
+}
+
+/// Matches recognized texts against a vocabulary, tolerating a few wrong characters.
+/// Several words may map to the same value, e.g. variant spellings of a name
+pub struct FuzzyMatcher<T> {
+    words: Vec<(String, T)>,
+    exact: HashMap<String, usize>,
+}
+
+impl<T: PartialEq> FuzzyMatcher<T> {
+    pub fn new<S: Into<String>>(vocabulary: impl IntoIterator<Item = (S, T)>) -> Self {
+        let words: Vec<(String, T)> = vocabulary.into_iter()
+            .map(|(word, value)| (word.into(), value))
+            .collect();
+        let mut exact = HashMap::new();
+        for (index, (word, _)) in words.iter().enumerate() {
+            exact.entry(word.clone()).or_insert(index);
+        }
+
+        Self { words, exact }
+    }
+
+    /// The word closest to `text`, None if the vocabulary is empty
+    pub fn best_match(&self, text: &str) -> Option<FuzzyMatch<'_, T>> {
+        if let Some(&index) = self.exact.get(text) {
+            let (word, value) = &self.words[index];
+            return Some(FuzzyMatch { word, value, distance: 0, ambiguous: false });
+        }
+
+        let mut best: Option<FuzzyMatch<'_, T>> = None;
+        for (word, value) in self.words.iter() {
+            let distance = edit_distance(text, word);
+            match best.as_mut() {
+                Some(b) if distance > b.distance => {},
+                Some(b) if distance == b.distance => {
+                    b.ambiguous |= *b.value != *value;
+                },
+                _ => {
+                    best = Some(FuzzyMatch { word, value, distance, ambiguous: false });
+                },
+            }
+        }
+
+        best
+    }
+
+    /// The value of the word closest to `text`, if it is at most `max_distance` away,
+    /// no other value is as close, and less than half of the word is changed
+    pub fn find(&self, text: &str, max_distance: usize) -> Option<&T> {
+        if let Some(&index) = self.exact.get(text) {
+            return Some(&self.words[index].1);
+        }
+        if max_distance == 0 {
+            return None;
+        }
+
+        let m = self.best_match(text)?;
+        if m.distance > max_distance || m.ambiguous || m.distance * 2 >= m.word.chars().count() {
+            return None;
+        }
+        Some(m.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stat_matcher() -> FuzzyMatcher<u32> {
+        FuzzyMatcher::new([("暴击率", 1), ("暴击伤害", 2), ("元素精通", 3)])
+    }
+
+    #[test]
+    fn exact_hit() {
+        let matcher = stat_matcher();
+        assert_eq!(matcher.find("暴击伤害", 0), Some(&2));
+
+        let m = matcher.best_match("元素精通").unwrap();
+        assert_eq!((m.word, m.distance, m.ambiguous), ("元素精通", 0, false));
+    }
+
+    #[test]
+    fn one_char_typo() {
+        let matcher = stat_matcher();
+        assert_eq!(matcher.find("暴击伤宝", 1), Some(&2));
+        assert_eq!(matcher.find("元索精通", 1), Some(&3));
+        // only exact hits without a distance
+        assert_eq!(matcher.find("暴击伤宝", 0), None);
+    }
+
+    #[test]
+    fn ambiguous_words_are_rejected() {
+        let matcher = FuzzyMatcher::new([("攻击力", 1), ("防御力", 2), ("攻御力", 3)]);
+        // "攻御力" is exact, but "攻防力" is one edit away from all three
+        assert_eq!(matcher.find("攻御力", 1), Some(&3));
+        assert!(matcher.best_match("攻防力").unwrap().ambiguous);
+        assert_eq!(matcher.find("攻防力", 1), None);
+
+        // variant spellings of the same value are not ambiguous
+        let matcher = FuzzyMatcher::new([("星罗圭壁之晷", 1), ("星罗圭璧之晷", 1)]);
+        assert_eq!(matcher.find("星罗圭碧之晷", 1), Some(&1));
+    }
+
+    #[test]
+    fn half_changed_words_are_rejected() {
+        let matcher = stat_matcher();
+        // one edit in the three chars of "暴击率" is accepted, 1 * 2 < 3
+        assert_eq!(matcher.find("暴击卒", 3), Some(&1));
+        // two edits are not, 2 * 2 >= 3, however large max_distance is
+        assert_eq!(matcher.find("暴去卒", 3), None);
+
+        let matcher = FuzzyMatcher::new([("ab", 1)]);
+        assert_eq!(matcher.best_match("ax").unwrap().distance, 1);
+        assert_eq!(matcher.find("ax", 5), None);
+    }
+}
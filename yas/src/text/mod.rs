@@ -0,0 +1,5 @@
+pub use fuzzy_matcher::{FuzzyMatch, FuzzyMatcher};
+pub use text_match_config::TextMatchConfig;
+
+mod fuzzy_matcher;
+mod text_match_config;
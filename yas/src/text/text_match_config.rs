@@ -0,0 +1,6 @@
+#[derive(Clone, clap::Args, Default)]
+pub struct TextMatchConfig {
+    /// Recognized names within this edit distance of a known name are corrected to it, 0 accepts exact names only
+    #[arg(id = "match-tolerance", long = "match-tolerance", help = "名称与已知名称的编辑距离不超过该值时自动纠正，0 表示只接受完全一致的名称", value_name = "DISTANCE", default_value_t = 0)]
+    pub max_distance: usize,
+}
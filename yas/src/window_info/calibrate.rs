@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use log::{info, warn};
+
+use crate::common::color::Color;
+use crate::draw_capture_region::draw_capture_region::DrawCaptureRegion;
+use crate::game_info::{Platform, UI};
+use crate::positioning::{Pos, Rect, Size};
+use crate::window_info::{LayoutRule, WindowInfoRepository, WindowInfoTemplatePerSize, WindowInfoType};
+
+#[derive(Clone, clap::Args)]
+pub struct CalibrateConfig {
+    /// A screenshot of the game window at the new size, showing the repository page
+    #[arg(id = "screenshot", long = "screenshot", help = "新分辨率下游戏窗口（仅客户区）的仓库页面截图", value_name = "FILE")]
+    pub screenshot: PathBuf,
+
+    #[arg(id = "calibrate-output", long = "output", short = 'o', help = "生成的窗口布局文件，可以通过 --window-info 加载", value_name = "FILE", default_value = "window_info.json")]
+    pub output: PathBuf,
+
+    /// Draw the layout onto the screenshot, to check it by eye
+    #[arg(id = "preview", long = "preview", help = "将推算出的布局绘制在截图上并保存到该文件，用于检查", value_name = "FILE")]
+    pub preview: Option<PathBuf>,
+
+    #[arg(id = "interactive", long = "interactive", help = "逐项确认或修改推算出的坐标")]
+    pub interactive: bool,
+
+    #[arg(id = "calibrate-ui", long = "ui", help = "截图的界面类型", value_enum, default_value_t = UI::Desktop)]
+    pub ui: UI,
+
+    #[arg(id = "calibrate-platform", long = "platform", help = "截图的平台", value_enum, default_value_t = Platform::current())]
+    pub platform: Platform,
+}
+
+/// Estimates the window info of a new window size from a screenshot, by scaling the nearest known layout.
+/// The values can be corrected one by one in the interactive mode
+pub struct Calibrator<'a> {
+    repo: &'a WindowInfoRepository,
+    config: CalibrateConfig,
+}
+
+impl<'a> Calibrator<'a> {
+    pub fn new(repo: &'a WindowInfoRepository, config: CalibrateConfig) -> Self {
+        Self { repo, config }
+    }
+
+    pub fn run(&self) -> Result<WindowInfoTemplatePerSize> {
+        let screenshot = image::open(&self.config.screenshot)
+            .map_err(|e| anyhow!("无法读取截图 {:?}: {}", self.config.screenshot, e))?
+            .to_rgb8();
+        let window_size = Size::new(screenshot.width() as usize, screenshot.height() as usize);
+        info!("截图尺寸：{}x{}", window_size.width, window_size.height);
+
+        let mut data = self.estimate(window_size);
+        if data.is_empty() {
+            return Err(anyhow!("没有 {:?} {:?} 的已知布局可以推算", self.config.ui, self.config.platform));
+        }
+
+        if self.config.interactive {
+            self.correct_interactively(&mut data)?;
+        }
+
+        if let Some(path) = self.config.preview.as_ref() {
+            let mut preview = screenshot.clone();
+            for value in data.values() {
+                match *value {
+                    WindowInfoType::Rect(rect) => rect.draw_capture_region(&mut preview),
+                    WindowInfoType::Pos(pos) => pos.draw_capture_region(&mut preview),
+                    WindowInfoType::PosList(ref list) => list.iter().for_each(|pos| pos.draw_capture_region(&mut preview)),
+                    _ => {},
+                }
+            }
+            preview.save(path)?;
+            info!("预览图已保存到 {:?}", path);
+        }
+
+        let template = WindowInfoTemplatePerSize {
+            current_resolution: window_size,
+            platform: self.config.platform,
+            ui: self.config.ui,
+            data,
+            layout: HashMap::new(),
+        };
+        // through a json value, so that the keys are sorted
+        let json = serde_json::to_string_pretty(&serde_json::to_value(&template)?)?;
+        std::fs::write(&self.config.output, json)?;
+        info!("窗口布局已保存到 {:?}", self.config.output);
+
+        Ok(template)
+    }
+
+    /// Every key known for the ui and platform, projected onto the window size
+    fn estimate(&self, window_size: Size<usize>) -> HashMap<String, WindowInfoType> {
+        let mut data = HashMap::new();
+        for (key, entries) in self.repo.data.iter() {
+            if !entries.keys().any(|k| k.1 == self.config.ui && k.2 == self.config.platform) {
+                continue;
+            }
+            // a key without a layout rule is projected with the default one, the estimate is corrected below anyway
+            let value = self.repo.get_auto_scale_with_layout::<WindowInfoType>(
+                key, window_size, self.config.ui, self.config.platform, LayoutRule::default()
+            );
+            match value {
+                Some(value) => {
+                    data.insert(key.clone(), value);
+                },
+                None => warn!("无法推算 {}", key),
+            }
+        }
+        data
+    }
+
+    fn correct_interactively(&self, data: &mut HashMap<String, WindowInfoType>) -> Result<()> {
+        info!("依次输入各项的新值，直接回车保留推算值");
+        let mut keys: Vec<String> = data.keys().cloned().collect();
+        keys.sort();
+
+        let stdin = std::io::stdin();
+        let mut lines = stdin.lock().lines();
+        for key in keys.iter() {
+            let value = data[key].clone();
+            // the colors of a palette are not corrected one by one
+            if let WindowInfoType::ColorPalette(_) = value {
+                continue;
+            }
+            loop {
+                print!("{} {} = {}\n> ", key, format_hint(&value), format_value(&value));
+                std::io::stdout().flush()?;
+
+                let line = match lines.next() {
+                    Some(line) => line?,
+                    None => return Ok(()),
+                };
+                let line = line.trim();
+                if line.is_empty() {
+                    break;
+                }
+                match parse_value(&value, line) {
+                    Some(v) => {
+                        data.insert(key.clone(), v);
+                        break;
+                    },
+                    None => println!("格式错误，应为 {}", format_hint(&value)),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn format_hint(value: &WindowInfoType) -> &'static str {
+    match *value {
+        WindowInfoType::Rect(_) => "(left,top,width,height)",
+        WindowInfoType::Pos(_) => "(x,y)",
+        WindowInfoType::Size(_) => "(width,height)",
+        WindowInfoType::Float(_) | WindowInfoType::InvariantFloat(_) => "(数值)",
+        WindowInfoType::InvariantInt(_) => "(整数)",
+        WindowInfoType::Color(_) => "(r,g,b)",
+        WindowInfoType::PosList(_) => "(x1,y1,x2,y2,...)",
+        WindowInfoType::ColorPalette(_) => "(颜色表)",
+    }
+}
+
+fn format_value(value: &WindowInfoType) -> String {
+    match value {
+        WindowInfoType::Rect(r) => format!("{:.1},{:.1},{:.1},{:.1}", r.left, r.top, r.width, r.height),
+        WindowInfoType::Pos(p) => format!("{:.1},{:.1}", p.x, p.y),
+        WindowInfoType::Size(s) => format!("{:.1},{:.1}", s.width, s.height),
+        WindowInfoType::Float(v) | WindowInfoType::InvariantFloat(v) => format!("{:.2}", v),
+        WindowInfoType::InvariantInt(v) => v.to_string(),
+        WindowInfoType::Color(c) => format!("{},{},{}", c.0, c.1, c.2),
+        WindowInfoType::PosList(list) => list.iter()
+            .map(|p| format!("{:.1},{:.1}", p.x, p.y))
+            .collect::<Vec<_>>()
+            .join(","),
+        WindowInfoType::ColorPalette(palette) => format!("{:?}", palette.0),
+    }
+}
+
+/// Parse a value of the same type as `value`, e.g. "1090,100,410,700" for a rect
+fn parse_value(value: &WindowInfoType, s: &str) -> Option<WindowInfoType> {
+    let numbers = s.split(',')
+        .map(|x| x.trim().parse::<f64>())
+        .collect::<Result<Vec<f64>, _>>()
+        .ok()?;
+
+    let result = match (value, numbers.as_slice()) {
+        (WindowInfoType::Rect(_), &[left, top, width, height]) => WindowInfoType::Rect(Rect::new(left, top, width, height)),
+        (WindowInfoType::Pos(_), &[x, y]) => WindowInfoType::Pos(Pos { x, y }),
+        (WindowInfoType::Size(_), &[width, height]) => WindowInfoType::Size(Size::new(width, height)),
+        (WindowInfoType::Float(_), &[v]) => WindowInfoType::Float(v),
+        (WindowInfoType::InvariantFloat(_), &[v]) => WindowInfoType::InvariantFloat(v),
+        (WindowInfoType::InvariantInt(_), &[v]) if v.fract() == 0.0 => WindowInfoType::InvariantInt(v as i32),
+        (WindowInfoType::Color(_), &[r, g, b]) if [r, g, b].iter().all(|v| v.fract() == 0.0 && (0.0..=255.0).contains(v)) => {
+            WindowInfoType::Color(Color::new(r as u8, g as u8, b as u8))
+        },
+        (WindowInfoType::PosList(_), list) if !list.is_empty() && list.len() % 2 == 0 => {
+            WindowInfoType::PosList(list.chunks(2).map(|p| Pos { x: p[0], y: p[1] }).collect())
+        },
+        _ => return None,
+    };
+    Some(result)
+}
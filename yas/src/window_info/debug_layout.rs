@@ -0,0 +1,163 @@
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use image::{GenericImageView, Rgb, RgbImage};
+use log::{info, warn};
+
+use crate::draw_capture_region::draw_capture_region::DrawCaptureRegion;
+use crate::game_info::{Platform, UI};
+use crate::ocr::ImageToText;
+use crate::positioning::{Pos, Size};
+use crate::window_info::WindowInfoType;
+
+#[derive(Clone, clap::Args)]
+pub struct DebugLayoutConfig {
+    /// A screenshot of the game window, showing the repository page
+    #[arg(id = "screenshot", long = "screenshot", help = "游戏窗口（仅客户区）的仓库页面截图", value_name = "FILE")]
+    pub screenshot: PathBuf,
+
+    #[arg(id = "debug-layout-output", long = "output", short = 'o', help = "绘制了所有区域的图片", value_name = "FILE", default_value = "layout_debug.png")]
+    pub output: PathBuf,
+
+    #[arg(id = "no-ocr", long = "no-ocr", help = "不识别各区域的文字")]
+    pub no_ocr: bool,
+
+    #[arg(id = "debug-layout-ui", long = "ui", help = "截图的界面类型", value_enum, default_value_t = UI::Desktop)]
+    pub ui: UI,
+
+    #[arg(id = "debug-layout-platform", long = "platform", help = "截图的平台", value_enum, default_value_t = Platform::current())]
+    pub platform: Platform,
+}
+
+/// 3x5 bitmaps of the digits, a row per element and the leftmost pixel in the highest bit
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b010, 0b010, 0b010],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+const LABEL_SCALE: i64 = 2;
+
+/// Draw a number with its top left corner at `pos`, on a black background
+fn draw_label(image: &mut RgbImage, pos: Pos<i64>, number: usize) {
+    let digits = number.to_string();
+    let width = (digits.len() as i64 * 4 + 1) * LABEL_SCALE;
+    let height = 7 * LABEL_SCALE;
+
+    let mut put = |x: i64, y: i64, color: Rgb<u8>| {
+        if x >= 0 && y >= 0 && x < image.width() as i64 && y < image.height() as i64 {
+            image.put_pixel(x as u32, y as u32, color);
+        }
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            put(pos.x + x, pos.y + y, Rgb([0, 0, 0]));
+        }
+    }
+
+    for (i, digit) in digits.bytes().enumerate() {
+        let bitmap = DIGITS[(digit - b'0') as usize];
+        let left = pos.x + (i as i64 * 4 + 1) * LABEL_SCALE;
+        let top = pos.y + LABEL_SCALE;
+        for (row, bits) in bitmap.iter().enumerate() {
+            for col in 0..3 {
+                if bits & (0b100 >> col) == 0 {
+                    continue;
+                }
+                for dy in 0..LABEL_SCALE {
+                    for dx in 0..LABEL_SCALE {
+                        put(left + col * LABEL_SCALE + dx, top + row as i64 * LABEL_SCALE + dy, Rgb([255, 255, 0]));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Draw the rects and positions of `values` onto the screenshot, each labelled with its index in `values`
+pub fn draw_debug_layout(screenshot: &RgbImage, values: &[(String, WindowInfoType)]) -> RgbImage {
+    let mut image = screenshot.clone();
+    for (index, (_, value)) in values.iter().enumerate() {
+        match *value {
+            WindowInfoType::Rect(rect) => {
+                rect.draw_capture_region(&mut image);
+                // above the top left corner
+                draw_label(&mut image, Pos { x: rect.left as i64, y: rect.top as i64 - 7 * LABEL_SCALE }, index);
+            },
+            WindowInfoType::Pos(pos) => {
+                pos.draw_capture_region(&mut image);
+                // right of the marker
+                draw_label(&mut image, Pos { x: pos.x as i64 + 7, y: pos.y as i64 - 3 * LABEL_SCALE }, index);
+            },
+            WindowInfoType::PosList(ref list) => {
+                for pos in list.iter() {
+                    pos.draw_capture_region(&mut image);
+                    draw_label(&mut image, Pos { x: pos.x as i64 + 7, y: pos.y as i64 - 3 * LABEL_SCALE }, index);
+                }
+            },
+            _ => {},
+        }
+    }
+    image
+}
+
+/// Crop a rect relative to the window from the screenshot, None if the rect is not inside it
+pub fn crop_window_info_rect(screenshot: &RgbImage, value: &WindowInfoType) -> Option<RgbImage> {
+    let rect = match *value {
+        WindowInfoType::Rect(rect) => rect,
+        _ => return None,
+    };
+    if rect.left < 0.0 || rect.top < 0.0 || rect.width < 1.0 || rect.height < 1.0
+        || rect.left + rect.width > screenshot.width() as f64
+        || rect.top + rect.height > screenshot.height() as f64 {
+        return None;
+    }
+
+    Some(screenshot.view(rect.left as u32, rect.top as u32, rect.width as u32, rect.height as u32).to_image())
+}
+
+impl DebugLayoutConfig {
+    /// Draw the window info resolved by `resolve` for the size of the screenshot, and print the
+    /// key and the recognized text of each labelled region
+    pub fn run<F>(&self, resolve: F, image_to_text: Option<&dyn ImageToText<RgbImage>>) -> Result<()>
+    where
+        F: FnOnce(Size<usize>, UI, Platform) -> Result<Vec<(String, WindowInfoType)>>
+    {
+        let screenshot = image::open(&self.screenshot)
+            .map_err(|e| anyhow!("无法读取截图 {:?}: {}", self.screenshot, e))?
+            .to_rgb8();
+        let window_size = Size::new(screenshot.width() as usize, screenshot.height() as usize);
+        info!("截图尺寸：{}x{}", window_size.width, window_size.height);
+
+        let values = resolve(window_size, self.ui, self.platform)?;
+        draw_debug_layout(&screenshot, &values).save(&self.output)?;
+        info!("区域图已保存到 {:?}", self.output);
+
+        for (index, (key, value)) in values.iter().enumerate() {
+            let text = match (image_to_text, value) {
+                (Some(model), WindowInfoType::Rect(_)) => match crop_window_info_rect(&screenshot, value) {
+                    Some(im) => match model.image_to_text(&im, false) {
+                        Ok(text) => format!("\"{}\"", text),
+                        Err(e) => {
+                            warn!("识别 {} 失败：{}", key, e);
+                            String::new()
+                        },
+                    },
+                    None => String::from("（超出截图）"),
+                },
+                _ => String::new(),
+            };
+            info!("{:>3} {} {:?} {}", index, key, value, text);
+        }
+
+        Ok(())
+    }
+}
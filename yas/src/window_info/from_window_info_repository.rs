@@ -3,10 +3,108 @@ use crate::positioning::Size;
 use crate::window_info::WindowInfoRepository;
 
 pub trait FromWindowInfoRepository: Sized {
-    fn from_window_info_repository(
+    /// Read the struct with every window info key prefixed by `prefix`,
+    /// which is how a struct is flattened into another
+    fn from_window_info_repository_with_prefix(
+        prefix: &str,
         window_size: Size<usize>,
         ui: UI,
         platform: Platform,
         repo: &WindowInfoRepository
     ) -> anyhow::Result<Self>;
+
+    fn from_window_info_repository(
+        window_size: Size<usize>,
+        ui: UI,
+        platform: Platform,
+        repo: &WindowInfoRepository
+    ) -> anyhow::Result<Self> {
+        Self::from_window_info_repository_with_prefix("", window_size, ui, platform, repo)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::positioning::Pos;
+    use crate::window_info::{RequiredWindowInfoKeys, WindowInfoError, WindowInfoType};
+
+    use super::*;
+
+    fn default_gap() -> f64 {
+        7.5
+    }
+
+    #[derive(Debug, yas_derive::YasWindowInfo)]
+    struct Probe {
+        #[window_info(anchor = "Right", scale = "Height")]
+        panel: Pos<f64>,
+        maybe: Option<Pos<f64>>,
+        #[window_info(default)]
+        count: i32,
+        #[window_info(default = "default_gap")]
+        gap: f64,
+        #[window_info(exact, rename = "exact_pos")]
+        exact: Option<Pos<f64>>,
+    }
+
+    fn repo() -> WindowInfoRepository {
+        let size = Size::new(1600, 900);
+        let mut repo = WindowInfoRepository::new();
+        repo.add_pos("panel", size, UI::Desktop, Platform::Windows, Pos::new(1500.0, 200.0));
+        repo.add_pos("maybe", size, UI::Desktop, Platform::Windows, Pos::new(100.0, 200.0));
+        repo.add("exact_pos", size, UI::Desktop, Platform::Windows, WindowInfoType::Pos(Pos::new(10.0, 20.0)));
+        repo
+    }
+
+    fn read(size: Size<usize>, repo: &WindowInfoRepository) -> anyhow::Result<Probe> {
+        Probe::from_window_info_repository(size, UI::Desktop, Platform::Windows, repo)
+    }
+
+    #[test]
+    fn same_family_is_scaled_and_missing_keys_take_their_default() {
+        let probe = read(Size::new(1920, 1080), &repo()).unwrap();
+        assert_eq!(probe.panel, Pos::new(1800.0, 240.0));
+        assert_eq!(probe.maybe, Some(Pos::new(120.0, 240.0)));
+        assert_eq!(probe.count, 0);
+        assert_eq!(probe.gap, 7.5);
+        // an exact field is only read at the recorded size
+        assert_eq!(probe.exact, None);
+
+        let probe = read(Size::new(1600, 900), &repo()).unwrap();
+        assert_eq!(probe.exact, Some(Pos::new(10.0, 20.0)));
+    }
+
+    #[test]
+    fn anchored_fields_are_projected_onto_other_aspect_ratios() {
+        let probe = read(Size::new(2100, 900), &repo()).unwrap();
+        assert_eq!(probe.panel, Pos::new(2000.0, 200.0));
+        // no layout rule is known for the key
+        assert_eq!(probe.maybe, None);
+    }
+
+    #[test]
+    fn a_missing_required_key_is_an_error() {
+        let mut repo = repo();
+        repo.data.remove("panel");
+        let e = read(Size::new(1600, 900), &repo).unwrap_err();
+        match e.downcast_ref::<WindowInfoError>() {
+            Some(WindowInfoError::MissingKey { key, .. }) => assert_eq!(key, "panel"),
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn required_keys_tell_which_keys_are_optional() {
+        let keys = Probe::required_window_info_keys_with_prefix("probe_");
+        let keys = keys.iter()
+            .map(|k| (k.key.as_str(), k.type_name, k.optional))
+            .collect::<Vec<_>>();
+        assert_eq!(keys, vec![
+            ("probe_panel", "Pos<f64>", false),
+            ("probe_maybe", "Pos<f64>", true),
+            ("probe_count", "i32", true),
+            ("probe_gap", "f64", true),
+            ("probe_exact_pos", "Pos<f64>", true),
+        ]);
+    }
 }
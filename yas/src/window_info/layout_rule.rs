@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use crate::positioning::{Pos, Rect, Size};
+use crate::window_info::WindowInfoType;
+
+/// The point of the window an element keeps its distance to, when the window has another aspect ratio
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Anchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// The anchor point as a fraction of the window width and height
+    fn fractions(&self) -> (f64, f64) {
+        match *self {
+            Anchor::TopLeft => (0.0, 0.0),
+            Anchor::Top => (0.5, 0.0),
+            Anchor::TopRight => (1.0, 0.0),
+            Anchor::Left => (0.0, 0.5),
+            Anchor::Center => (0.5, 0.5),
+            Anchor::Right => (1.0, 0.5),
+            Anchor::BottomLeft => (0.0, 1.0),
+            Anchor::Bottom => (0.5, 1.0),
+            Anchor::BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
+/// How the size of an element follows the window size
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScaleMode {
+    /// Scale with the window width
+    Width,
+    /// Scale with the window height, as most game UIs do
+    Height,
+    /// Scale with the smaller factor, so the element stays inside the window
+    #[default]
+    Fit,
+    /// Scale with the larger factor
+    Fill,
+    /// Scale x and y separately, the anchor is not used
+    Stretch,
+}
+
+/// Describes how a window info entry is projected onto a window of another aspect ratio
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayoutRule {
+    #[serde(default)]
+    pub anchor: Anchor,
+    #[serde(default)]
+    pub scale: ScaleMode,
+}
+
+impl LayoutRule {
+    pub fn new(anchor: Anchor, scale: ScaleMode) -> Self {
+        Self { anchor, scale }
+    }
+
+    /// Project a value recorded for a window of size `from` onto a window of size `to`
+    pub fn project(&self, value: &WindowInfoType, from: Size<usize>, to: Size<usize>) -> WindowInfoType {
+        let fx = to.width as f64 / from.width as f64;
+        let fy = to.height as f64 / from.height as f64;
+        let (fx, fy) = match self.scale {
+            ScaleMode::Width => (fx, fx),
+            ScaleMode::Height => (fy, fy),
+            ScaleMode::Fit => (fx.min(fy), fx.min(fy)),
+            ScaleMode::Fill => (fx.max(fy), fx.max(fy)),
+            ScaleMode::Stretch => (fx, fy),
+        };
+
+        let (ax, ay) = match self.scale {
+            ScaleMode::Stretch => (0.0, 0.0),
+            _ => self.anchor.fractions(),
+        };
+        // keep the distance to the anchor point, scaled
+        let project_pos = |pos: Pos<f64>| Pos {
+            x: ax * to.width as f64 + (pos.x - ax * from.width as f64) * fx,
+            y: ay * to.height as f64 + (pos.y - ay * from.height as f64) * fy,
+        };
+        let scale_size = |size: Size<f64>| Size {
+            width: size.width * fx,
+            height: size.height * fy,
+        };
+
+        match *value {
+            WindowInfoType::Rect(rect) => {
+                let origin = project_pos(rect.origin());
+                let size = scale_size(rect.size());
+                WindowInfoType::Rect(Rect::new(origin.x, origin.y, size.width, size.height))
+            },
+            WindowInfoType::Pos(pos) => WindowInfoType::Pos(project_pos(pos)),
+            WindowInfoType::Size(size) => WindowInfoType::Size(scale_size(size)),
+            // a plain length follows the vertical factor when stretched
+            WindowInfoType::Float(v) => WindowInfoType::Float(v * fy),
+            WindowInfoType::InvariantInt(v) => WindowInfoType::InvariantInt(v),
+            WindowInfoType::InvariantFloat(v) => WindowInfoType::InvariantFloat(v),
+            WindowInfoType::Color(color) => WindowInfoType::Color(color),
+            WindowInfoType::ColorPalette(ref palette) => WindowInfoType::ColorPalette(palette.clone()),
+            WindowInfoType::PosList(ref list) => WindowInfoType::PosList(list.iter().map(|&pos| project_pos(pos)).collect()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 16:9 onto 21:9 of the same height, the horizontal factor is 1.3125
+    const FROM: Size<usize> = Size { width: 1600, height: 900 };
+    const TO: Size<usize> = Size { width: 2100, height: 900 };
+
+    fn project_rect(rule: LayoutRule, rect: Rect<f64>) -> Rect<f64> {
+        match rule.project(&WindowInfoType::Rect(rect), FROM, TO) {
+            WindowInfoType::Rect(rect) => rect,
+            other => panic!("a rect is projected to {:?}", other),
+        }
+    }
+
+    fn project_pos(rule: LayoutRule, pos: Pos<f64>) -> Pos<f64> {
+        match rule.project(&WindowInfoType::Pos(pos), FROM, TO) {
+            WindowInfoType::Pos(pos) => pos,
+            other => panic!("a pos is projected to {:?}", other),
+        }
+    }
+
+    #[test]
+    fn left_anchor_keeps_the_distance_to_the_left_edge() {
+        let rule = LayoutRule::new(Anchor::Left, ScaleMode::Height);
+        let rect = Rect::new(100.0, 200.0, 50.0, 60.0);
+        assert_eq!(project_rect(rule, rect), rect);
+    }
+
+    #[test]
+    fn right_anchor_keeps_the_distance_to_the_right_edge() {
+        let rule = LayoutRule::new(Anchor::Right, ScaleMode::Height);
+        let rect = project_rect(rule, Rect::new(1500.0, 200.0, 50.0, 60.0));
+        assert_eq!(rect, Rect::new(2000.0, 200.0, 50.0, 60.0));
+    }
+
+    #[test]
+    fn center_fit_keeps_the_center() {
+        let rule = LayoutRule::default();
+        assert_eq!(project_pos(rule, Pos::new(800.0, 450.0)), Pos::new(1050.0, 450.0));
+        // the smaller factor is 1, sizes are unchanged
+        assert_eq!(project_rect(rule, Rect::new(700.0, 400.0, 200.0, 100.0)), Rect::new(950.0, 400.0, 200.0, 100.0));
+    }
+
+    #[test]
+    fn width_and_fill_scale_with_the_larger_factor() {
+        let rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let expected = Rect::new(0.0, 0.0, 131.25, 131.25);
+        assert_eq!(project_rect(LayoutRule::new(Anchor::TopLeft, ScaleMode::Width), rect), expected);
+        assert_eq!(project_rect(LayoutRule::new(Anchor::TopLeft, ScaleMode::Fill), rect), expected);
+    }
+
+    #[test]
+    fn stretch_scales_the_axes_separately() {
+        // the anchor is not used
+        let rule = LayoutRule::new(Anchor::BottomRight, ScaleMode::Stretch);
+        assert_eq!(project_rect(rule, Rect::new(800.0, 450.0, 100.0, 100.0)), Rect::new(1050.0, 450.0, 131.25, 100.0));
+
+        match rule.project(&WindowInfoType::Float(10.0), FROM, TO) {
+            WindowInfoType::Float(v) => assert_eq!(v, 10.0),
+            other => panic!("a float is projected to {:?}", other),
+        }
+    }
+
+    #[test]
+    fn invariant_values_are_not_projected() {
+        let rule = LayoutRule::new(Anchor::Right, ScaleMode::Width);
+        match rule.project(&WindowInfoType::InvariantFloat(0.5), FROM, TO) {
+            WindowInfoType::InvariantFloat(v) => assert_eq!(v, 0.5),
+            other => panic!("an invariant float is projected to {:?}", other),
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use serde::{Deserialize, Serialize};
 use crate::game_info::{Platform, UI};
 use crate::positioning::Size;
-use crate::window_info::WindowInfoType;
+use crate::window_info::{LayoutRule, RequiredWindowInfoKey, WindowInfoError, WindowInfoType};
 use crate::window_info::WindowInfoRepository;
 
 /// Which is a format, where the whole file are recorded under a certain resolution
@@ -11,15 +12,98 @@ pub struct WindowInfoTemplatePerSize {
     pub current_resolution: Size<usize>,
     pub platform: Platform,
     pub ui: UI,
-    pub data: HashMap<String, WindowInfoType>
+    pub data: HashMap<String, WindowInfoType>,
+    /// How some keys are projected onto windows of other aspect ratios, see `LayoutRule`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub layout: HashMap<String, LayoutRule>,
 }
 
 impl WindowInfoTemplatePerSize {
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, WindowInfoError> {
+        let path = path.as_ref();
+        let s = std::fs::read_to_string(path)
+            .map_err(|e| WindowInfoError::Load(format!("cannot read {:?}: {}", path, e)))?;
+        serde_json::from_str(&s)
+            .map_err(|e| WindowInfoError::Load(format!("invalid window info {:?}: {}", path, e)))
+    }
+
+    /// A description of each key missing or with a value of the wrong type
+    pub fn check_keys(&self, keys: &[RequiredWindowInfoKey]) -> Vec<String> {
+        let mut problems = Vec::new();
+        for required in keys.iter() {
+            match self.data.get(&required.key) {
+                None if required.optional => {},
+                None => problems.push(format!("missing \"{}\" ({})", required.key, required.type_name)),
+                Some(value) if !(required.accepts)(value) => {
+                    problems.push(format!("\"{}\" is {:?}, expect {}", required.key, value, required.type_name));
+                },
+                Some(_) => {},
+            }
+        }
+        problems
+    }
+
     pub fn inject_into_window_info_repo(&self, repo: &mut WindowInfoRepository) {
         for (name, value) in self.data.iter() {
-            repo.add(name, self.current_resolution, self.ui, self.platform, *value);
+            repo.add(name, self.current_resolution, self.ui, self.platform, value.clone());
+        }
+        for (name, rule) in self.layout.iter() {
+            repo.set_layout(name, *rule);
+        }
+    }
+}
+
+/// The path itself if it is a file, or the json files of a directory in the order of their names
+fn window_info_files(path: &Path) -> Result<Vec<PathBuf>, WindowInfoError> {
+    if !path.is_dir() {
+        return Ok(vec![PathBuf::from(path)]);
+    }
+
+    let entries = std::fs::read_dir(path)
+        .map_err(|e| WindowInfoError::Load(format!("cannot read directory {:?}: {}", path, e)))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| WindowInfoError::Load(format!("cannot read directory {:?}: {}", path, e)))?;
+        let file = entry.path();
+        if file.is_file() && file.extension().is_some_and(|ext| ext == "json") {
+            files.push(file);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Load a window info file, or every json file of a directory in the order of their names,
+/// the later files override the earlier ones
+pub fn load_window_info_path<P: AsRef<Path>>(path: P) -> Result<WindowInfoRepository, WindowInfoError> {
+    let mut result = WindowInfoRepository::new();
+    for file in window_info_files(path.as_ref())?.iter() {
+        WindowInfoTemplatePerSize::from_file(file)?.inject_into_window_info_repo(&mut result);
+    }
+    Ok(result)
+}
+
+/// Check that every window info file of a path, as in `load_window_info_path`, has all the `keys`
+/// with values of the right types. All problems are reported at once
+pub fn validate_window_info_path<P: AsRef<Path>>(path: P, keys: &[RequiredWindowInfoKey]) -> Result<(), WindowInfoError> {
+    let files = window_info_files(path.as_ref())?;
+    if files.is_empty() {
+        return Err(WindowInfoError::Load(format!("no window info file in {:?}", path.as_ref())));
+    }
+
+    let mut problems = Vec::new();
+    for file in files.iter() {
+        let template = WindowInfoTemplatePerSize::from_file(file)?;
+        for problem in template.check_keys(keys) {
+            problems.push(format!("{:?}: {}", file, problem));
         }
     }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(WindowInfoError::Incomplete(problems))
+    }
 }
 
 pub macro load_window_info_repo($($filename:literal),+ $(,)?) {
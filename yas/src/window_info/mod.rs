@@ -2,8 +2,24 @@ mod window_info_repository;
 mod window_info_type;
 mod load_window_info;
 mod from_window_info_repository;
+mod window_info_error;
+mod layout_rule;
+mod window_info_config;
+mod calibrate;
+mod window_info_values;
+mod required_keys;
+mod repository_layout_window_info;
+mod debug_layout;
 
 pub use from_window_info_repository::FromWindowInfoRepository;
 pub use window_info_repository::WindowInfoRepository;
 pub use window_info_type::WindowInfoType;
-pub use load_window_info::load_window_info_repo;
\ No newline at end of file
+pub use window_info_error::WindowInfoError;
+pub use load_window_info::{load_window_info_path, load_window_info_repo, validate_window_info_path, WindowInfoTemplatePerSize};
+pub use layout_rule::{Anchor, LayoutRule, ScaleMode};
+pub use window_info_config::WindowInfoConfig;
+pub use calibrate::{CalibrateConfig, Calibrator};
+pub use window_info_values::{merge_window_info_values, WindowInfoValues};
+pub use debug_layout::{DebugLayoutConfig, crop_window_info_rect, draw_debug_layout};
+pub use required_keys::{merge_required_keys, RequiredWindowInfoKey, RequiredWindowInfoKeys};
+pub use repository_layout_window_info::RepositoryLayoutWindowInfo;
\ No newline at end of file
@@ -0,0 +1,30 @@
+use crate::positioning::{Pos, Rect, Size};
+
+/// The layout of the item grid of a repository, common to the games.
+/// Flattened into a window info struct with a prefix such as `genshin_repository_`.
+/// On other aspect ratios the grid keeps to the left edge of the window and the panel to the right,
+/// both scale with the window height
+#[derive(Clone, Debug, yas_derive::YasWindowInfo)]
+pub struct RepositoryLayoutWindowInfo {
+    /// the whole panel of the selected item
+    #[window_info(anchor = "Right", scale = "Height")]
+    pub panel_rect: Rect<f64>,
+
+    #[window_info(anchor = "Left", scale = "Height")]
+    pub item_gap_size: Size<f64>,
+    #[window_info(anchor = "Left", scale = "Height")]
+    pub item_size: Size<f64>,
+
+    /// the top left corner of the first item
+    #[window_info(anchor = "Left", scale = "Height")]
+    pub scan_margin_pos: Pos<f64>,
+
+    /// the region compared to tell whether the selected item has switched
+    #[window_info(anchor = "Right", scale = "Height")]
+    pub pool_rect: Rect<f64>,
+
+    #[window_info(anchor = "Left", scale = "Height")]
+    pub item_row: i32,
+    #[window_info(anchor = "Left", scale = "Height")]
+    pub item_col: i32,
+}
@@ -0,0 +1,34 @@
+use crate::window_info::WindowInfoType;
+
+/// A window info key read by a window info struct
+#[derive(Clone)]
+pub struct RequiredWindowInfoKey {
+    pub key: String,
+    /// The type of the field, e.g. `Rect<f64>`
+    pub type_name: &'static str,
+    /// Whether the key may be absent, for `Option` fields and fields with a default
+    pub optional: bool,
+    /// Whether the value of the key can be read as the type of the field
+    pub accepts: fn(&WindowInfoType) -> bool,
+}
+
+/// The keys a window info struct reads, derived by `YasWindowInfo`
+pub trait RequiredWindowInfoKeys {
+    /// The keys with every key prefixed by `prefix`
+    fn required_window_info_keys_with_prefix(prefix: &str) -> Vec<RequiredWindowInfoKey>;
+
+    fn required_window_info_keys() -> Vec<RequiredWindowInfoKey> {
+        Self::required_window_info_keys_with_prefix("")
+    }
+}
+
+/// Append the keys of `other` which are not in `keys` yet,
+/// for a scanner reading several window info structs that share some keys
+pub fn merge_required_keys(mut keys: Vec<RequiredWindowInfoKey>, other: Vec<RequiredWindowInfoKey>) -> Vec<RequiredWindowInfoKey> {
+    for key in other {
+        if keys.iter().all(|k| k.key != key.key) {
+            keys.push(key);
+        }
+    }
+    keys
+}
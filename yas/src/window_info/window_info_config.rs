@@ -0,0 +1,24 @@
+use std::path::PathBuf;
+
+use log::info;
+
+use crate::window_info::{load_window_info_path, WindowInfoError, WindowInfoRepository};
+
+#[derive(Clone, clap::Args, Default)]
+pub struct WindowInfoConfig {
+    /// Window info files, or directories of them, which override the built-in window info
+    #[arg(id = "window-info", long = "window-info", help = "从 JSON 文件或目录加载窗口布局信息，覆盖内置的布局，可以指定多次", value_name = "FILE|DIR")]
+    pub paths: Vec<PathBuf>,
+}
+
+impl WindowInfoConfig {
+    /// Merge the window info files over `repo`, in the order they are given
+    pub fn merge_into(&self, repo: &mut WindowInfoRepository) -> Result<(), WindowInfoError> {
+        for path in self.paths.iter() {
+            let loaded = load_window_info_path(path)?;
+            info!("从 {:?} 加载了 {} 项窗口布局信息", path, loaded.data.len());
+            repo.merge_inplace(&loaded);
+        }
+        Ok(())
+    }
+}
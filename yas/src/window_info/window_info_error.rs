@@ -0,0 +1,30 @@
+use thiserror::Error;
+
+use crate::game_info::{Platform, UI};
+use crate::positioning::Size;
+
+/// Errors when reading window info from a `WindowInfoRepository`
+#[derive(Error, Debug)]
+pub enum WindowInfoError {
+    /// The key is absent, or cannot be scaled to the window size
+    #[error("cannot find window info key \"{key}\" for {}x{} {ui:?} {platform:?}", .window_size.width, .window_size.height)]
+    MissingKey {
+        key: String,
+        window_size: Size<usize>,
+        ui: UI,
+        platform: Platform,
+    },
+
+    #[error("window info is not a {expected} type")]
+    TypeMismatch {
+        expected: &'static str,
+    },
+
+    /// A window info file given at runtime cannot be read or parsed
+    #[error("cannot load window info: {0}")]
+    Load(String),
+
+    /// Window info files lack keys which a window info struct reads
+    #[error("window info is incomplete:\n{}", .0.join("\n"))]
+    Incomplete(Vec<String>),
+}
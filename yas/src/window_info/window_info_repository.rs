@@ -1,10 +1,10 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
-use crate::game_info::{Platform, UI};
+use crate::game_info::{Platform, ResolutionFamily, UI};
 use crate::positioning::{Pos, Scalable, Size};
 
-use crate::window_info::WindowInfoType;
+use crate::window_info::{LayoutRule, WindowInfoType};
 
 /// Maps a window-info-key to a list of entries
 /// where entries consist of a size where the value is recorded, and accordingly a value
@@ -12,12 +12,16 @@ use crate::window_info::WindowInfoType;
 pub struct WindowInfoRepository {
     /// window info key -> (window size, ui, platform)
     pub data: HashMap<String, HashMap<(Size<usize>, UI, Platform), WindowInfoType>>,
+    /// window info key -> how the value is projected onto other aspect ratios
+    #[serde(default)]
+    pub layout: HashMap<String, LayoutRule>,
 }
 
 impl WindowInfoRepository {
     pub fn new() -> WindowInfoRepository {
         WindowInfoRepository {
             data: HashMap::new(),
+            layout: HashMap::new(),
         }
     }
 
@@ -35,7 +39,15 @@ impl WindowInfoRepository {
             .insert((size, ui, platform), WindowInfoType::Pos(value));
     }
 
+    pub fn set_layout(&mut self, name: &str, rule: LayoutRule) {
+        self.layout.insert(String::from(name), rule);
+    }
+
     pub fn merge_inplace(&mut self, other: &WindowInfoRepository) {
+        for (key, rule) in other.layout.iter() {
+            self.layout.insert(key.clone(), *rule);
+        }
+
         for (key, data) in other.data.iter() {
             if self.data.contains_key(key) {
                 for (resolution, value) in data.iter() {
@@ -58,33 +70,118 @@ impl WindowInfoRepository {
     pub fn get_exact<T>(&self, name: &str, window_size: Size<usize>, ui: UI, platform: Platform) -> Option<T> where WindowInfoType: TryInto<T> {
         if self.data.contains_key(name) &&
           self.data[name].contains_key(&(window_size, ui, platform)) {
-            return self.data[name][&(window_size, ui, platform)].try_into().ok();
+            return self.data[name][&(window_size, ui, platform)].clone().try_into().ok();
         }
 
         None
     }
 
     /// Get window info by name and size
-    /// if window size does not exists exactly, this function will search for the same resolution family and scale the result
+    /// if window size does not exists exactly, this function will scale the value recorded for the same resolution family.
+    /// Otherwise the value recorded for the closest aspect ratio is projected with the layout rule of the key,
+    /// None if the repository has no layout rule for the key
     pub fn get_auto_scale<T>(&self, name: &str, window_size: Size<usize>, ui: UI, platform: Platform) -> Option<T> where WindowInfoType: TryInto<T> {
-        if self.data.contains_key(name) {
-            if self.data[name].contains_key(&(window_size, ui, platform)) {
-                return self.data[name][&(window_size, ui, platform)].try_into().ok();
-            } else {
-                // todo find a biggest size which can be scaled, this will reduce error
-                // find if a resolution can be scaled
-                for (k, value) in self.data[name].iter() {
-                    let size = &k.0;
-                    if size.width * window_size.height == size.height * window_size.width
-                        && k.1 == ui && k.2 == platform
-                    {
-                        let factor: f64 = window_size.width as f64 / size.width as f64;
-                        return value.scale(factor).try_into().ok();
-                    }
-                }
-            }
+        self.get_projected(name, window_size, ui, platform, None)
+    }
+
+    /// Same as `get_auto_scale`, but `default_layout` is used to project the value
+    /// when the repository has no layout rule for the key
+    pub fn get_auto_scale_with_layout<T>(
+        &self,
+        name: &str,
+        window_size: Size<usize>,
+        ui: UI,
+        platform: Platform,
+        default_layout: LayoutRule
+    ) -> Option<T> where WindowInfoType: TryInto<T> {
+        self.get_projected(name, window_size, ui, platform, Some(default_layout))
+    }
+
+    fn get_projected<T>(
+        &self,
+        name: &str,
+        window_size: Size<usize>,
+        ui: UI,
+        platform: Platform,
+        default_layout: Option<LayoutRule>
+    ) -> Option<T> where WindowInfoType: TryInto<T> {
+        let entries = self.data.get(name)?;
+        if let Some(value) = entries.get(&(window_size, ui, platform)) {
+            return value.clone().try_into().ok();
         }
 
-        None
+        let candidates = entries.iter()
+            .filter(|(k, _)| k.1 == ui && k.2 == platform && k.0.width > 0 && k.0.height > 0);
+
+        // the biggest size of the same resolution family is scaled, which has the least error.
+        // A window a little off the aspect ratio of its family is scaled from the family too
+        let family = ResolutionFamily::new(window_size, platform);
+        let same_family = candidates.clone()
+            .filter(|(k, _)| {
+                k.0.width * window_size.height == k.0.height * window_size.width
+                    || family.is_some() && ResolutionFamily::new(k.0, platform) == family
+            })
+            .max_by_key(|(k, _)| k.0.width);
+        if let Some((k, value)) = same_family {
+            let factor: f64 = window_size.width as f64 / k.0.width as f64;
+            return value.scale(factor).try_into().ok();
+        }
+
+        // a value recorded for another aspect ratio is only projected if it is known where the element sits
+        let rule = self.layout.get(name).copied().or(default_layout)?;
+
+        let aspect_ratio = |size: &Size<usize>| size.width as f64 / size.height as f64;
+        let target = aspect_ratio(&window_size);
+        let (k, value) = candidates.min_by(|(a, _), (b, _)| {
+            let da = (aspect_ratio(&a.0) / target).ln().abs();
+            let db = (aspect_ratio(&b.0) / target).ln().abs();
+            da.total_cmp(&db).then(b.0.width.cmp(&a.0.width))
+        })?;
+
+        rule.project(value, k.0, window_size).try_into().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::window_info::{Anchor, ScaleMode};
+
+    use super::*;
+
+    fn repo() -> WindowInfoRepository {
+        let mut repo = WindowInfoRepository::new();
+        repo.add_pos("panel", Size::new(1600, 900), UI::Desktop, Platform::Windows, Pos::new(1500.0, 200.0));
+        repo
+    }
+
+    #[test]
+    fn same_family_is_scaled_without_a_rule() {
+        let pos: Pos<f64> = repo().get_auto_scale("panel", Size::new(1920, 1080), UI::Desktop, Platform::Windows).unwrap();
+        assert_eq!(pos, Pos::new(1800.0, 240.0));
+    }
+
+    #[test]
+    fn sizes_a_little_off_are_scaled_from_their_family() {
+        let repo = repo();
+        let pos: Pos<f64> = repo.get_auto_scale("panel", Size::new(1600, 901), UI::Desktop, Platform::Windows).unwrap();
+        assert_eq!(pos, Pos::new(1500.0, 200.0));
+        let pos: Pos<f64> = repo.get_auto_scale("panel", Size::new(1921, 1080), UI::Desktop, Platform::Windows).unwrap();
+        assert!((pos.x - 1500.0 * 1921.0 / 1600.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn other_aspect_ratio_needs_a_rule() {
+        let mut repo = repo();
+        let size = Size::new(2100, 900);
+        assert_eq!(repo.get_auto_scale::<Pos<f64>>("panel", size, UI::Desktop, Platform::Windows), None);
+
+        let rule = LayoutRule::new(Anchor::Right, ScaleMode::Height);
+        let pos = repo.get_auto_scale_with_layout::<Pos<f64>>("panel", size, UI::Desktop, Platform::Windows, rule);
+        assert_eq!(pos, Some(Pos::new(2000.0, 200.0)));
+
+        // the rule of the repository wins over the one of the caller
+        repo.set_layout("panel", LayoutRule::new(Anchor::Left, ScaleMode::Height));
+        let pos = repo.get_auto_scale_with_layout::<Pos<f64>>("panel", size, UI::Desktop, Platform::Windows, rule);
+        assert_eq!(pos, Some(Pos::new(1500.0, 200.0)));
     }
 }
@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
+use crate::common::color::{Color, ColorPalette};
 use crate::positioning::{Pos, Rect, Scalable, Size};
-use anyhow::anyhow;
+use crate::window_info::WindowInfoError;
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum WindowInfoType {
     Rect(Rect<f64>),
     Pos(Pos<f64>),
@@ -11,74 +12,163 @@ pub enum WindowInfoType {
     /// when window size scales, these amount will not scale
     InvariantInt(i32),
     InvariantFloat(f64),
+    /// a reference color, which does not scale
+    Color(Color),
+    /// named reference colors, e.g. of the stars of an item
+    ColorPalette(ColorPalette),
+    PosList(Vec<Pos<f64>>),
+}
+
+impl From<Rect<f64>> for WindowInfoType {
+    fn from(rect: Rect<f64>) -> Self {
+        WindowInfoType::Rect(rect)
+    }
+}
+
+impl From<Pos<f64>> for WindowInfoType {
+    fn from(pos: Pos<f64>) -> Self {
+        WindowInfoType::Pos(pos)
+    }
+}
+
+impl From<Size<f64>> for WindowInfoType {
+    fn from(size: Size<f64>) -> Self {
+        WindowInfoType::Size(size)
+    }
+}
+
+impl From<f64> for WindowInfoType {
+    fn from(v: f64) -> Self {
+        WindowInfoType::Float(v)
+    }
+}
+
+impl From<i32> for WindowInfoType {
+    fn from(v: i32) -> Self {
+        WindowInfoType::InvariantInt(v)
+    }
+}
+
+impl From<Color> for WindowInfoType {
+    fn from(color: Color) -> Self {
+        WindowInfoType::Color(color)
+    }
+}
+
+impl From<ColorPalette> for WindowInfoType {
+    fn from(palette: ColorPalette) -> Self {
+        WindowInfoType::ColorPalette(palette)
+    }
+}
+
+impl From<Vec<Pos<f64>>> for WindowInfoType {
+    fn from(list: Vec<Pos<f64>>) -> Self {
+        WindowInfoType::PosList(list)
+    }
 }
 
 // due to orphan rule, we implement TryInto instead of TryFrom
 impl TryInto<i32> for WindowInfoType {
-    type Error = anyhow::Error;
+    type Error = WindowInfoError;
 
     fn try_into(self) -> Result<i32, Self::Error> {
         match self {
             WindowInfoType::InvariantInt(v) => Ok(v),
-            _ => Err(anyhow!(String::from("not an i32 type")))
+            _ => Err(WindowInfoError::TypeMismatch { expected: "i32" })
         }
     }
 }
 
 impl TryInto<Rect<f64>> for WindowInfoType {
-    type Error = anyhow::Error;
+    type Error = WindowInfoError;
 
     fn try_into(self) -> Result<Rect<f64>, Self::Error> {
         match self {
             WindowInfoType::Rect(rect) => Ok(rect),
-            _ => Err(anyhow!(String::from("not a rect type"))),
+            _ => Err(WindowInfoError::TypeMismatch { expected: "rect" }),
         }
     }
 }
 
 impl TryInto<Pos<f64>> for WindowInfoType {
-    type Error = anyhow::Error;
+    type Error = WindowInfoError;
 
     fn try_into(self) -> Result<Pos<f64>, Self::Error> {
         match self {
             WindowInfoType::Pos(pos) => Ok(pos),
-            _ => Err(anyhow!(String::from("not a pos type"))),
+            _ => Err(WindowInfoError::TypeMismatch { expected: "pos" }),
         }
     }
 }
 
 impl TryInto<f64> for WindowInfoType {
-    type Error = anyhow::Error;
+    type Error = WindowInfoError;
 
     fn try_into(self) -> std::result::Result<f64, Self::Error> {
         match self {
             WindowInfoType::Float(f) => Ok(f),
             WindowInfoType::InvariantFloat(f) => Ok(f),
-            _ => Err(anyhow!(String::from("not a float type"))),
+            _ => Err(WindowInfoError::TypeMismatch { expected: "float" }),
         }
     }
 }
 
 impl TryInto<Size<f64>> for WindowInfoType {
-    type Error = anyhow::Error;
+    type Error = WindowInfoError;
 
     fn try_into(self) -> Result<Size<f64>, Self::Error> {
         match self {
             WindowInfoType::Size(size) => Ok(size),
-            _ => Err(anyhow!(String::from("not a size type"))),
+            _ => Err(WindowInfoError::TypeMismatch { expected: "size" }),
+        }
+    }
+}
+
+impl TryInto<Color> for WindowInfoType {
+    type Error = WindowInfoError;
+
+    fn try_into(self) -> Result<Color, Self::Error> {
+        match self {
+            WindowInfoType::Color(color) => Ok(color),
+            _ => Err(WindowInfoError::TypeMismatch { expected: "color" }),
+        }
+    }
+}
+
+impl TryInto<ColorPalette> for WindowInfoType {
+    type Error = WindowInfoError;
+
+    fn try_into(self) -> Result<ColorPalette, Self::Error> {
+        match self {
+            WindowInfoType::ColorPalette(palette) => Ok(palette),
+            _ => Err(WindowInfoError::TypeMismatch { expected: "color palette" }),
+        }
+    }
+}
+
+impl TryInto<Vec<Pos<f64>>> for WindowInfoType {
+    type Error = WindowInfoError;
+
+    fn try_into(self) -> Result<Vec<Pos<f64>>, Self::Error> {
+        match self {
+            WindowInfoType::PosList(list) => Ok(list),
+            _ => Err(WindowInfoError::TypeMismatch { expected: "pos list" }),
         }
     }
 }
 
 impl Scalable for WindowInfoType {
     fn scale(&self, factor: f64) -> Self {
-        let result = match *self {
+        let result = match self {
             WindowInfoType::Rect(rect) => WindowInfoType::Rect(rect.scale(factor)),
             WindowInfoType::Pos(pos) => WindowInfoType::Pos(pos.scale(factor)),
             WindowInfoType::Size(size) => WindowInfoType::Size(size.scale(factor)),
             WindowInfoType::Float(v) => WindowInfoType::Float(v.scale(factor)),
-            WindowInfoType::InvariantInt(v) => WindowInfoType::InvariantInt(v),
-            WindowInfoType::InvariantFloat(v) => WindowInfoType::InvariantFloat(v),
+            WindowInfoType::InvariantInt(v) => WindowInfoType::InvariantInt(*v),
+            WindowInfoType::InvariantFloat(v) => WindowInfoType::InvariantFloat(*v),
+            WindowInfoType::Color(color) => WindowInfoType::Color(*color),
+            WindowInfoType::ColorPalette(palette) => WindowInfoType::ColorPalette(palette.clone()),
+            WindowInfoType::PosList(list) => WindowInfoType::PosList(list.iter().map(|pos| pos.scale(factor)).collect()),
         };
         result
     }
@@ -0,0 +1,25 @@
+use crate::window_info::WindowInfoType;
+
+/// The resolved values of a window info struct with their window info keys, derived by `YasWindowInfo`
+pub trait WindowInfoValues {
+    /// The values with every key prefixed by `prefix`
+    fn window_info_values_with_prefix(&self, prefix: &str) -> Vec<(String, WindowInfoType)>;
+
+    fn window_info_values(&self) -> Vec<(String, WindowInfoType)> {
+        self.window_info_values_with_prefix("")
+    }
+}
+
+/// Append the values of `other` whose keys are not in `values` yet,
+/// for a scanner reading several window info structs that share some keys
+pub fn merge_window_info_values(
+    mut values: Vec<(String, WindowInfoType)>,
+    other: Vec<(String, WindowInfoType)>,
+) -> Vec<(String, WindowInfoType)> {
+    for (key, value) in other {
+        if values.iter().all(|(k, _)| *k != key) {
+            values.push((key, value));
+        }
+    }
+    values
+}
@@ -0,0 +1,16 @@
+//! onnxruntime is loaded at runtime, a missing library must be an error instead of a crash
+#![cfg(feature = "ort")]
+
+use yas_core::ocr::{OcrBackend, OcrError};
+
+#[test]
+fn missing_onnxruntime_is_an_error() {
+    let missing = std::env::temp_dir().join("yas-missing-onnxruntime").join("libonnxruntime");
+    std::env::set_var("ORT_DYLIB_PATH", &missing);
+
+    let error = OcrBackend::Ort.load_session(&[], &[None, Some(1)]).err().expect("onnxruntime should be missing");
+    assert!(matches!(error.downcast_ref::<OcrError>(), Some(OcrError::ModelLoad(_))), "{}", error);
+
+    // falls back to tract if it is compiled in, which cannot load the empty model either
+    assert!(OcrBackend::Auto.load_session(&[], &[None, Some(1)]).is_err());
+}